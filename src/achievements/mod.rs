@@ -0,0 +1,272 @@
+pub mod toasts;
+
+pub use self::toasts::Toasts;
+
+use std::path::Path;
+
+use json::JsonValue;
+
+use settings::res;
+use config;
+use json_file;
+use score::ScoreType;
+use score::lifetime::{ LevelStats, StatsEvent };
+
+// When an achievement is checked, and which stats its bounds are checked against
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Scope {
+  Level,     // Clearing a level, with the stats of that attempt
+  Game,      // Beating the game, with the stats of the run from its first level
+  Lifetime,  // Whenever the lifetime stats change, with the totals of all levels
+}
+
+impl Scope {
+  fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "level"    => Scope::Level,
+      "game"     => Scope::Game,
+      "lifetime" => Scope::Lifetime,
+      _          => return None,
+    })
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Stat {
+  Time,  // In whole seconds
+  Jumps,
+  Commands,
+  Resets,
+  Saved,
+  ChildrenSaved,
+  Cleared,
+}
+
+impl Stat {
+  fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "time"           => Stat::Time,
+      "jumps"          => Stat::Jumps,
+      "commands"       => Stat::Commands,
+      "resets"         => Stat::Resets,
+      "saved"          => Stat::Saved,
+      "children_saved" => Stat::ChildrenSaved,
+      "cleared"        => Stat::Cleared,
+      _                => return None,
+    })
+  }
+
+  fn of(&self, stats: &LevelStats) -> ScoreType {
+    match self {
+      Stat::Time          => (stats.ticks as f64 / config::with( |c| c.ups ) as f64) as ScoreType,
+      Stat::Jumps         => stats.jumps,
+      Stat::Commands      => stats.commands(),
+      Stat::Resets        => stats.resets,
+      Stat::Saved         => stats.saved,
+      Stat::ChildrenSaved => stats.children_saved,
+      Stat::Cleared       => stats.cleared,
+    }
+  }
+}
+
+struct Achievement {
+  id:          String,
+  name:        String,
+  description: String,
+  secret:      bool,  // Its name and description stay hidden until it is unlocked
+  scope:       Scope,
+  min:         Vec<(Stat, ScoreType)>,
+  max:         Vec<(Stat, ScoreType)>,
+}
+
+impl Achievement {
+  fn from_json(json: &JsonValue) -> Result<Self, String> {
+    let id = json["id"].as_str().ok_or("Achievement has no `id`".to_string())?;
+    let scope = json["when"].as_str()
+      .and_then(Scope::from_name)
+      .ok_or(format!("Achievement {} has an invalid `when`", id))?;
+    Ok(Self {
+      id:          id.to_string(),
+      name:        json["name"].as_str().unwrap_or(id).to_string(),
+      description: json["description"].as_str().unwrap_or("").to_string(),
+      secret:      json["secret"].as_bool().unwrap_or(false),
+      scope,
+      min:         bounds_from_json(&json["min"]).ok_or(format!("Achievement {} has an invalid `min`", id))?,
+      max:         bounds_from_json(&json["max"]).ok_or(format!("Achievement {} has an invalid `max`", id))?,
+    })
+  }
+
+  fn has_bounds(&self) -> bool {
+    !self.min.is_empty() || !self.max.is_empty()
+  }
+
+  fn is_met(&self, stats: &LevelStats) -> bool {
+    self.min.iter().all( |&(stat, min)| stat.of(stats) >= min )
+      && self.max.iter().all( |&(stat, max)| stat.of(stats) <= max )
+  }
+}
+
+// `{ "stat": value }`, e.g. `{ "children_saved": 3 }`; `None` if a stat is unknown
+fn bounds_from_json(json: &JsonValue) -> Option<Vec<(Stat, ScoreType)>> {
+  if json.is_null() {
+    return Some(Vec::new());
+  }
+  if !json.is_object() {
+    return None;
+  }
+  json.entries()
+    .map( |(name, value)| Some((Stat::from_name(name)?, value.as_u32()?)) )  // as ScoreType
+    .collect()
+}
+
+// An achievement as the player sees it
+pub struct AchievementInfo {
+  pub name:        String,
+  pub description: String,
+  pub unlocked:    bool,
+}
+
+// The achievements from `resources/achievements.json`, e.g.
+// `{ "achievements": [{ "id": "hands_off", "name": "Hands Off", "description": "...",
+//                       "when": "level", "min": { "children_saved": 1 }, "max": { "commands": 0 } }] }`.
+// `when` is `level`, `game` or `lifetime`; `min` and `max` bound the stats of that scope:
+// `time`, `jumps`, `commands`, `resets`, `saved`, `children_saved` and `cleared`.
+// The unlocked achievements are saved with the profile.
+pub struct Achievements {
+  achievements: Vec<Achievement>,
+  unlocked:     Vec<String>,
+  new_unlocked: Vec<usize>,
+  attempt:      LevelStats,          // Since the level was started or reset
+  run:          Option<LevelStats>,  // Since the level pack's first level, None if it was started elsewhere
+}
+
+impl Achievements {
+  // Invalid achievements are left out, so they don't keep the game from starting
+  pub fn load() -> Self {
+    let achievements = match json_file::read(Path::new(res::ACHIEVEMENTS)) {
+      Ok(data) => data["achievements"].members()
+        .filter_map( |json| match Achievement::from_json(json) {
+          Ok(achievement) => Some(achievement),
+          Err(e)          => {
+            eprintln!("{}", e);
+            None
+          }
+        })
+        .collect(),
+      Err(e)   => {
+        eprintln!("Couldn't load achievements {}: {}", res::ACHIEVEMENTS, e);
+        Vec::new()
+      }
+    };
+    Self {
+      achievements,
+      unlocked:     Vec::new(),
+      new_unlocked: Vec::new(),
+      attempt:      LevelStats::default(),
+      run:          None,
+    }
+  }
+
+  pub fn load_unlocked(&mut self, json: &JsonValue) {
+    self.unlocked = json.members()
+      .filter_map( |id| id.as_str().map( |id| id.to_string() ) )
+      .collect();
+    self.new_unlocked.clear();
+  }
+
+  pub fn unlocked_json(&self) -> JsonValue {
+    self.unlocked.clone().into()
+  }
+
+  pub fn start_level(&mut self, is_first: bool) {
+    self.attempt = LevelStats::default();
+    if is_first {
+      self.run = Some(LevelStats::default());
+    }
+  }
+
+  pub fn end_run(&mut self) {
+    self.run = None;
+  }
+
+  pub fn tick(&mut self) {
+    self.attempt.ticks += 1;
+    if let Some(run) = &mut self.run {
+      run.ticks += 1;
+    }
+  }
+
+  pub fn record(&mut self, event: StatsEvent) {
+    self.attempt.record(event);
+    if let Some(run) = &mut self.run {
+      run.record(event);
+    }
+    match event {
+      StatsEvent::Reset   => self.attempt = LevelStats::default(),
+      StatsEvent::Cleared => {
+        let attempt = self.attempt.clone();
+        self.check(Scope::Level, Some(&attempt));
+      },
+      _                   => (),
+    }
+  }
+
+  // Beating the game without a run (e.g. after continuing from the title)
+  // only unlocks the achievements that don't need its stats
+  pub fn beat_game(&mut self) {
+    let run = self.run.clone();
+    self.check(Scope::Game, run.as_ref());
+  }
+
+  pub fn check_lifetime(&mut self, total: &LevelStats) {
+    self.check(Scope::Lifetime, Some(total));
+  }
+
+  fn check(&mut self, scope: Scope, stats: Option<&LevelStats>) {
+    let met = self.achievements.iter().enumerate()
+      .filter( |(_, achievement)| achievement.scope == scope && !self.unlocked.contains(&achievement.id) )
+      .filter( |(_, achievement)| match stats {
+        Some(stats) => achievement.is_met(stats),
+        None        => !achievement.has_bounds(),
+      })
+      .map( |(i, _)| i )
+      .collect::<Vec<usize>>();
+    for i in met {
+      self.unlocked.push(self.achievements[i].id.clone());
+      self.new_unlocked.push(i);
+    }
+  }
+
+  pub fn has_new_unlocked(&self) -> bool {
+    !self.new_unlocked.is_empty()
+  }
+
+  // The achievements unlocked since the last call, for the toasts
+  pub fn drain_new_unlocked(&mut self) -> Vec<AchievementInfo> {
+    let new_unlocked = self.new_unlocked.drain(..).collect::<Vec<usize>>();
+    new_unlocked.into_iter().map( |i| self.info(i) ).collect()
+  }
+
+  pub fn infos(&self) -> Vec<AchievementInfo> {
+    (0 .. self.achievements.len()).map( |i| self.info(i) ).collect()
+  }
+
+  fn info(&self, index: usize) -> AchievementInfo {
+    let achievement = &self.achievements[index];
+    let unlocked    = self.unlocked.contains(&achievement.id);
+    if achievement.secret && !unlocked {
+      AchievementInfo {
+        name:        "???".to_string(),
+        description: "A secret achievement.".to_string(),
+        unlocked,
+      }
+    } else {
+      AchievementInfo {
+        name:        achievement.name.clone(),
+        description: achievement.description.clone(),
+        unlocked,
+      }
+    }
+  }
+}
+
@@ -0,0 +1,94 @@
+use std::time::{ Instant, Duration };
+
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use settings::res;
+use settings::achievements::toasts::*;
+use menu::stats::prelude::{ StatsText, TextOrigin };
+use color_rect::prelude::*;
+use layout::Layout;
+use super::AchievementInfo;
+
+struct Toast {
+  rect:     ColorRect,
+  texts:    Vec<StatsText>,
+  shown_at: Instant,
+}
+
+// Pops up in the bottom right corner for every unlocked achievement, one after another.
+pub struct Toasts {
+  queue:       Vec<AchievementInfo>,
+  shown:       Option<Toast>,
+  window_size: Size,
+}
+
+impl Toasts {
+  pub fn new(window_size: &Size) -> Self {
+    Self {
+      queue:       Vec::new(),
+      shown:       None,
+      window_size: window_size.clone(),
+    }
+  }
+
+  pub fn push(&mut self, unlocked: Vec<AchievementInfo>) {
+    self.queue.extend(unlocked);
+  }
+
+  pub fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let expired = self.shown.as_ref()
+      .map( |toast| toast.shown_at.elapsed() > Duration::from_secs(DURATION) )
+      .unwrap_or(false);
+    if expired {
+      self.shown = None;
+    }
+    if self.shown.is_none() && !self.queue.is_empty() {
+      let achievement = self.queue.remove(0);
+      self.shown = Some(self.new_toast(ctx, &achievement)?);
+    }
+    Ok(())
+  }
+
+  fn new_toast(&self, ctx: &mut Context, achievement: &AchievementInfo) -> GameResult<Toast> {
+    let title_font = Font::new(ctx, res::fonts::DEFAULT, TITLE_FONT_SIZE)?;
+    let font       = Font::new(ctx, res::fonts::DEFAULT, FONT_SIZE)?;
+    let title      = Text::new(ctx, &format!("Achievement Unlocked: {}", achievement.name), &title_font)?;
+    let text       = Text::new(ctx, &achievement.description, &font)?;
+    let size       = Size::new(
+      title.width().max(text.width()) as NumType + PADDING * 2.0,
+      (title.height() + text.height()) as NumType + PADDING * 3.0
+    );
+    let rect = ColorRectBuilder::new()
+      .point(Layout::new(&self.window_size).at(Origin::BottomRight, -PADDING, -PADDING))
+      .size(size)
+      .origin(Origin::BottomRight)
+      .color(BG_COLOR)
+      .build();
+    let title_point = rect.top_left() + Point::new(PADDING, PADDING);
+    let text_point  = title_point.clone() + Point::new(0.0, title.height() as NumType + PADDING);
+    Ok(Toast {
+      texts:    vec![
+        StatsText::new(title, title_point, TextOrigin::Left, Some(TITLE_COLOR)),
+        StatsText::new(text,  text_point,  TextOrigin::Left, Some(FONT_COLOR)),
+      ],
+      rect,
+      shown_at: Instant::now(),
+    })
+  }
+
+  pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(toast) = &self.shown {
+      toast.rect.draw(ctx)?;
+      for text in &toast.texts {
+        text.draw(ctx)?;
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+use ggez::event::Keycode;
+
+use settings::{ player, camera, child, level, level_manager };
+
+// Everything the player can do ingame. Keyboard and gamepad input is translated to actions,
+// so the game never checks raw keys or buttons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+  MoveLeft,
+  MoveRight,
+  Jump,
+  CameraUp,
+  CameraDown,
+  CameraLeft,
+  CameraRight,
+  CenterCamera,
+  Mute,
+  Pause,
+  Reset,
+  Next,
+  ToTitle,
+  PrevChild,
+  NextChild,
+  SelectChild1,
+  SelectChild2,
+  SelectChild3,
+  CommandLeft,
+  CommandRight,
+}
+
+impl Action {
+  pub fn all() -> Vec<Action> {
+    use self::Action::*;
+    vec![
+      MoveLeft, MoveRight, Jump,
+      CameraUp, CameraDown, CameraLeft, CameraRight, CenterCamera,
+      Mute, Pause, Reset, Next, ToTitle,
+      PrevChild, NextChild, SelectChild1, SelectChild2, SelectChild3,
+      CommandLeft, CommandRight,
+    ]
+  }
+
+  // The action's key in the config file
+  pub fn id(&self) -> &'static str {
+    use self::Action::*;
+    match self {
+      MoveLeft     => "left",
+      MoveRight    => "right",
+      Jump         => "jump",
+      CameraUp     => "camera_up",
+      CameraDown   => "camera_down",
+      CameraLeft   => "camera_left",
+      CameraRight  => "camera_right",
+      CenterCamera => "center_camera",
+      Mute         => "mute",
+      Pause        => "pause",
+      Reset        => "reset",
+      Next         => "next",
+      ToTitle      => "to_title",
+      PrevChild    => "prev_child",
+      NextChild    => "next_child",
+      SelectChild1 => "select_child_1",
+      SelectChild2 => "select_child_2",
+      SelectChild3 => "select_child_3",
+      CommandLeft  => "command_left",
+      CommandRight => "command_right",
+    }
+  }
+
+  // Actions without a default key are only on the gamepad, until a key is bound to them
+  pub fn default_key(&self) -> Option<Keycode> {
+    use self::Action::*;
+    match self {
+      MoveLeft     => Some(player::controls::LEFT),
+      MoveRight    => Some(player::controls::RIGHT),
+      Jump         => Some(player::controls::JUMP),
+      CameraUp     => Some(camera::controls::UP),
+      CameraDown   => Some(camera::controls::DOWN),
+      CameraLeft   => Some(camera::controls::LEFT),
+      CameraRight  => Some(camera::controls::RIGHT),
+      CenterCamera => Some(level::CENTER_KEY),
+      Mute         => Some(level_manager::controls::MUTE),
+      Pause        => Some(level_manager::controls::PAUSE),
+      Reset        => Some(level_manager::controls::RESET),
+      Next         => Some(level_manager::controls::NEXT),
+      ToTitle      => Some(level_manager::controls::TO_TITLE),
+      PrevChild    => None,
+      NextChild    => Some(child::controls::NEXT),
+      SelectChild1 => Some(child::controls::SELECT_1),
+      SelectChild2 => Some(child::controls::SELECT_2),
+      SelectChild3 => Some(child::controls::SELECT_3),
+      CommandLeft  => Some(child::controls::COMMAND_LEFT),
+      CommandRight => Some(child::controls::COMMAND_RIGHT),
+    }
+  }
+}
+
+impl fmt::Display for Action {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    use self::Action::*;
+    write!(f, "{}", match self {
+      MoveLeft     => "Move Left",
+      MoveRight    => "Move Right",
+      Jump         => "Jump",
+      CameraUp     => "Camera Up",
+      CameraDown   => "Camera Down",
+      CameraLeft   => "Camera Left",
+      CameraRight  => "Camera Right",
+      CenterCamera => "Center Camera",
+      Mute         => "Mute",
+      Pause        => "Pause",
+      Reset        => "Reset Level",
+      Next         => "Next Level",
+      ToTitle      => "To Title",
+      PrevChild    => "Previous Child",
+      NextChild    => "Next Child",
+      SelectChild1 => "Select Child 1",
+      SelectChild2 => "Select Child 2",
+      SelectChild3 => "Select Child 3",
+      CommandLeft  => "Command Left",
+      CommandRight => "Command Right",
+    })
+  }
+}
@@ -14,6 +14,8 @@ use ggez::{
 
 use noframe::geo::prelude::*;
 
+use settings::res::MISSING_IMAGE;
+
 #[derive(Clone)]
 pub enum Facing {
   Left,
@@ -58,6 +60,25 @@ impl Animation {
     Self::new_with_details(ctx, image_filepaths, image_update_intervals_ms, true)
   }
 
+  // For images named in level files, which may be missing;
+  // those are reported and replaced with the missing image instead of panicking.
+  pub fn from_level_images(ctx: &mut Context, dir: &str, names: &Vec<String>, interval_ms: u64) -> Self {
+    let mut filepaths = names.iter().map( |name| {
+      let filepath = ::join_str(dir, name);
+      if ctx.filesystem.is_file(&filepath) {
+        filepath
+      } else {
+        eprintln!("Couldn't find image: {}", filepath);
+        MISSING_IMAGE.to_string()
+      }
+    }).collect::<Vec<String>>();
+    if filepaths.is_empty() {
+      filepaths.push(MISSING_IMAGE.to_string());
+    }
+    let intervals = vec![interval_ms; filepaths.len()];
+    Self::new(ctx, filepaths, intervals)
+  }
+
   fn new_with_details(
     ctx:                       &mut Context,
     image_filepaths:           Vec<String>,
@@ -0,0 +1,120 @@
+use ggez::{
+  Context,
+  GameResult,
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+
+use settings::res::BACKGROUND_IMAGES;
+use settings::background::*;
+use animation::prelude::*;
+
+struct Layer {
+  animation: Animation,
+  parallax:  f32,
+}
+
+// A level's background, made of layers drawn back to front.
+// Each layer scrolls with the camera by its parallax factor and repeats to fill the screen.
+pub struct Background {
+  layers:      Vec<Layer>,
+  window_size: Size,
+}
+
+impl Background {
+  // `data` is the level's optional `background` array, e.g.
+  // `[{ "images": ["bg1.1.png"], "parallax": 0.0 }, { "images": ["fog.png"], "parallax": 0.5, "interval": 500 }]`.
+  // Without it, the level uses its chapter's background image.
+  pub fn new(ctx: &mut Context, window_size: &Size, data: &JsonValue, level_index: usize) -> Self {
+    let layers = if data.is_array() {
+      data.members().map( |layer| {
+        let images = layer["images"].members()
+          .filter_map( |image| image.as_str().map( |image| image.to_string() ) )
+          .collect();
+        Layer {
+          animation: Animation::from_level_images(
+            ctx,
+            BACKGROUND_IMAGES,
+            &images,
+            layer["interval"].as_u64().unwrap_or(DEFAULT_INTERVAL)
+          ),
+          parallax:  layer["parallax"].as_f32().unwrap_or(DEFAULT_PARALLAX),
+        }
+      }).collect()
+    } else {
+      vec![Layer {
+        animation: Animation::new(
+          ctx,
+          vec![::join_str(BACKGROUND_IMAGES, default_image(level_index))],
+          vec![DEFAULT_INTERVAL]
+        ),
+        parallax:  DEFAULT_PARALLAX,
+      }]
+    };
+    Self {
+      layers,
+      window_size: window_size.clone(),
+    }
+  }
+
+  pub fn update(&mut self) -> GameResult<()> {
+    for layer in &mut self.layers {
+      layer.animation.update()?;
+    }
+    Ok(())
+  }
+
+  // `camera` is the camera's top left point in the level
+  pub fn draw(&self, ctx: &mut Context, camera: &Point) -> GameResult<()> {
+    let size = &self.window_size;
+    for layer in &self.layers {
+      // Wrap the layer's offset into a single screen, then draw the neighbouring tiles that come into view
+      let offset = Point::new(
+        -wrap(camera.x * layer.parallax, size.w),
+        -wrap(camera.y * layer.parallax, size.h)
+      );
+      let columns = if offset.x < 0.0 { 2 } else { 1 };
+      let rows    = if offset.y < 0.0 { 2 } else { 1 };
+      for column in 0 .. columns {
+        for row in 0 .. rows {
+          let point = offset.clone() + Point::new(size.w * column as NumType, size.h * row as NumType);
+          layer.animation.draw(ctx, &point, size, &Facing::Right)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+fn wrap(value: NumType, max: NumType) -> NumType {
+  ((value % max) + max) % max
+}
+
+fn default_image(level_index: usize) -> &'static str {
+  let chapter = level_index / 5;
+  let sub = level_index % 5;
+  match chapter {
+    0 => match sub {
+      0 => "bg1.1.png",
+      1 => "bg1.2.png",
+      2 => "bg1.3.png",
+      3 => "bg1.4.png",
+      _ => "bg1.5.png",
+    },
+    1 => match sub {
+      0 => "bg2.1.png",
+      1 => "bg2.2.png",
+      2 => "bg2.3.png",
+      3 => "bg2.4.png",
+      _ => "bg2.5.png",
+    },
+    2 => match sub {
+      0 => "bg3.1.png",
+      1 => "bg3.2.png",
+      2 => "bg3.3.png",
+      3 => "bg3.4.png",
+      _ => "bg3.5.png",
+    },
+    _ => "bg4.1.png",
+  }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use ggez::event::Keycode;
+use json::JsonValue;
+
+use action::Action;
+use super::helpers::*;
+
+// Every action is bound to at most one key.
+#[derive(Clone, Debug)]
+pub struct Bindings {
+  keys: HashMap<Action, Keycode>,
+}
+
+impl Default for Bindings {
+  fn default() -> Self {
+    Self {
+      keys: Action::all().into_iter()
+        .filter_map( |action| action.default_key().map( |key| (action, key) ))
+        .collect(),
+    }
+  }
+}
+
+impl Bindings {
+  pub fn from_json(data: &JsonValue) -> Self {
+    Self {
+      keys: Action::all().into_iter()
+        .filter_map( |action| {
+          keycode_from_json(&data[action.id()], action.default_key()).map( |key| (action, key) )
+        })
+        .collect(),
+    }
+  }
+
+  pub fn to_json(&self) -> JsonValue {
+    let mut data = object!{};
+    for action in Action::all() {
+      data[action.id()] = keycode_to_json(self.key(action));
+    }
+    data
+  }
+
+  pub fn key(&self, action: Action) -> Option<Keycode> {
+    self.keys.get(&action).cloned()
+  }
+
+  pub fn action(&self, keycode: Keycode) -> Option<Action> {
+    Action::all().into_iter()
+      .find( |&action| self.key(action) == Some(keycode) )
+  }
+
+  pub fn actions(&self, keycodes: &Vec<Keycode>) -> Vec<Action> {
+    keycodes.iter()
+      .filter_map( |&keycode| self.action(keycode) )
+      .collect()
+  }
+
+  // If the key is already bound to another action, that action gets this action's old key.
+  pub fn bind(&mut self, action: Action, keycode: Keycode) {
+    let old_key = self.key(action);
+    if let Some(other) = self.action(keycode) {
+      match old_key {
+        Some(old_key) => { self.keys.insert(other, old_key); },
+        None          => { self.keys.remove(&other); },
+      }
+    }
+    self.keys.insert(action, keycode);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bind_gives_the_conflicting_action_the_old_key() {
+    let mut bindings = Bindings::default();
+    let left = bindings.key(Action::MoveLeft).expect("MoveLeft should have a default key");
+    let jump = bindings.key(Action::Jump).expect("Jump should have a default key");
+    bindings.bind(Action::Jump, left);
+    assert_eq!(bindings.key(Action::Jump),     Some(left));
+    assert_eq!(bindings.key(Action::MoveLeft), Some(jump));
+    assert_eq!(bindings.action(left),          Some(Action::Jump));
+  }
+
+  #[test]
+  fn bind_unbinds_the_conflicting_action_without_an_old_key() {
+    let mut bindings = Bindings::default();
+    let next = bindings.key(Action::NextChild).expect("NextChild should have a default key");
+    assert_eq!(bindings.key(Action::PrevChild), None);
+    bindings.bind(Action::PrevChild, next);
+    assert_eq!(bindings.key(Action::PrevChild), Some(next));
+    assert_eq!(bindings.key(Action::NextChild), None);
+  }
+
+  #[test]
+  fn bind_to_a_free_key_leaves_the_other_actions_alone() {
+    let mut bindings = Bindings::default();
+    let left = bindings.key(Action::MoveLeft);
+    bindings.bind(Action::Jump, Keycode::F12);
+    assert_eq!(bindings.key(Action::Jump),     Some(Keycode::F12));
+    assert_eq!(bindings.key(Action::MoveLeft), left);
+  }
+}
@@ -0,0 +1,46 @@
+use ggez::event::Keycode;
+use json::JsonValue;
+
+use super::Physics;
+
+// Keys are written by their SDL names, e.g. "A", "Space" or "Return".
+// An unbound key is written as `false`.
+pub fn keycode_from_json(value: &JsonValue, default: Option<Keycode>) -> Option<Keycode> {
+  if let Some(false) = value.as_bool() {
+    return None;
+  }
+  if let Some(name) = value.as_str() {
+    if let Some(keycode) = Keycode::from_name(name) {
+      return Some(keycode);
+    }
+    eprintln!("Unknown key '{}' in config, using the default", name);
+  }
+  default
+}
+
+pub fn keycode_to_json(keycode: Option<Keycode>) -> JsonValue {
+  match keycode {
+    Some(keycode) => keycode.name().into(),
+    None          => false.into(),
+  }
+}
+
+pub fn positive_f32(value: &JsonValue, default: f32) -> f32 {
+  match value.as_f32() {
+    Some(n) if n > 0.0 => n,
+    _                  => default,
+  }
+}
+
+pub fn physics_from_json(data: &JsonValue, defaults: Physics) -> Physics {
+  Physics {
+    speed_increase:     data["speed_increase"].as_f32().unwrap_or(defaults.speed_increase),
+    speed_decrease_x:   data["speed_decrease_x"].as_f32().unwrap_or(defaults.speed_decrease_x),
+    speed_decrease_y:   data["speed_decrease_y"].as_f32().unwrap_or(defaults.speed_decrease_y),
+    max_velocity_x:     data["max_velocity_x"].as_f32().unwrap_or(defaults.max_velocity_x),
+    max_velocity_y:     data["max_velocity_y"].as_f32().unwrap_or(defaults.max_velocity_y),
+    gravity_increase:   data["gravity_increase"].as_f32().unwrap_or(defaults.gravity_increase),
+    jump_speed:         data["jump_speed"].as_f32().unwrap_or(defaults.jump_speed),
+    jump_kill_velocity: data["jump_kill_velocity"].as_f32().unwrap_or(defaults.jump_kill_velocity),
+  }
+}
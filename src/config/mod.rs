@@ -0,0 +1,251 @@
+mod helpers;
+mod bindings;
+
+pub use self::bindings::Bindings;
+
+use std::cell::RefCell;
+use std::path::{ Path, PathBuf };
+
+use ggez::{
+  Context,
+  GameResult,
+  GameError,
+  graphics,
+  conf::{ WindowMode, FullscreenType },
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+
+use settings::{ meta, game, player, child };
+use settings::config::*;
+use self::helpers::*;
+use viewport;
+use json_file;
+
+thread_local! {
+  static CONFIG: RefCell<Config> = RefCell::new(Config::default());
+}
+
+// The current config, loaded at startup and changed by the settings menus.
+// Reading a value doesn't need a copy of the whole config.
+pub fn with<T, F: FnOnce(&Config) -> T>(f: F) -> T {
+  CONFIG.with( |config| f(&config.borrow()) )
+}
+
+// A copy of the current config, to be changed and `set` again.
+pub fn get() -> Config {
+  CONFIG.with( |config| config.borrow().clone() )
+}
+
+pub fn set(config: Config) {
+  CONFIG.with( |c| *c.borrow_mut() = config );
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Physics {
+  pub speed_increase:     f32,
+  pub speed_decrease_x:   f32,
+  pub speed_decrease_y:   f32,
+  pub max_velocity_x:     f32,
+  pub max_velocity_y:     f32,
+  pub gravity_increase:   f32,
+  // Children can't jump
+  pub jump_speed:         f32,
+  pub jump_kill_velocity: f32,
+}
+
+impl Physics {
+  pub fn player() -> Self {
+    Self {
+      speed_increase:     player::SPEED_INCREASE,
+      speed_decrease_x:   player::SPEED_DECREASE_X,
+      speed_decrease_y:   player::SPEED_DECREASE_Y,
+      max_velocity_x:     player::MAX_VELOCITY_X,
+      max_velocity_y:     player::MAX_VELOCITY_Y,
+      gravity_increase:   player::GRAVITY_INCREASE,
+      jump_speed:         player::JUMP_SPEED,
+      jump_kill_velocity: player::JUMP_KILL_VELOCITY,
+    }
+  }
+
+  pub fn child() -> Self {
+    Self {
+      speed_increase:     child::SPEED_INCREASE,
+      speed_decrease_x:   child::SPEED_DECREASE_X,
+      speed_decrease_y:   child::SPEED_DECREASE_Y,
+      max_velocity_x:     child::MAX_VELOCITY_X,
+      max_velocity_y:     child::MAX_VELOCITY_Y,
+      gravity_increase:   child::GRAVITY_INCREASE,
+      jump_speed:         0.0,
+      jump_kill_velocity: 0.0,
+    }
+  }
+}
+
+// User settings, read from the config file in the user config directory.
+// Every value that is missing from the file falls back to its constant in `settings`.
+#[derive(Clone, Debug)]
+pub struct Config {
+  filepath:            Option<PathBuf>,
+  // The virtual resolution; the game is always laid out and drawn at this size
+  pub window_size:     Size,
+  pub window_scale:    f32,
+  pub fullscreen:      bool,
+  pub resizable:       bool,
+  pub integer_scaling: bool,
+  pub volume:          f32,
+  pub sfx_volume:      f32,
+  pub muted:           bool,
+  pub ups:             f32,
+  pub camera_follow:   bool,
+  pub speedrun:        bool,
+  pub ghosts:          bool,
+  pub hot_reload:      bool,
+  pub bindings:        Bindings,
+  pub player:          Physics,
+  pub child:           Physics,
+  // Physics overrides are kept as they were written,
+  // so saving the config doesn't pin the default physics values.
+  physics:             JsonValue,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      filepath:        None,
+      window_size:     meta::WINDOW_SIZE,
+      window_scale:    game::WINDOW_SCALE,
+      fullscreen:      false,
+      resizable:       game::RESIZABLE,
+      integer_scaling: game::INTEGER_SCALING,
+      volume:          game::VOLUME,
+      sfx_volume:      game::SFX_VOLUME,
+      muted:           game::MUTED,
+      ups:             game::UPS,
+      camera_follow:   game::CAMERA_FOLLOW,
+      speedrun:        game::SPEEDRUN,
+      ghosts:          game::GHOSTS,
+      hot_reload:      game::HOT_RELOAD,
+      bindings:        Bindings::default(),
+      player:          Physics::player(),
+      child:           Physics::child(),
+      physics:         object!{},
+    }
+  }
+}
+
+impl Config {
+  // A missing config file is created with the default values.
+  // An unreadable one is left alone, so it can be fixed by hand.
+  pub fn load(config_dir: &Path) -> Self {
+    let filepath = config_dir.join(FILENAME);
+    if !filepath.exists() {
+      let config = Self { filepath: Some(filepath), ..Self::default() };
+      if let Err(e) = config.save() {
+        eprintln!("Couldn't write default config file: {}", e);
+      }
+      return config;
+    }
+    match read_json(&filepath) {
+      Ok(data) => Self::from_json(filepath, &data),
+      Err(e)   => {
+        eprintln!("Couldn't read config file {}, using defaults: {}", filepath.display(), e);
+        Self::default()
+      }
+    }
+  }
+
+  fn from_json(filepath: PathBuf, data: &JsonValue) -> Self {
+    let defaults = Self::default();
+    let window   = &data["window"];
+    let audio    = &data["audio"];
+    let physics  = &data["physics"];
+    Self {
+      filepath:        Some(filepath),
+      window_size:     Size::new(
+        positive_f32(&window["width"],  defaults.window_size.w),
+        positive_f32(&window["height"], defaults.window_size.h),
+      ),
+      window_scale:    positive_f32(&window["scale"], defaults.window_scale),
+      fullscreen:      window["fullscreen"].as_bool().unwrap_or(defaults.fullscreen),
+      resizable:       window["resizable"].as_bool().unwrap_or(defaults.resizable),
+      integer_scaling: window["integer_scaling"].as_bool().unwrap_or(defaults.integer_scaling),
+      volume:          audio["volume"].as_f32().unwrap_or(defaults.volume).max(0.0).min(1.0),
+      sfx_volume:      audio["sfx_volume"].as_f32().unwrap_or(defaults.sfx_volume).max(0.0).min(1.0),
+      muted:           audio["muted"].as_bool().unwrap_or(defaults.muted),
+      ups:             positive_f32(&data["ups"], defaults.ups),
+      camera_follow:   data["camera"]["follow"].as_bool().unwrap_or(defaults.camera_follow),
+      speedrun:        data["speedrun"].as_bool().unwrap_or(defaults.speedrun),
+      ghosts:          data["ghosts"].as_bool().unwrap_or(defaults.ghosts),
+      hot_reload:      data["hot_reload"].as_bool().unwrap_or(defaults.hot_reload),
+      bindings:        Bindings::from_json(&data["controls"]),
+      player:          physics_from_json(&physics["player"], defaults.player),
+      child:           physics_from_json(&physics["child"],  defaults.child),
+      physics:         if physics.is_object() { physics.clone() } else { object!{} },
+    }
+  }
+
+  fn to_json(&self) -> JsonValue {
+    object!{
+      "window" => object!{
+        "width"           => self.window_size.w,
+        "height"          => self.window_size.h,
+        "scale"           => self.window_scale,
+        "fullscreen"      => self.fullscreen,
+        "resizable"       => self.resizable,
+        "integer_scaling" => self.integer_scaling
+      },
+      "audio" => object!{
+        "volume"     => self.volume,
+        "sfx_volume" => self.sfx_volume,
+        "muted"      => self.muted
+      },
+      "ups" => self.ups,
+      "camera" => object!{
+        "follow" => self.camera_follow
+      },
+      "speedrun" => self.speedrun,
+      "ghosts" => self.ghosts,
+      "hot_reload" => self.hot_reload,
+      "controls" => self.bindings.to_json(),
+      "physics" => self.physics.clone()
+    }
+  }
+
+  pub fn save(&self) -> GameResult<()> {
+    let filepath = if let Some(filepath) = &self.filepath {
+      filepath
+    } else {
+      return Err(GameError::from("Config has no file to save to".to_string()));
+    };
+    json_file::write_str(filepath, &self.to_json().pretty(2))
+  }
+
+  // The window starts out as `window_size` scaled by `window_scale`,
+  // but the game is always drawn at `window_size`, see `viewport`.
+  pub fn window_mode(&self) -> WindowMode {
+    WindowMode::default()
+      .dimensions(
+        (self.window_size.w * self.window_scale) as u32,
+        (self.window_size.h * self.window_scale) as u32
+      )
+      .fullscreen_type(if self.fullscreen { FullscreenType::Desktop } else { FullscreenType::Off })
+  }
+
+  pub fn apply_window_mode(&self, ctx: &mut Context) -> GameResult<()> {
+    graphics::set_mode(ctx, self.window_mode())?;
+    viewport::apply(ctx, self)
+  }
+
+  pub fn update_interval_ms(&self) -> u64 {
+    (1.0 / self.ups * 1000.0) as u64
+  }
+}
+
+fn read_json(filepath: &Path) -> GameResult<JsonValue> {
+  let data = json_file::read(filepath)?;
+  if !data.is_object() {
+    return Err(GameError::from("Config must be an object".to_string()));
+  }
+  Ok(data)
+}
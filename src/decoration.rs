@@ -0,0 +1,71 @@
+use ggez::{
+  Context,
+  GameResult,
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use settings::decoration::*;
+use animation::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecorationLayer {
+  Back,   // Behind walls and persons
+  Front,  // In front of everything in the level
+}
+
+// An animated prop placed in a level, which nothing collides with.
+// In level JSON, `additional` holds the `images` (in `/images/decorations/`),
+// the optional frame `interval` in ms and the optional `layer`, "back" (default) or "front".
+pub struct Decoration {
+  point:     Point,
+  size:      Size,
+  origin:    Origin,
+  animation: Animation,
+  pub layer: DecorationLayer,
+}
+
+impl Decoration {
+  pub fn new(ctx: &mut Context, point: Point, size: Size, additional: &JsonValue) -> Self {
+    let images = additional["images"].members()
+      .filter_map( |image| image.as_str().map( |image| image.to_string() ) )
+      .collect();
+    Self {
+      point,
+      size,
+      origin:    Origin::TopLeft,
+      animation: Animation::from_level_images(
+        ctx,
+        IMAGES,
+        &images,
+        additional["interval"].as_u64().unwrap_or(DEFAULT_INTERVAL)
+      ),
+      layer:     match additional["layer"].as_str() {
+        Some("front") => DecorationLayer::Front,
+        _             => DecorationLayer::Back,
+      },
+    }
+  }
+}
+
+impl Mask for Decoration {
+  fn point(&self)         -> &Point     { &self.point     }
+  fn point_mut(&mut self) -> &mut Point { &mut self.point }
+  fn size(&self)          -> &Size      { &self.size      }
+  fn origin(&self)        -> &Origin    { &self.origin    }
+}
+
+impl Entity for Decoration {
+  fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+    self.animation.update()
+  }
+
+  fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+    self.animation.draw(ctx, &self.point, &self.size, &Facing::Right)
+  }
+
+  fn draw_offset(&self, ctx: &mut Context, offset: &Point) -> GameResult<()> {
+    self.animation.draw_offset(ctx, &self.point, &self.size, &Facing::Right, offset)
+  }
+}
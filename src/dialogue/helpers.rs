@@ -0,0 +1,102 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+
+use settings::res::*;
+use settings::buttons;
+use settings::dialogue::*;
+use menu::buttons::prelude::*;
+use text_box::prelude::*;
+use layout::Layout;
+use super::script::Choice;
+
+// The panel along the bottom edge of the screen
+pub fn new_panel(window_size: &Size) -> TextBox {
+  TextBoxBuilder::new()
+    .point(Layout::new(window_size).at(Origin::BottomCenter, 0.0, -PADDING))
+    .size_from(window_size.w - PADDING * 2.0, PANEL_HEIGHT)
+    .origin(Origin::BottomCenter)
+    .bg_color(BG_COLOR)
+    .build()
+}
+
+// The area right of the portrait, if there is one
+pub fn text_layout(panel: &TextBox, has_portrait: bool) -> Layout {
+  let portrait_w = if has_portrait { PORTRAIT_SIZE + PADDING } else { 0.0 };
+  let size = panel.size();
+  Layout::from_rect(
+    panel.top_left() + Point::new(PADDING + portrait_w, PADDING),
+    &Size::new(size.w - PADDING * 2.0 - portrait_w, size.h - PADDING * 2.0)
+  )
+}
+
+pub fn new_name(ctx: &mut Context, layout: &Layout, name: &str) -> GameResult<TextBox> {
+  let font = Font::new(ctx, fonts::DEFAULT, NAME_FONT_SIZE)?;
+  Ok(TextBoxBuilder::new()
+     .point(layout.at(Origin::TopLeft, 0.0, 0.0))
+     .size_from(layout.size().w, LINE_HEIGHT)
+     .text(Text::new(ctx, name, &font)?)
+     .text_color(NAME_COLOR)
+     .build())
+}
+
+// One text box per wrapped line, below the speaker's name
+pub fn new_text(ctx: &mut Context, layout: &Layout, text: &str) -> GameResult<Vec<TextBox>> {
+  let font = Font::new(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  wrap(text, LINE_CHARS).iter().enumerate().map( |(i, line)| {
+    Ok(TextBoxBuilder::new()
+       .point(layout.at(Origin::TopLeft, 0.0, LINE_HEIGHT * (i + 1) as NumType + PADDING * 0.5))
+       .size_from(layout.size().w, LINE_HEIGHT)
+       .text(Text::new(ctx, line, &font)?)
+       .text_color(FONT_COLOR)
+       .build())
+  }).collect()
+}
+
+// Stacked above the panel's right edge, the first choice on top
+pub fn new_choice_buttons(ctx: &mut Context, panel: &TextBox, choices: &Vec<Choice>) -> GameResult<Vec<Button>> {
+  let font = Font::new(ctx, fonts::DEFAULT, CHOICE_FONT_SIZE)?;
+  let count = choices.len();
+  choices.iter().enumerate().map( |(i, choice)| {
+    let point = panel.top_right() + Point::new(
+      -CHOICE_WIDTH * 0.5,
+      -(CHOICE_HEIGHT + PADDING * 0.5) * (count - i) as NumType + CHOICE_HEIGHT * 0.5
+    );
+    let text = Text::new(ctx, &choice.text, &font)?;
+    ButtonBuilder::new(ctx)
+      .point(point.clone())
+      .size_from(CHOICE_WIDTH, CHOICE_HEIGHT)
+      .origin(Origin::Center)
+      .button_type(ButtonType::DialogueChoice(i))
+      .animation_from(vec![::join_str(buttons::IMAGES, "level_select1.png")], vec![1000])
+      .text_from(
+        point,
+        Size::new(text.width() as NumType, text.height() as NumType),
+        Origin::Center,
+        text
+      ).build()
+  }).collect()
+}
+
+// Breaks the text between words, so no line is longer than `max_chars` (unless a single word is)
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+  let mut lines: Vec<String> = Vec::new();
+  let mut line = String::new();
+  for word in text.split_whitespace() {
+    if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+      lines.push(line);
+      line = String::new();
+    }
+    if !line.is_empty() {
+      line.push(' ');
+    }
+    line.push_str(word);
+  }
+  if !line.is_empty() {
+    lines.push(line);
+  }
+  lines
+}
@@ -0,0 +1,202 @@
+pub mod prelude {
+  pub use super::Dialogue;
+  pub use menu::Menu;
+}
+
+pub mod script;
+mod helpers;
+
+use ggez::{
+  Context,
+  GameResult,
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use self::helpers::*;
+use self::script::{ DialogueScript, DialogueLine };
+use settings::res;
+use settings::dialogue::PORTRAIT_SIZE;
+use action::Action;
+use menu::prelude::*;
+use text_box::TextBox;
+
+// A conversation playing in a panel along the bottom of the screen, while the level waits.
+// Lines are advanced by clicking the panel or with the `next` and `jump` actions;
+// lines with choices wait for one of them to be clicked or selected with the child select actions.
+pub struct Dialogue {
+  script:   DialogueScript,
+  line:     Option<usize>,  // None once the dialogue is over
+  panel:    TextBox,
+  name:     Option<TextBox>,
+  text:     Vec<TextBox>,
+  portrait: Option<Animation>,
+  buttons:  Vec<Button>,
+  clicked:  Option<ButtonType>,
+  advance:  bool,
+}
+
+impl Dialogue {
+  pub fn new(ctx: &mut Context, window_size: &Size, script_name: &str) -> GameResult<Self> {
+    let mut dialogue = Self {
+      script:   DialogueScript::load(script_name)?,
+      line:     None,
+      panel:    new_panel(window_size),
+      name:     None,
+      text:     Vec::new(),
+      portrait: None,
+      buttons:  Vec::new(),
+      clicked:  None,
+      advance:  false,
+    };
+    dialogue.show_line(ctx, Some(0))?;
+    Ok(dialogue)
+  }
+
+  pub fn is_finished(&self) -> bool {
+    self.line.is_none()
+  }
+
+  pub fn actions_down(&mut self, actions: &Vec<Action>) {
+    for action in actions {
+      match action {
+        Action::Next | Action::Jump => self.advance = true,
+        Action::SelectChild1        => self.clicked(ButtonType::DialogueChoice(0)),
+        Action::SelectChild2        => self.clicked(ButtonType::DialogueChoice(1)),
+        Action::SelectChild3        => self.clicked(ButtonType::DialogueChoice(2)),
+        _                           => (),
+      }
+    }
+  }
+
+  // Goes on to the next line or to the chosen one
+  pub fn apply_clicked(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let next = if let Some(line) = self.current_line() {
+      match &self.clicked {
+        Some(ButtonType::DialogueChoice(i)) => if let Some(choice) = line.choices.get(*i) {
+          Some(choice.next)
+        } else { None },
+        _ => if self.advance && line.choices.is_empty() {
+          Some(line.next)
+        } else { None },
+      }
+    } else { None };
+    self.advance = false;
+    if let Some(next) = next {
+      self.show_line(ctx, next)?;
+    }
+    Ok(())
+  }
+
+  fn current_line(&self) -> Option<&DialogueLine> {
+    self.line.and_then( |i| self.script.lines.get(i) )
+  }
+
+  fn show_line(&mut self, ctx: &mut Context, index: Option<usize>) -> GameResult<()> {
+    self.line     = index;
+    self.name     = None;
+    self.text     = Vec::new();
+    self.portrait = None;
+    self.buttons  = Vec::new();
+    let line = if let Some(line) = index.and_then( |i| self.script.lines.get(i) ) {
+      line
+    } else { return Ok(()); };
+    let speaker = self.script.speaker(line);
+    let has_portrait = speaker.map( |speaker| !speaker.portrait.is_empty() ).unwrap_or(false);
+    let layout = text_layout(&self.panel, has_portrait);
+    if let Some(speaker) = speaker {
+      self.name = Some(new_name(ctx, &layout, &speaker.name)?);
+      if has_portrait {
+        self.portrait = Some(Animation::from_level_images(ctx, res::IMAGES, &speaker.portrait, speaker.interval));
+      }
+    }
+    self.text    = new_text(ctx, &layout, &line.text)?;
+    self.buttons = new_choice_buttons(ctx, &self.panel, &line.choices)?;
+    // Focused, so the gamepad can confirm a choice right away
+    if let Some(btn) = self.buttons.first_mut() {
+      btn.focus();
+    }
+    Ok(())
+  }
+}
+
+impl Mask for Dialogue {
+  fn point(&self)         -> &Point     { self.panel.point()     }
+  fn point_mut(&mut self) -> &mut Point { self.panel.point_mut() }
+  fn size(&self)          -> &Size      { self.panel.size()      }
+  fn origin(&self)        -> &Origin    { self.panel.origin()    }
+}
+
+impl Menu for Dialogue {
+  fn mouse_down(&mut self, x: i32, y: i32) {
+    let point = Point::new(x as NumType, y as NumType);
+    let clicked = self.buttons.iter()
+      .find( |btn| btn.intersects_point(&point) )
+      .map( |btn| btn.button_type.clone() );
+    if let Some(btn_type) = clicked {
+      self.clicked(btn_type);
+    } else if self.panel.intersects_point(&point) {
+      self.advance = true;
+    }
+  }
+
+  fn menu_input(&mut self, input: MenuInput) {
+    match input {
+      MenuInput::Move(side) => self.move_focus(side),
+      MenuInput::Confirm    => if self.buttons.is_empty() {
+        self.advance = true;
+      } else {
+        self.click_focused();
+      },
+      MenuInput::Back       => (),
+    }
+  }
+
+  fn update(&mut self) -> GameResult<()> {
+    if let Some(portrait) = &mut self.portrait {
+      portrait.update()?;
+    }
+    self.update_menu()
+  }
+
+  fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.panel.draw(ctx)?;
+    if let Some(portrait) = &self.portrait {
+      let size = Size::new(PORTRAIT_SIZE, PORTRAIT_SIZE);
+      let point = Point::new(
+        self.panel.top_left().x + (self.panel.size().h - size.h) * 0.5,
+        self.panel.top_left().y + (self.panel.size().h - size.h) * 0.5
+      );
+      portrait.draw(ctx, &point, &size, &Facing::Right)?;
+    }
+    if let Some(name) = &self.name {
+      name.draw(ctx)?;
+    }
+    for text in &self.text {
+      text.draw(ctx)?;
+    }
+    self.draw_menu(ctx)
+  }
+
+  fn buttons(&self) -> Vec<&Button> {
+    self.buttons.iter().collect()
+  }
+  fn buttons_mut(&mut self) -> Vec<&mut Button> {
+    self.buttons.iter_mut().collect()
+  }
+  fn animation(&self) -> Option<&Animation> {
+    None
+  }
+  fn animation_mut(&mut self) -> Option<&mut Animation> {
+    None
+  }
+  fn clicked(&mut self, btn_type: ButtonType) {
+    self.clicked = Some(btn_type);
+  }
+  fn get_clicked(&self) -> &Option<ButtonType> {
+    &self.clicked
+  }
+  fn clear_clicked(&mut self) {
+    self.clicked = None;
+  }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use ggez::{
+  GameResult,
+  GameError,
+};
+use json::JsonValue;
+
+use settings::res;
+use settings::dialogue::PORTRAIT_INTERVAL;
+use json_file;
+
+pub struct Speaker {
+  pub name:     String,
+  pub portrait: Vec<String>,  // Images in `/images/`
+  pub interval: u64,
+}
+
+pub struct Choice {
+  pub text: String,
+  pub next: Option<usize>,  // None ends the dialogue
+}
+
+pub struct DialogueLine {
+  pub speaker: Option<String>,
+  pub text:    String,
+  pub choices: Vec<Choice>,
+  pub next:    Option<usize>,  // None ends the dialogue
+}
+
+// A conversation, loaded from `resources/dialogue/<name>.json`, e.g.
+// `{ "speakers": { "larry": { "name": "Larry", "portrait": ["children/child_1_closeup.png"] } },
+//    "lines": [{ "speaker": "larry", "text": "Hi!", "choices": [{ "text": "Bye.", "end": true }] }] }`.
+// Lines and choices go on to the following line, unless they have a `next` line index or `"end": true`.
+pub struct DialogueScript {
+  pub speakers: HashMap<String, Speaker>,
+  pub lines:    Vec<DialogueLine>,
+}
+
+impl DialogueScript {
+  pub fn load(name: &str) -> GameResult<Self> {
+    let filepath = format!("{}{}.json", res::DIALOGUE, name);
+    let data = json_file::read(Path::new(&filepath))
+      .map_err( |e| GameError::from(format!("Couldn't open dialogue {}: {}", filepath, e)) )?;
+    Self::from_json(&data)
+      .map_err( |e| GameError::from(format!("Dialogue {}: {}", name, e)) )
+  }
+
+  pub fn from_json(data: &JsonValue) -> GameResult<Self> {
+    let err = |msg: String| Err(GameError::from(msg));
+    let mut speakers = HashMap::new();
+    for (id, speaker) in data["speakers"].entries() {
+      let portrait = speaker["portrait"].members()
+        .filter_map( |image| image.as_str().map( |image| image.to_string() ) )
+        .collect::<Vec<String>>();
+      if !speaker["portrait"].is_null() && portrait.len() != speaker["portrait"].len() {
+        return err(format!("Speaker {} has an invalid `portrait`", id));
+      }
+      speakers.insert(id.to_string(), Speaker {
+        name:     speaker["name"].as_str().unwrap_or(id).to_string(),
+        portrait,
+        interval: speaker["interval"].as_u64().unwrap_or(PORTRAIT_INTERVAL),
+      });
+    }
+
+    let len = data["lines"].len();
+    if !data["lines"].is_array() || len == 0 {
+      return err("Dialogue must have a `lines` array".to_string());
+    }
+    let mut lines = Vec::new();
+    for (i, line) in data["lines"].members().enumerate() {
+      let text = if let Some(text) = line["text"].as_str() {
+        text.to_string()
+      } else { return err(format!("Line {}: missing `text`", i)); };
+      let speaker = line["speaker"].as_str().map( |speaker| speaker.to_string() );
+      if let Some(speaker) = &speaker {
+        if !speakers.contains_key(speaker) {
+          return err(format!("Line {}: unknown speaker {}", i, speaker));
+        }
+      }
+      if !line["choices"].is_null() && !line["choices"].is_array() {
+        return err(format!("Line {}: invalid `choices`", i));
+      }
+      let mut choices = Vec::new();
+      for choice in line["choices"].members() {
+        let text = if let Some(text) = choice["text"].as_str() {
+          text.to_string()
+        } else { return err(format!("Line {}: choice without `text`", i)); };
+        choices.push(Choice { text, next: next_line(choice, i, len)? });
+      }
+      lines.push(DialogueLine {
+        speaker,
+        text,
+        choices,
+        next: next_line(line, i, len)?,
+      });
+    }
+
+    Ok(Self { speakers, lines })
+  }
+
+  pub fn speaker(&self, line: &DialogueLine) -> Option<&Speaker> {
+    line.speaker.as_ref().and_then( |id| self.speakers.get(id) )
+  }
+}
+
+fn next_line(data: &JsonValue, index: usize, len: usize) -> GameResult<Option<usize>> {
+  if data["end"].as_bool().unwrap_or(false) {
+    return Ok(None);
+  }
+  if data["next"].is_null() {
+    return Ok(if index + 1 < len { Some(index + 1) } else { None });
+  }
+  match data["next"].as_usize() {
+    Some(next) if next < len => Ok(Some(next)),
+    _                        => Err(GameError::from(format!("Line {}: invalid `next`", index))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use json;
+
+  fn parse(raw: &str) -> GameResult<DialogueScript> {
+    DialogueScript::from_json(&json::parse(raw).expect("Test dialogue should be valid JSON"))
+  }
+
+  #[test]
+  fn lines_go_on_to_the_following_line_unless_told_otherwise() {
+    let script = parse(r#"{
+      "speakers": { "larry": { "name": "Larry", "portrait": ["children/child_1_closeup.png"] } },
+      "lines": [
+        { "speaker": "larry", "text": "Hi!" },
+        { "text": "Again?", "choices": [{ "text": "Yes", "next": 0 }, { "text": "No" }, { "text": "Bye", "end": true }] },
+        { "text": "Bye then." }
+      ]
+    }"#).expect("Should parse the dialogue");
+    assert_eq!(script.lines.len(), 3);
+    assert_eq!(script.lines[0].next, Some(1));
+    assert_eq!(script.lines[2].next, None);
+    let nexts = script.lines[1].choices.iter().map( |choice| choice.next ).collect::<Vec<Option<usize>>>();
+    assert_eq!(nexts, vec![Some(0), Some(2), None]);
+    let speaker = script.speaker(&script.lines[0]).expect("The first line should have a speaker");
+    assert_eq!(speaker.name, "Larry");
+    assert_eq!(speaker.interval, PORTRAIT_INTERVAL);
+  }
+
+  #[test]
+  fn a_speaker_without_a_name_is_named_by_its_id() {
+    let script = parse(r#"{ "speakers": { "thing": {} }, "lines": [{ "speaker": "thing", "text": "..." }] }"#)
+      .expect("Should parse the dialogue");
+    assert_eq!(script.speakers["thing"].name, "thing");
+  }
+
+  #[test]
+  fn invalid_dialogues_are_errors() {
+    assert!(parse(r#"{}"#).is_err());
+    assert!(parse(r#"{ "lines": [] }"#).is_err());
+    assert!(parse(r#"{ "lines": [{ "speaker": "nobody", "text": "Hi!" }] }"#).is_err());
+    assert!(parse(r#"{ "lines": [{ "text": "Hi!", "next": 1 }] }"#).is_err());
+    assert!(parse(r#"{ "lines": [{ "text": "Hi!", "choices": "Bye" }] }"#).is_err());
+    assert!(parse(r#"{ "lines": [{ "text": "Hi!", "choices": [{ "next": 0 }] }] }"#).is_err());
+    assert!(parse(r#"{ "speakers": { "larry": { "portrait": [1] } }, "lines": [{ "text": "Hi!" }] }"#).is_err());
+  }
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ self, Text, Font },
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+
+use settings::editor::*;
+use level::validate_level_contents;
+use json_file;
+use text_box::prelude::*;
+
+// Only a level that doesn't exist yet starts out blank;
+// a broken one isn't opened, so saving can't overwrite it.
+pub fn load_level_data(filepath: &Path) -> GameResult<JsonValue> {
+  if !filepath.exists() {
+    return Ok(object!{
+      "size"      => object!{ "w" => 1280, "h" => 720 },
+      "instances" => array![],
+    });
+  }
+  let data = json_file::read(filepath)?;
+  validate_level_contents(&data)?;
+  Ok(data)
+}
+
+pub fn instance_rect(instance: &JsonValue) -> Rect {
+  Rect::new(
+    Point::new(
+      instance["position"]["x"].as_f32().unwrap_or(0.0),
+      instance["position"]["y"].as_f32().unwrap_or(0.0)
+    ),
+    Size::new(
+      instance["size"]["w"].as_f32().unwrap_or(0.0),
+      instance["size"]["h"].as_f32().unwrap_or(0.0)
+    ),
+    Origin::TopLeft
+  )
+}
+
+pub fn snap_to_grid(point: &Point) -> Point {
+  Point::new(
+    (point.x / GRID_SIZE).floor() * GRID_SIZE,
+    (point.y / GRID_SIZE).floor() * GRID_SIZE
+  )
+}
+
+pub fn new_info_texts(ctx: &mut Context, font: &Font, lines: &Vec<String>) -> GameResult<Vec<TextBox>> {
+  let padding = 4.0;
+  let mut y = 8.0;
+  let mut texts = Vec::new();
+  for line in lines {
+    let text = Text::new(ctx, line, font)?;
+    let size = Size::new(text.width() as NumType, text.height() as NumType);
+    texts.push(TextBoxBuilder::new()
+               .point_from(8.0, y)
+               .size(size.clone())
+               .bg_color(INFO_BG_COLOR)
+               .text(text)
+               .text_color(INFO_FONT_COLOR)
+               .build());
+    y += size.h + padding;
+  }
+  Ok(texts)
+}
+
+pub fn draw_grid(ctx: &mut Context, camera_top_left: &Point, window_size: &Size) -> GameResult<()> {
+  let prev_color = graphics::get_color(ctx);
+  graphics::set_color(ctx, GRID_COLOR.into())?;
+  let start = Point::new(
+    (camera_top_left.x / GRID_SIZE).floor() * GRID_SIZE - camera_top_left.x,
+    (camera_top_left.y / GRID_SIZE).floor() * GRID_SIZE - camera_top_left.y
+  );
+  let mut x = start.x;
+  while x < window_size.w {
+    graphics::line(ctx, &[graphics::Point2::new(x, 0.0), graphics::Point2::new(x, window_size.h)], 1.0)?;
+    x += GRID_SIZE;
+  }
+  let mut y = start.y;
+  while y < window_size.h {
+    graphics::line(ctx, &[graphics::Point2::new(0.0, y), graphics::Point2::new(window_size.w, y)], 1.0)?;
+    y += GRID_SIZE;
+  }
+  graphics::set_color(ctx, prev_color)?;
+  Ok(())
+}
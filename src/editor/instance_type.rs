@@ -0,0 +1,122 @@
+use json::JsonValue;
+use noframe::geo::prelude::*;
+
+use settings::editor::{ COLORS, DIALOGUE_SCRIPT };
+use settings::interactables::jump_pad::JUMP_SPEED;
+use id_generator::IdType;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InstanceType {
+  Wall,
+  Player,
+  LarryChild,
+  ThingChild,
+  BloatChild,
+  JumpPad,
+  Switch,
+  Door,
+  OneWay,
+  Solidifier,
+  Goal,
+  DialogueTrigger,
+}
+
+impl InstanceType {
+  pub fn all() -> Vec<InstanceType> {
+    use self::InstanceType::*;
+    vec![
+      Wall, Player, LarryChild, ThingChild, BloatChild,
+      JumpPad, Switch, Door, OneWay, Solidifier, Goal, DialogueTrigger,
+    ]
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::all().into_iter().find( |instance_type| instance_type.name() == name )
+  }
+
+  pub fn name(&self) -> &'static str {
+    use self::InstanceType::*;
+    match self {
+      Wall            => "Wall",
+      Player          => "Player",
+      LarryChild      => "LarryChild",
+      ThingChild      => "ThingChild",
+      BloatChild      => "BloatChild",
+      JumpPad         => "JumpPadInteractable",
+      Switch          => "SwitchInteractable",
+      Door            => "DoorInteractable",
+      OneWay          => "OneWayInteractable",
+      Solidifier      => "SolidifierInteractable",
+      Goal            => "GoalInteractable",
+      DialogueTrigger => "DialogueTrigger",
+    }
+  }
+
+  pub fn default_size(&self) -> Size {
+    use self::InstanceType::*;
+    match self {
+      Wall            => Size::new(32.0,  32.0),
+      Player          => Size::new(32.0,  64.0),
+      LarryChild      => Size::new(32.0,  64.0),
+      ThingChild      => Size::new(32.0,  64.0),
+      BloatChild      => Size::new(32.0,  64.0),
+      JumpPad         => Size::new(64.0,  12.0),
+      Switch          => Size::new(32.0,  32.0),
+      Door            => Size::new(64.0,  160.0),
+      OneWay          => Size::new(64.0,  32.0),
+      Solidifier      => Size::new(32.0,  32.0),
+      Goal            => Size::new(160.0, 160.0),
+      DialogueTrigger => Size::new(64.0,  128.0),
+    }
+  }
+
+  // There may only be a single instance of these types in a level.
+  pub fn is_unique(&self) -> bool {
+    match self {
+      InstanceType::Player | InstanceType::Goal => true,
+      _                                         => false,
+    }
+  }
+
+  pub fn is_invisible(&self) -> bool {
+    match self {
+      InstanceType::Solidifier | InstanceType::DialogueTrigger => true,
+      _                                                        => false,
+    }
+  }
+
+  pub fn states(&self) -> Vec<&'static str> {
+    match self {
+      InstanceType::JumpPad => vec![ "Active", "Inactive", "Trigger" ],
+      InstanceType::Door    => vec![ "Open", "Closed", "Opening", "Closing" ],
+      _                     => Vec::new(),
+    }
+  }
+
+  pub fn default_additional(&self, id: IdType) -> Option<JsonValue> {
+    let color = COLORS[0];
+    match self {
+      InstanceType::JumpPad => Some(object!{
+        "id"       => id,
+        "color"    => color,
+        "state"    => "Active",
+        "strength" => JUMP_SPEED,
+      }),
+      InstanceType::Switch => Some(object!{
+        "id"       => id,
+        "color"    => color,
+        "triggers" => array![],
+      }),
+      InstanceType::Door => Some(object!{
+        "id"       => id,
+        "color"    => color,
+        "state"    => "Closed",
+      }),
+      InstanceType::DialogueTrigger => Some(object!{
+        "script"   => DIALOGUE_SCRIPT,
+        "once"     => true,
+      }),
+      _ => None,
+    }
+  }
+}
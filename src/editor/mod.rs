@@ -0,0 +1,585 @@
+mod helpers;
+mod instance_type;
+
+use std::path::PathBuf;
+
+use ggez::{
+  Context,
+  GameResult,
+  graphics,
+  event::{ Keycode, MouseButton, MouseState },
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+use noframe::camera::Camera;
+use noframe::color::Color;
+use noframe::entity::Entity;
+use noframe::deltatime::Deltatime;
+
+use self::helpers::*;
+use self::instance_type::InstanceType;
+use settings::res;
+use settings::camera::CAMERA_SPEED;
+use settings::editor::*;
+use settings::interactables::jump_pad::JUMP_SPEED;
+use level::{ Level, InteractablesContainer, validate_level_contents, load_instances };
+use persons::player::Player;
+use persons::children::Child;
+use wall::Walls;
+use decoration::{ Decoration, DecorationLayer };
+use background::Background;
+use color_rect::prelude::*;
+use text_box::TextBox;
+use id_generator::IdType;
+use config;
+use json_file;
+use sfx::Sfx;
+use dialogue::prelude::*;
+
+pub struct Editor {
+  window_size:    Size,
+  level_paths:    Vec<PathBuf>,
+  level_index:    usize,
+  data:           JsonValue,
+  camera:         Camera,
+  instance_types: Vec<InstanceType>,
+  type_index:     usize,
+  selected:       Option<usize>,
+  snap:           bool,
+  mouse:          Point,
+  player:         Option<Player>,
+  children:       Vec<Child>,
+  walls:          Walls,
+  interactables:  InteractablesContainer,
+  decorations:    Vec<Decoration>,
+  background:     Background,
+  playtest:       Option<Level>,
+  sfx:            Sfx,
+  dialogue:       Option<Dialogue>,
+  dialogue_queue: Vec<String>,
+  info_font:      graphics::Font,
+  info_texts:     Vec<TextBox>,
+  notice:         Option<String>,
+  pub to_title:   bool,
+  dt:             Deltatime,
+}
+
+impl Editor {
+  pub fn new(ctx: &mut Context, window_size: Size, level_paths: Vec<PathBuf>, level_index: usize) -> GameResult<Self> {
+    let mut editor = Self {
+      window_size:    window_size.clone(),
+      level_paths,
+      level_index,
+      data:           object!{},
+      camera:         Camera::new(window_size.clone()),
+      instance_types: InstanceType::all(),
+      type_index:     0,
+      selected:       None,
+      snap:           true,
+      mouse:          Point::new(0.0, 0.0),
+      player:         None,
+      children:       Vec::new(),
+      walls:          Walls::new(ctx, level_index),
+      interactables:  InteractablesContainer::new(),
+      decorations:    Vec::new(),
+      background:     Background::new(ctx, &window_size, &JsonValue::Null, level_index),
+      playtest:       None,
+      sfx:            Sfx::new(),
+      dialogue:       None,
+      dialogue_queue: Vec::new(),
+      info_font:      graphics::Font::new(ctx, res::fonts::DEFAULT, INFO_FONT_SIZE)?,
+      info_texts:     Vec::new(),
+      notice:         None,
+      to_title:       false,
+      dt:             Deltatime::new(),
+    };
+    editor.load_level(ctx, level_index)?;
+    Ok(editor)
+  }
+
+  fn load_level(&mut self, ctx: &mut Context, level_index: usize) -> GameResult<()> {
+    if level_index >= self.level_paths.len() { return Ok(()); }
+    let data = load_level_data(&self.level_paths[level_index])?;
+    self.level_index = level_index;
+    self.data        = data;
+    self.selected    = None;
+    self.notice      = None;
+    self.rebuild(ctx)?;
+    let center = if let Some(player) = &self.player {
+      player.center()
+    } else {
+      Rect::new(Point::new(0.0, 0.0), self.window_size.clone(), Origin::TopLeft).center()
+    };
+    self.camera.move_to(&center);
+    Ok(())
+  }
+
+  // The current level stays open if the other one can't be read
+  fn switch_level(&mut self, ctx: &mut Context, level_index: usize) {
+    if let Err(e) = self.load_level(ctx, level_index) {
+      self.notice = Some(format!("Can't open level: {}", e));
+    }
+  }
+
+  // Rebuild the actual game objects from the JSON data, so everything looks just like ingame.
+  fn rebuild(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if let Err(e) = validate_level_contents(&self.data) {
+      self.notice = Some(format!("Invalid level: {}", e));
+      return self.update_info(ctx);
+    }
+    let (player, children, walls, interactables, decorations) = load_instances(ctx, &self.data, self.level_index);
+    self.player        = player;
+    self.children      = children;
+    self.walls         = walls;
+    self.interactables = interactables;
+    self.decorations   = decorations;
+    self.background    = Background::new(ctx, &self.window_size, &self.data["background"], self.level_index);
+    self.update_info(ctx)
+  }
+
+  fn level_name(&self) -> String {
+    self.level_paths[self.level_index].file_stem()
+      .and_then( |stem| stem.to_str() )
+      .unwrap_or("")
+      .to_string()
+  }
+
+  fn instance_type(&self) -> InstanceType {
+    self.instance_types[self.type_index]
+  }
+
+  fn selected_instance_type(&self) -> Option<InstanceType> {
+    self.selected.and_then( |index| InstanceType::from_name(
+        self.data["instances"][index]["type"].as_str().unwrap_or("")
+    ))
+  }
+
+  fn world_mouse(&self) -> Point {
+    self.mouse.clone() + self.camera.top_left()
+  }
+
+  fn placement_point(&self) -> Point {
+    let point = self.world_mouse();
+    if self.snap {
+      snap_to_grid(&point)
+    } else { point }
+  }
+
+  // Returns the index of the topmost instance at the given point
+  fn instance_at(&self, point: &Point) -> Option<usize> {
+    self.data["instances"].members().rposition( |instance| instance_rect(instance).intersects_point(point) )
+  }
+
+  fn next_id(&self) -> IdType {
+    self.data["instances"].members()
+      .filter_map( |instance| instance["additional"]["id"].as_u32() )
+      .max()
+      .map( |id| id + 1 )
+      .unwrap_or(0)
+  }
+
+  fn place_instance(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let instance_type = self.instance_type();
+    let point = self.placement_point();
+    let size = instance_type.default_size();
+    if instance_type.is_unique() {
+      self.remove_instances_of(instance_type);
+    }
+    let mut instance = object!{
+      "type"     => instance_type.name(),
+      "position" => object!{ "x" => point.x, "y" => point.y },
+      "size"     => object!{ "w" => size.w,  "h" => size.h },
+    };
+    if let Some(additional) = instance_type.default_additional(self.next_id()) {
+      instance["additional"] = additional;
+    }
+    self.data["instances"].push(instance).expect("Level instances should be an array");
+    self.selected = Some(self.data["instances"].len() - 1);
+    self.rebuild(ctx)
+  }
+
+  fn remove_instance(&mut self, ctx: &mut Context, index: usize) -> GameResult<()> {
+    self.data["instances"].array_remove(index);
+    self.selected = None;
+    self.rebuild(ctx)
+  }
+
+  fn remove_instances_of(&mut self, instance_type: InstanceType) {
+    let instances = self.data["instances"].members()
+      .filter( |instance| instance["type"].as_str() != Some(instance_type.name()) )
+      .cloned()
+      .collect::<Vec<JsonValue>>();
+    self.data["instances"] = JsonValue::Array(instances);
+    self.selected = None;
+  }
+
+  fn selected_additional_mut(&mut self) -> Option<&mut JsonValue> {
+    if let Some(index) = self.selected {
+      let additional = &mut self.data["instances"][index]["additional"];
+      if additional.is_object() {
+        return Some(additional);
+      }
+    }
+    None
+  }
+
+  fn edit_id(&mut self, incr: i64) {
+    if let Some(additional) = self.selected_additional_mut() {
+      if let Some(id) = additional["id"].as_u32() {
+        additional["id"] = ((id as i64 + incr).max(0) as IdType).into();
+      }
+    }
+  }
+
+  fn cycle_color(&mut self) {
+    if let Some(additional) = self.selected_additional_mut() {
+      let index = additional["color"].as_str()
+        .and_then( |color| COLORS.iter().position( |&c| c == color ) )
+        .map( |i| (i + 1) % COLORS.len() )
+        .unwrap_or(0);
+      additional["color"] = COLORS[index].into();
+    }
+  }
+
+  fn cycle_state(&mut self) {
+    let states = if let Some(instance_type) = self.selected_instance_type() {
+      instance_type.states()
+    } else { return; };
+    if states.is_empty() { return; }
+    if let Some(additional) = self.selected_additional_mut() {
+      let index = additional["state"].as_str()
+        .and_then( |state| states.iter().position( |&s| s == state ) )
+        .map( |i| (i + 1) % states.len() )
+        .unwrap_or(0);
+      additional["state"] = states[index].into();
+    }
+  }
+
+  fn edit_strength(&mut self, incr: f32) {
+    if self.selected_instance_type() != Some(InstanceType::JumpPad) { return; }
+    if let Some(additional) = self.selected_additional_mut() {
+      let strength = additional["strength"].as_f32().unwrap_or(JUMP_SPEED);
+      additional["strength"] = (strength + incr).max(0.0).into();
+    }
+  }
+
+  // Add or remove the hovered instance's id to or from the selected Switch's triggers
+  fn toggle_link(&mut self) {
+    if self.selected_instance_type() != Some(InstanceType::Switch) { return; }
+    let point = self.world_mouse();
+    let target_id = if let Some(index) = self.instance_at(&point) {
+      self.data["instances"][index]["additional"]["id"].as_u32()
+    } else { None };
+    if let Some(id) = target_id {
+      if let Some(additional) = self.selected_additional_mut() {
+        let position = additional["triggers"].members().position( |trigger| trigger.as_u32() == Some(id) );
+        if let Some(position) = position {
+          additional["triggers"].array_remove(position);
+        } else {
+          additional["triggers"].push(id).expect("Switch triggers should be an array");
+        }
+      }
+    }
+  }
+
+  fn cycle_instance_type(&mut self, forward: bool) {
+    let len = self.instance_types.len();
+    self.type_index = if forward {
+      (self.type_index + 1) % len
+    } else {
+      (self.type_index + len - 1) % len
+    };
+  }
+
+  fn save(&mut self) {
+    let filepath = &self.level_paths[self.level_index];
+    self.notice = Some(match json_file::write(filepath, &self.data) {
+      Ok(()) => format!("Saved {}", filepath.display()),
+      Err(e) => format!("Couldn't save {}: {}", filepath.display(), e),
+    });
+  }
+
+  fn toggle_playtest(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if self.playtest.is_some() {
+      self.playtest = None;
+      self.dialogue = None;
+      self.dialogue_queue.clear();
+      return Ok(());
+    }
+    let name = self.level_name();
+    match Level::from_json(ctx, self.window_size.clone(), &name, self.data.clone(), self.level_index) {
+      Ok(mut level) => {
+        level.camera_mut().move_to(self.camera.point());
+        self.dt.reset();
+        level.reset_dt(&self.dt);
+        self.dialogue_queue = level.drain_dialogue();
+        self.playtest = Some(level);
+      }
+      Err(e) => self.notice = Some(format!("Can't play level: {}", e)),
+    }
+    self.update_info(ctx)
+  }
+
+  fn update_info(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let mut lines = vec![
+      format!("Editing {} ({}/{})", self.level_name(), self.level_index + 1, self.level_paths.len()),
+      format!("Place: {}", self.instance_type().name()),
+      format!("Grid snapping: {}", if self.snap { "on" } else { "off" }),
+    ];
+    if let Some(index) = self.selected {
+      let instance = &self.data["instances"][index];
+      lines.push(format!("Selected: {} {}", instance["type"], instance["additional"].dump()));
+    }
+    lines.push("F2 save, F5 play, PageUp/PageDown level, Backspace exit".to_string());
+    if let Some(notice) = &self.notice {
+      lines.push(notice.clone());
+    }
+    self.info_texts = new_info_texts(ctx, &self.info_font, &lines)?;
+    Ok(())
+  }
+
+  pub fn keys_pressed(&mut self, keycodes: &Vec<Keycode>) {
+    if let Some(level) = &mut self.playtest {
+      if self.dialogue.is_none() {
+        let actions = config::with( |c| c.bindings.actions(keycodes) );
+        level.actions_pressed(&actions, &self.dt);
+      }
+      return;
+    }
+    for key in keycodes {
+      match key {
+        Keycode::Up    => self.camera.move_y(-CAMERA_SPEED * self.dt.secs()),
+        Keycode::Down  => self.camera.move_y( CAMERA_SPEED * self.dt.secs()),
+        Keycode::Left  => self.camera.move_x(-CAMERA_SPEED * self.dt.secs()),
+        Keycode::Right => self.camera.move_x( CAMERA_SPEED * self.dt.secs()),
+        _              => (),
+      };
+    }
+  }
+
+  pub fn keys_down(&mut self, ctx: &mut Context, keycodes: &Vec<Keycode>) -> GameResult<()> {
+    if self.playtest.is_some() {
+      let stop = keycodes.iter().any( |&key| key == controls::PLAY || key == controls::TO_TITLE );
+      let actions = config::with( |c| c.bindings.actions(keycodes) );
+      if let Some(dialogue) = &mut self.dialogue {
+        dialogue.actions_down(&actions);
+      } else if let Some(level) = &mut self.playtest {
+        level.actions_down(&actions, &self.dt);
+      }
+      if stop {
+        self.toggle_playtest(ctx)?;
+      }
+      return Ok(());
+    }
+
+    let mut changed = false;
+    for &key in keycodes {
+      match key {
+        controls::PREV_TYPE     => self.cycle_instance_type(false),
+        controls::NEXT_TYPE     => self.cycle_instance_type(true),
+        controls::TOGGLE_SNAP   => self.snap = !self.snap,
+        controls::ID_UP         => { self.edit_id(1);                  changed = true; },
+        controls::ID_DOWN       => { self.edit_id(-1);                 changed = true; },
+        controls::COLOR         => { self.cycle_color();               changed = true; },
+        controls::STATE         => { self.cycle_state();               changed = true; },
+        controls::LINK          => { self.toggle_link();               changed = true; },
+        controls::STRENGTH_UP   => { self.edit_strength(STRENGTH_STEP);  changed = true; },
+        controls::STRENGTH_DOWN => { self.edit_strength(-STRENGTH_STEP); changed = true; },
+        controls::REMOVE        => if let Some(index) = self.selected {
+          self.remove_instance(ctx, index)?;
+        },
+        controls::SAVE          => self.save(),
+        controls::PLAY          => return self.toggle_playtest(ctx),
+        controls::PREV_LEVEL    => if self.level_index > 0 {
+          let index = self.level_index - 1;
+          self.switch_level(ctx, index);
+        },
+        controls::NEXT_LEVEL    => {
+          let index = self.level_index + 1;
+          self.switch_level(ctx, index);
+        },
+        controls::TO_TITLE      => self.to_title = true,
+        _                       => (),
+      }
+    }
+    if changed {
+      self.rebuild(ctx)
+    } else {
+      self.update_info(ctx)
+    }
+  }
+
+  pub fn keys_up(&mut self, keycodes: &Vec<Keycode>) {
+    if let Some(level) = &mut self.playtest {
+      if self.dialogue.is_none() {
+        let actions = config::with( |c| c.bindings.actions(keycodes) );
+        level.actions_up(&actions, &self.dt);
+      }
+    }
+  }
+
+  pub fn mouse_down(&mut self, ctx: &mut Context, button: MouseButton, x: i32, y: i32) -> GameResult<()> {
+    self.mouse = Point::new(x as NumType, y as NumType);
+    if let Some(dialogue) = &mut self.dialogue {
+      dialogue.mouse_down(x, y);
+      return Ok(());
+    }
+    if let Some(level) = &mut self.playtest {
+      level.mouse_down(x, y);
+      return Ok(());
+    }
+    let point = self.world_mouse();
+    let hovered = self.instance_at(&point);
+    match button {
+      MouseButton::Left => if let Some(index) = hovered {
+        self.selected = Some(index);
+        self.update_info(ctx)?;
+      } else {
+        self.place_instance(ctx)?;
+      },
+      MouseButton::Right => if let Some(index) = hovered {
+        self.remove_instance(ctx, index)?;
+      },
+      _ => (),
+    }
+    Ok(())
+  }
+
+  pub fn mouse_motion(&mut self, state: MouseState, x: i32, y: i32, xrel: i32, yrel: i32) {
+    self.mouse = Point::new(x as NumType, y as NumType);
+    let drag = Point::new(xrel as NumType, yrel as NumType).inverted();
+    if let Some(level) = &mut self.playtest {
+      if state.left() || state.right() {
+        level.camera_mut().move_by(&drag);
+      }
+    } else if state.middle() {
+      self.camera.move_by(&drag);
+    }
+  }
+
+  pub fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.update_dialogue(ctx)?;
+    let mut finished = false;
+    if let Some(level) = &mut self.playtest {
+      if self.dialogue.is_none() {
+        level.update(ctx, &self.dt)?;
+        // Playtests don't count towards the player's statistics or seen hints
+        level.drain_stats_events();
+        level.drain_seen_hints();
+        self.sfx.play_all(ctx, level.drain_sounds())?;
+        self.dialogue_queue.extend(level.drain_dialogue());
+        finished = level.next_level;
+      }
+    }
+    if finished {
+      self.toggle_playtest(ctx)?;
+    }
+    self.dt.update();
+    Ok(())
+  }
+
+  // Plays the playtest's dialogues like the LevelManager does, skipping scripts that can't be loaded
+  fn update_dialogue(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if self.dialogue.as_ref().map( |dialogue| dialogue.is_finished() ).unwrap_or(false) {
+      self.dialogue = None;
+      if let Some(level) = &mut self.playtest {
+        level.reset_dt(&self.dt);
+      }
+    }
+    while self.dialogue.is_none() && !self.dialogue_queue.is_empty() {
+      let script = self.dialogue_queue.remove(0);
+      match Dialogue::new(ctx, &self.window_size, &script) {
+        Ok(dialogue) => self.dialogue = Some(dialogue),
+        Err(e)       => eprintln!("Couldn't start dialogue: {}", e),
+      }
+    }
+    if let Some(dialogue) = &mut self.dialogue {
+      dialogue.apply_clicked(ctx)?;
+      dialogue.update()?;
+    }
+    Ok(())
+  }
+
+  pub fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(level) = &mut self.playtest {
+      level.draw(ctx)?;
+      if let Some(dialogue) = &mut self.dialogue {
+        dialogue.draw(ctx)?;
+      }
+      return Ok(());
+    }
+    let offset = self.camera.top_left().inverted();
+    self.background.draw(ctx, &self.camera.top_left())?;
+    self.draw_decorations(ctx, DecorationLayer::Back)?;
+    if let Some(goal) = &self.interactables.goal {
+      self.camera.draw(ctx, goal)?;
+    }
+    self.walls.draw_offset(ctx, &offset)?;
+    for child in &self.children {
+      self.camera.draw(ctx, child)?;
+    }
+    if let Some(player) = &self.player {
+      self.camera.draw(ctx, player)?;
+    }
+    for jump_pad in &self.interactables.jump_pads {
+      self.camera.draw(ctx, jump_pad)?;
+    }
+    for switch in &self.interactables.switches {
+      self.camera.draw(ctx, switch)?;
+    }
+    for door in &self.interactables.doors {
+      self.camera.draw(ctx, door)?;
+    }
+    for oneway in &self.interactables.one_ways {
+      self.camera.draw(ctx, oneway)?;
+    }
+    self.draw_decorations(ctx, DecorationLayer::Front)?;
+    self.draw_invisible(ctx, &offset)?;
+    if self.snap {
+      draw_grid(ctx, &self.camera.top_left(), &self.window_size)?;
+    }
+    self.draw_selected(ctx, &offset)?;
+    for text in &self.info_texts {
+      text.draw(ctx)?;
+    }
+    Ok(())
+  }
+
+  fn draw_decorations(&self, ctx: &mut Context, layer: DecorationLayer) -> GameResult<()> {
+    for decoration in &self.decorations {
+      if decoration.layer == layer {
+        self.camera.draw(ctx, decoration)?;
+      }
+    }
+    Ok(())
+  }
+
+  fn draw_invisible(&self, ctx: &mut Context, offset: &Point) -> GameResult<()> {
+    for instance in self.data["instances"].members() {
+      let is_invisible = instance["type"].as_str()
+        .and_then( |name| InstanceType::from_name(name) )
+        .map( |instance_type| instance_type.is_invisible() )
+        .unwrap_or(false);
+      if is_invisible {
+        self.draw_rect(ctx, &instance_rect(instance), offset, INVISIBLE_COLOR)?;
+      }
+    }
+    Ok(())
+  }
+
+  fn draw_selected(&self, ctx: &mut Context, offset: &Point) -> GameResult<()> {
+    if let Some(index) = self.selected {
+      self.draw_rect(ctx, &instance_rect(&self.data["instances"][index]), offset, SELECTED_COLOR)?;
+    }
+    Ok(())
+  }
+
+  fn draw_rect(&self, ctx: &mut Context, rect: &Rect, offset: &Point, color: Color) -> GameResult<()> {
+    ColorRectBuilder::new()
+      .point(rect.top_left() + offset.clone())
+      .size(rect.size().clone())
+      .color(color)
+      .build()
+      .draw(ctx)
+  }
+}
@@ -0,0 +1,23 @@
+use ggez::{
+  Context,
+  GameResult,
+};
+use noframe::geo::prelude::*;
+
+use settings::res::fonts;
+use settings::save::*;
+use text_box::prelude::*;
+
+pub fn new_notice(ctx: &mut Context, window_size: &Size, msg: &str) -> GameResult<TextBox> {
+  let font = Font::new(ctx, fonts::DEFAULT, NOTICE_FONT_SIZE)?;
+  let text = Text::new(ctx, msg, &font)?;
+  Ok(TextBoxBuilder::new()
+     .point_from(window_size.w / 2.0, window_size.h - 16.0)
+     .size_from(text.width() as NumType + 16.0, text.height() as NumType + 8.0)
+     .origin(Origin::BottomCenter)
+     .bg_color(NOTICE_BG_COLOR)
+     .text(text)
+     .text_color(NOTICE_COLOR)
+     .text_origin(TextOrigin::BottomCenter)
+     .build())
+}
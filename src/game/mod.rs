@@ -1,6 +1,8 @@
+mod helpers;
+pub mod save_file;
+mod profiles;
+
 use std::time::{ Instant, Duration };
-use std::fs;
-use std::io::prelude::*;
 
 use ggez::{
   Context,
@@ -10,122 +12,207 @@ use ggez::{
     self,
     Keycode,
     MouseButton,
-    MouseState
+    MouseState,
+    Button,
+    Axis,
   },
-  audio
 };
 
 use noframe::geo::prelude::*;
 use noframe::input_manager::InputManager;
+use noframe::entity::Entity;
 
-use settings::game::*;
-use settings::res;
+use settings::save::NOTICE_DURATION;
+use settings::music;
 use settings::menus::title::controls;
+#[cfg(debug_assertions)]
+use settings::level::SKIP_KEY;
+use config;
+use viewport;
+use action::Action;
+use gamepad::{ self, Gamepad };
 use level_manager::LevelManager;
+use music::MusicManager;
+use level_manager::level_info::LevelStatus;
+use editor::Editor;
 use menu::title_menu_manager::prelude::*;
+use menu::MenuInput;
 use menu::buttons::ButtonType;
 use frames_counter::FramesCounter;
+use achievements::Toasts;
+use text_box::TextBox;
+use self::helpers::*;
+use self::save_file::LoadedSave;
+use self::profiles::Profiles;
 
 enum Scene {
   Title,
   Ingame,
+  Editor,
 }
 
 pub struct GameState {
   window_size:   Size,
   window_rect:   Rect,
   input_manager: InputManager,
+  gamepad:       Gamepad,
   level_manager: LevelManager,
   menu_manager:  TitleMenuManager,
+  editor:        Option<Editor>,
+  profiles:      Profiles,
+  // The active profile's save is from a newer version of the game
+  save_too_new:  bool,
+  notice:        Option<(TextBox, Instant)>,
+  toasts:        Toasts,
   running:       bool,
   last_update:   Instant,
   scene:         Scene,
-  title_song:    Option<audio::Source>,
+  music:         MusicManager,
   fps:           FramesCounter,
   ups:           FramesCounter,
 
+  #[cfg(debug_assertions)]
   last_log:      Instant,
 }
 
 impl GameState {
   pub fn new(ctx: &mut Context, window_size: Size) -> GameResult<Self> {
+    let level_manager = LevelManager::new(ctx, window_size.clone());
+    let pack_names = level_manager.level_packs().iter()
+      .map( |pack| pack.name.clone() )
+      .collect::<Vec<String>>();
+    let profiles = Profiles::load(ctx)?;
+    let menu_manager = TitleMenuManager::new(
+      ctx,
+      window_size.clone(),
+      &level_manager.level_infos(),
+      &pack_names,
+      &profile_names(&profiles),
+      profiles.active_index()
+    )?;
     Ok(Self {
       window_size:   window_size.clone(),
       window_rect:   Rect::new(Point::new(0.0, 0.0), window_size.clone(), Origin::TopLeft),
       input_manager: InputManager::new(),
-      level_manager: LevelManager::new(ctx, window_size.clone()),
+      gamepad:       Gamepad::new(),
+      level_manager,
       running:       true,
       last_update:   Instant::now(),
-      menu_manager:  TitleMenuManager::new(ctx, window_size.clone())?,
+      menu_manager,
+      editor:        None,
+      profiles,
+      save_too_new:  false,
+      notice:        None,
+      toasts:        Toasts::new(&window_size),
       scene:         Scene::Title,
-      title_song:    None,
+      music:         MusicManager::new(),
       fps:           FramesCounter::new(),
       ups:           FramesCounter::new(),
 
+      #[cfg(debug_assertions)]
       last_log:      Instant::now(),
     })
   }
 
   pub fn init(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.load(ctx)?;
-    self.play_song(ctx)?;
     Ok(())
   }
 
-  fn play_song(&mut self, ctx: &mut Context) -> GameResult<()> {
-    if !MUTED {
-      if let Some(song) = &mut self.title_song {
-        song.stop();
-      }
-      let mut title_song = audio::Source::new(ctx, ::join_str(res::AUDIO, &"titletheme.ogg"))?;
-      title_song.set_volume(VOLUME);
-      title_song.set_repeat(true);
-      title_song.play()?;
-      self.title_song = Some(title_song);
-    }
+  // The music follows the scene; switching tracks crossfades between them.
+  fn update_music(&mut self, ctx: &mut Context) -> GameResult<()> {
+    match self.scene {
+      Scene::Title  => self.music.play(ctx, music::TITLE_TRACK)?,
+      Scene::Ingame => if let Some(song_name) = self.level_manager.song_name() {
+        self.music.play(ctx, &song_name)?;
+      },
+      Scene::Editor => self.music.stop(),
+    };
+    self.music.update();
     Ok(())
   }
 
-  fn stop_song(&mut self) {
-    if let Some(song) = &mut self.title_song {
-      song.stop();
-    }
-    self.title_song = None;
-  }
-
   fn display_score_in_title(&mut self, ctx: &mut Context) -> GameResult<()> {
     let score = self.level_manager.total_score();
     if score.any() {
       self.menu_manager.title.display_score(ctx, &score)?;
+    } else {
+      self.menu_manager.title.hide_score();
+    }
+    Ok(())
+  }
+
+  fn update_statistics(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let total  = self.level_manager.lifetime_stats().total();
+    let levels = self.level_manager.level_stats();
+    self.menu_manager.set_statistics(ctx, &total, levels)
+  }
+
+  fn update_achievements(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let achievements = self.level_manager.achievements().infos();
+    self.menu_manager.set_achievements(ctx, achievements)
+  }
+
+  fn update_level_select(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let levels = self.level_manager.level_infos();
+    // The level select becomes available once there is more than one level to choose from
+    if levels.iter().skip(1).any( |level| level.status != LevelStatus::Locked ) {
+      self.menu_manager.show_level_select();
+    } else {
+      self.menu_manager.hide_level_select();
     }
+    self.menu_manager.set_levels(ctx, &levels)
+  }
+
+  fn select_pack(&mut self, ctx: &mut Context, pack_index: usize) -> GameResult<()> {
+    self.level_manager.select_pack(pack_index);
+    self.update_level_select(ctx)?;
+    self.display_score_in_title(ctx)?;
     Ok(())
   }
 
   fn update_ingame(&mut self, ctx: &mut Context) -> GameResult<()> {
     if self.level_manager.save_data.is_some() {
-      self.save()?;
+      if let Err(e) = self.save() {
+        eprintln!("Couldn't save: {}", e);
+        self.show_notice(ctx, "Couldn't save your progress.")?;
+      }
     }
     if self.level_manager.to_title {
       self.level_manager.to_title = false;
-      self.play_song(ctx)?;
       self.display_score_in_title(ctx)?;
+      self.update_level_select(ctx)?;
+      self.update_statistics(ctx)?;
+      self.update_achievements(ctx)?;
       self.scene = Scene::Title;
       if self.level_manager.to_thank_you {
         self.level_manager.to_thank_you = false;
         self.menu_manager.load_thank_you(ctx, &self.window_size)?;
       }
     }
-    self.level_manager.keys_pressed(ctx, self.input_manager.keys_pressed());
-    self.level_manager.keys_down(ctx, self.input_manager.keys_down());
-    self.level_manager.keys_up(ctx, self.input_manager.keys_up());
+    let (pressed, down, up) = config::with( |c| (
+      merge_actions(c.bindings.actions(self.input_manager.keys_pressed()), self.gamepad.actions_pressed()),
+      merge_actions(c.bindings.actions(self.input_manager.keys_down()),    self.gamepad.actions_down()),
+      merge_actions(c.bindings.actions(self.input_manager.keys_up()),      self.gamepad.actions_up())
+    ));
+    if down.contains(&Action::Mute) {
+      self.toggle_mute();
+    }
+    // An open dialogue takes all input; releasing keys still reaches the level,
+    // so nothing keeps moving once the dialogue closes.
+    if self.level_manager.in_dialogue() {
+      self.level_manager.dialogue_actions_down(&down);
+    } else {
+      self.level_manager.actions_pressed(ctx, &pressed);
+      self.level_manager.actions_down(ctx, &down);
+    }
+    self.level_manager.actions_up(ctx, &up);
     self.level_manager.update(ctx)?;
+    self.toasts.push(self.level_manager.drain_unlocked_achievements());
     Ok(())
   }
 
   fn update_menu(&mut self, ctx: &mut Context) -> GameResult<()> {
-    if self.level_manager.beat_game {
-      self.menu_manager.show_level_select();
-    }
     let mut start_game   = false;
     let mut quit         = false;
     if let Some(clicked) = self.menu_manager.get_clicked() {
@@ -135,35 +222,90 @@ impl GameState {
         _ => ()
       }
     }
+    if let Some(pack_index) = self.menu_manager.load_pack {
+      self.menu_manager.load_pack = None;
+      self.select_pack(ctx, pack_index)?;
+    }
     if let Some(level_index) = self.menu_manager.load_level {
       self.menu_manager.load_level = None;
       self.start_level(ctx, level_index)?;
     }
+    self.update_profiles(ctx)?;
     if start_game {
       self.start_game(ctx)?;
     } else if quit {
       ctx.quit()?;
     }
-    self.menu_manager.update()?;
+    self.menu_manager.update(ctx)?;
     Ok(())
   }
 
   fn start_game(&mut self, ctx: &mut Context) -> GameResult<()> {
-    self.stop_song();
     self.level_manager.next_level(ctx)?;
     self.scene = Scene::Ingame;
     Ok(())
   }
 
   fn start_level(&mut self, ctx: &mut Context, level_index: usize) -> GameResult<()> {
-    self.stop_song();
     self.level_manager.load_level(ctx, level_index)?;
     self.scene = Scene::Ingame;
     Ok(())
   }
 
+  fn open_editor(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let level_paths = {
+      let pack = self.level_manager.pack();
+      pack.level_names.iter()
+        .map( |name| pack.level_filepath(name) )
+        .collect()
+    };
+    match Editor::new(ctx, self.window_size.clone(), level_paths, 0) {
+      Ok(editor) => {
+        self.editor = Some(editor);
+        self.scene = Scene::Editor;
+        // The key that opened the editor mustn't count as pressed in it too
+        self.input_manager.update();
+      }
+      Err(e) => self.show_notice(ctx, &format!("Can't open level: {}", e))?,
+    }
+    Ok(())
+  }
+
+  fn update_editor(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let mut to_title = false;
+    if let Some(editor) = &mut self.editor {
+      editor.keys_pressed(self.input_manager.keys_pressed());
+      editor.keys_down(ctx, self.input_manager.keys_down())?;
+      editor.keys_up(self.input_manager.keys_up());
+      editor.update(ctx)?;
+      to_title = editor.to_title;
+    }
+    if to_title {
+      self.editor = None;
+      self.scene = Scene::Title;
+      self.menu_manager.clear_thumbnails();
+      self.update_level_select(ctx)?;
+    }
+    Ok(())
+  }
+
+  // Music and sound effects are muted together, by the config's `muted`
+  fn toggle_mute(&mut self) {
+    let mut config = config::get();
+    config.muted = !config.muted;
+    if let Err(e) = config.save() {
+      eprintln!("Couldn't save the config: {}", e);
+    }
+    config::set(config);
+    self.music.apply_mute();
+  }
+
+  fn show_notice(&mut self, ctx: &mut Context, msg: &str) -> GameResult<()> {
+    self.notice = Some((new_notice(ctx, &self.window_size, msg)?, Instant::now()));
+    Ok(())
+  }
+
   fn save(&mut self) -> GameResult<()> {
-    let mut file = fs::File::create(SAVEFILE)?;
     let mut data = object!{
       "level_manager" => object!{},
       "beat_game"     => self.level_manager.beat_game,
@@ -174,17 +316,98 @@ impl GameState {
       }
       self.level_manager.save_data = None;
     }
-    write!(file, "{}", data).expect("Should write to savefile");
-    Ok(())
+    // A save from a newer version of the game is never overwritten
+    if self.save_too_new { return Ok(()); }
+    self.profiles.save_file().write_profile(&data, &self.profiles.active().name)
   }
 
-  fn load(&mut self, ctx: &mut Context) -> GameResult<()> {
-    if let Ok(mut file) = fs::File::open(SAVEFILE) {
-      let mut json_raw = String::new();
-      file.read_to_string(&mut json_raw)?;
-      let data = json::parse(&json_raw).expect("Should parse JSON string");
+  fn back_to_title_menu(&mut self) {
+    // Going back from a level's leaderboard only closes the leaderboard
+    if self.menu_manager.in_level_select_menu() && self.menu_manager.level_select.is_showing_leaderboard() {
+      self.menu_manager.level_select.hide_leaderboard();
+      return;
+    }
+    if self.menu_manager.in_profiles_menu() {
+      self.menu_manager.profiles.finish_rename();
+    }
+    self.menu_manager.to_title_menu();
+  }
 
-      // LevelSelectMenu button
+  // Reload everything from the newly selected profile's save
+  fn switch_profile(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.level_manager = LevelManager::new(ctx, self.window_size.clone());
+    self.refresh_profiles(ctx)?;
+    self.load(ctx)
+  }
+
+  fn refresh_profiles(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let names = profile_names(&self.profiles);
+    self.menu_manager.set_profiles(ctx, &names, self.profiles.active_index())
+  }
+
+  fn update_profiles(&mut self, ctx: &mut Context) -> GameResult<()> {
+    use menu::title_menu_manager::ProfileAction::*;
+    let action = if let Some(action) = self.menu_manager.profile_action.take() {
+      action
+    } else { return Ok(()); };
+    if let Rename = action {
+      let name = self.profiles.active().name.clone();
+      return self.menu_manager.profiles.start_rename(ctx, &name);
+    }
+    let result = match action {
+      Select(i) => self.profiles.select(i),
+      New       => {
+        let name = format!("Player {}", self.profiles.profiles().len() + 1);
+        self.profiles.create(&name)
+      },
+      Copy      => self.profiles.copy_active(),
+      Delete    => self.profiles.delete_active(),
+      Rename    => Ok(()),
+    };
+    // The current profile stays loaded if the action failed
+    match result {
+      Ok(()) => self.switch_profile(ctx),
+      Err(e) => {
+        eprintln!("Profile action failed: {}", e);
+        self.show_notice(ctx, &format!("Couldn't update the profile: {}", e))
+      },
+    }
+  }
+
+  fn finish_profile_rename(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(name) = self.menu_manager.profiles.finish_rename() {
+      if let Err(e) = self.profiles.rename_active(&name) {
+        eprintln!("Couldn't rename profile: {}", e);
+        self.show_notice(ctx, "Couldn't rename the profile.")?;
+      }
+    }
+    self.refresh_profiles(ctx)?;
+    let name = self.profiles.active().name.clone();
+    self.level_manager.set_profile_name(&name);
+    self.menu_manager.title.display_profile(ctx, &name)
+  }
+
+  fn load(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.save_too_new = false;
+    let data_opt = match self.profiles.save_file().read() {
+      LoadedSave::None                 => None,
+      LoadedSave::TooNew               => {
+        self.save_too_new = true;
+        self.show_notice(ctx, "This profile is from a newer version of the game, progress won't be saved.")?;
+        None
+      }
+      LoadedSave::Save(data)           => Some(data),
+      LoadedSave::Recovered(data, msg) => {
+        self.show_notice(ctx, &msg)?;
+        Some(data)
+      }
+    };
+    let name = self.profiles.active().name.clone();
+    self.menu_manager.title.display_profile(ctx, &name)?;
+    self.level_manager.set_profile_name(&name);
+    self.level_manager.set_ghost_dir(self.profiles.ghost_dir());
+    if let Some(data) = data_opt {
+      // Beat the main campaign
       if let Some(beat_game) = data["beat_game"].as_bool() {
         self.level_manager.beat_game = beat_game;
       }
@@ -193,9 +416,23 @@ impl GameState {
       if data["level_manager"].is_object() {
         self.level_manager.load_level_json(&data["level_manager"]);
       }
+    }
 
-      // Display total best score
-      self.display_score_in_title(ctx)?;
+    // Display total best score
+    self.display_score_in_title(ctx)?;
+    self.update_level_select(ctx)?;
+    self.update_statistics(ctx)?;
+    self.update_achievements(ctx)?;
+    Ok(())
+  }
+
+  fn draw_notice(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let expired = if let Some((notice, shown_at)) = &self.notice {
+      notice.draw(ctx)?;
+      Instant::now() - *shown_at > Duration::from_secs(NOTICE_DURATION)
+    } else { false };
+    if expired {
+      self.notice = None;
     }
     Ok(())
   }
@@ -205,12 +442,20 @@ impl GameState {
     Ok(())
   }
 
+  fn draw_editor(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(editor) = &mut self.editor {
+      editor.draw(ctx)?;
+    }
+    Ok(())
+  }
+
   fn draw_menu(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.menu_manager.draw(ctx)?;
     Ok(())
   }
 
-  // TODO: TEMPORARY, FOR DEBUGGING
+  // Debug builds only: logs UPS and FPS, O adds artificial lag and N skips the level
+  #[cfg(debug_assertions)]
   fn update_debug(&mut self) {
     let now = Instant::now();
     if now - self.last_log > Duration::from_secs(1) {
@@ -218,37 +463,77 @@ impl GameState {
                self.ups.avg(), self.fps.avg());
       self.last_log = now;
     }
-    // TODO: TEMPORARY ARTIFICIAL LAG!!!
     for keycode in self.input_manager.keys_pressed() {
       if let Keycode::O = keycode {
         std::thread::sleep(Duration::new(0, 50_000_000));
       }
     }
+    if let Scene::Ingame = self.scene {
+      if self.input_manager.keys_down().contains(&SKIP_KEY) {
+        self.level_manager.skip_level();
+      }
+    }
   }
 }
 
+// Keyboard and gamepad may both trigger the same action
+fn merge_actions(mut actions: Vec<Action>, other: &Vec<Action>) -> Vec<Action> {
+  for &action in other {
+    if !actions.contains(&action) {
+      actions.push(action);
+    }
+  }
+  actions
+}
+
+fn profile_names(profiles: &Profiles) -> Vec<String> {
+  profiles.profiles().iter()
+    .map( |profile| profile.name.clone() )
+    .collect()
+}
+
 impl event::EventHandler for GameState {
   fn key_down_event(&mut self,
                     ctx:     &mut Context,
                     keycode: Keycode,
                     _keymod: event::Mod,
                     repeat:  bool) {
+    // The key is bound instead of being used as input
+    if let Scene::Ingame = self.scene {
+      if self.level_manager.is_rebinding() {
+        if !repeat {
+          self.level_manager.rebind(ctx, keycode).expect("Should rebind key");
+        }
+        return;
+      }
+    }
     self.input_manager.key_down(keycode, _keymod, repeat);
     if let Keycode::Escape = keycode {
       ctx.quit().expect("Should quit game");
     }
     if let Scene::Title = self.scene {
+      if self.menu_manager.profiles.is_renaming() {
+        match keycode {
+          controls::CONFIRM  => self.finish_profile_rename(ctx).expect("Should rename profile"),
+          Keycode::Backspace => self.menu_manager.profiles.backspace(ctx).expect("Should update profile name"),
+          _                  => (),
+        }
+        return;
+      }
       match keycode {
         controls::PLAY if self.menu_manager.in_title_menu() =>
           self.start_game(ctx).expect("Should start game"),
         controls::BACK if self.menu_manager.in_title_menu() =>
           ctx.quit().expect("Should quit game"),
-        controls::BACK if self.menu_manager.in_level_select_menu() || self.menu_manager.in_thank_you_menu() =>
-          self.menu_manager.to_title_menu(),
+        controls::BACK => self.back_to_title_menu(),
         controls::LEVEL_SELECT if self.menu_manager.is_level_select_available() =>
           self.menu_manager.to_level_select_menu(),
-        // TODO: TEMPORARY!!!
-        controls::LEVEL_SELECT => self.menu_manager.show_level_select(),
+        controls::LEVEL_PACKS if self.menu_manager.is_level_packs_available() =>
+          self.menu_manager.to_level_packs_menu(),
+        controls::PROFILES if self.menu_manager.in_title_menu() =>
+          self.menu_manager.to_profiles_menu(),
+        controls::EDITOR if self.menu_manager.in_title_menu() =>
+          self.open_editor(ctx).expect("Should open editor"),
         Keycode::T => self.menu_manager.load_thank_you(ctx, &self.window_size).expect("Load ThankYouMenu"),
         _          => (),
       }
@@ -263,33 +548,83 @@ impl event::EventHandler for GameState {
     self.input_manager.key_up(keycode, _keymod, repeat);
   }
 
-  fn mouse_button_down_event(&mut self, _ctx: &mut Context, _btn: MouseButton, x: i32, y: i32) {
+  fn controller_button_down_event(&mut self, _ctx: &mut Context, btn: Button, _instance_id: i32) {
+    self.gamepad.button_down(btn);
+    if let Some(input) = gamepad::menu_input(btn) {
+      match self.scene {
+        Scene::Title  => if let MenuInput::Back = input {
+          self.back_to_title_menu();
+        } else {
+          self.menu_manager.menu_input(input);
+        },
+        Scene::Ingame => self.level_manager.menu_input(input),
+        Scene::Editor => (),
+      }
+    }
+  }
+
+  fn controller_button_up_event(&mut self, _ctx: &mut Context, btn: Button, _instance_id: i32) {
+    self.gamepad.button_up(btn);
+  }
+
+  fn controller_axis_event(&mut self, _ctx: &mut Context, axis: Axis, value: i16, _instance_id: i32) {
+    self.gamepad.axis(axis, value);
+  }
+
+  fn text_input_event(&mut self, ctx: &mut Context, text: String) {
+    if let Scene::Title = self.scene {
+      if self.menu_manager.profiles.is_renaming() {
+        self.menu_manager.profiles.text_input(ctx, &text).expect("Should update profile name");
+      }
+    }
+  }
+
+  fn mouse_button_down_event(&mut self, ctx: &mut Context, btn: MouseButton, x: i32, y: i32) {
     // self.input_manager.add_mouse_down(btn, x, y);
+    let (x, y) = viewport::to_virtual(ctx, x, y);
     match self.scene {
       Scene::Title  => self.menu_manager.mouse_down(x, y),
       Scene::Ingame => self.level_manager.mouse_down(x, y),
+      Scene::Editor => if let Some(editor) = &mut self.editor {
+        editor.mouse_down(ctx, btn, x, y).expect("Should handle editor mouse input");
+      },
     }
   }
 
-  fn mouse_motion_event(&mut self, _ctx: &mut Context, state: MouseState, _x: i32, _y: i32, xrel: i32, yrel: i32) {
-    if state.left() || state.right() {
-      self.level_manager.mouse_drag(xrel, yrel);
+  fn mouse_motion_event(&mut self, ctx: &mut Context, state: MouseState, x: i32, y: i32, xrel: i32, yrel: i32) {
+    let (x, y)       = viewport::to_virtual(ctx, x, y);
+    let (xrel, yrel) = viewport::to_virtual_rel(ctx, xrel, yrel);
+    match self.scene {
+      Scene::Editor => if let Some(editor) = &mut self.editor {
+        editor.mouse_motion(state, x, y, xrel, yrel);
+      },
+      _ => if state.left() || state.right() {
+        self.level_manager.mouse_drag(xrel, yrel);
+      },
     }
   }
 
+  fn resize_event(&mut self, ctx: &mut Context, _width: u32, _height: u32) {
+    config::with( |c| viewport::apply(ctx, c) ).expect("Should update viewport");
+  }
+
   fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
     let now = Instant::now();
-    if !self.running || now - self.last_update < Duration::from_millis(UPDATE_INTERVAL_MS) {
+    if !self.running || now - self.last_update < Duration::from_millis(config::with( |c| c.update_interval_ms() )) {
       return Ok(());
     }
 
-    // TODO: TEMPORARY, FOR DEBUGGING
+    #[cfg(debug_assertions)]
     self.update_debug();
 
+    self.gamepad.update();
     match self.scene {
       Scene::Title  => self.update_menu(ctx)?,
       Scene::Ingame => self.update_ingame(ctx)?,
+      Scene::Editor => self.update_editor(ctx)?,
     };
+    self.update_music(ctx)?;
+    self.toasts.update(ctx)?;
 
     self.input_manager.update();
     self.last_update = now;
@@ -303,7 +638,11 @@ impl event::EventHandler for GameState {
     match self.scene {
       Scene::Title  => self.draw_menu(ctx)?,
       Scene::Ingame => self.draw_ingame(ctx)?,
+      Scene::Editor => self.draw_editor(ctx)?,
     };
+    self.draw_notice(ctx)?;
+    self.toasts.draw(ctx)?;
+    viewport::draw_letterbox(ctx, &self.window_size)?;
 
     graphics::present(ctx);
     ::ggez::timer::yield_now();
@@ -0,0 +1,190 @@
+use std::fs::{ self, File };
+use std::io::prelude::*;
+use std::path::PathBuf;
+
+use ggez::{
+  Context,
+  GameResult,
+  GameError,
+};
+use json::JsonValue;
+
+use settings::save::*;
+use settings::ghosts;
+use json_file;
+use super::save_file::{ SaveFile, LoadedSave, read_legacy };
+
+#[derive(Clone, Debug)]
+pub struct Profile {
+  pub id:   String,
+  pub name: String,
+}
+
+// Every profile has its own save file in the profiles directory, named by the profile's id.
+// The profile's name is stored in its save data.
+pub struct Profiles {
+  directory: PathBuf,
+  profiles:  Vec<Profile>,
+  active:    usize,
+}
+
+impl Profiles {
+  pub fn load(ctx: &Context) -> GameResult<Self> {
+    let data_dir  = ctx.filesystem.get_user_data_dir();
+    let directory = data_dir.join(PROFILES_DIR);
+    fs::create_dir_all(&directory)?;
+
+    let mut profiles = Self {
+      directory,
+      profiles: Vec::new(),
+      active:   0,
+    };
+    profiles.read_profiles();
+
+    if profiles.profiles.is_empty() {
+      // Saves from before profiles existed become the first profile
+      let save_file = profiles.save_file_for(DEFAULT_PROFILE_ID);
+      let data = match SaveFile::with_path(data_dir.join(FILENAME)).read() {
+        LoadedSave::Save(data) | LoadedSave::Recovered(data, _) => data,
+        LoadedSave::None                                         => read_legacy().unwrap_or(object!{}),
+        LoadedSave::TooNew                                       => object!{},
+      };
+      save_file.write_profile(&data, DEFAULT_PROFILE_NAME)?;
+      profiles.read_profiles();
+    }
+
+    let active_id = read_active_id(&profiles.directory);
+    profiles.active = profiles.profiles.iter()
+      .position( |profile| Some(&profile.id) == active_id.as_ref() )
+      .unwrap_or(0);
+    Ok(profiles)
+  }
+
+  fn read_profiles(&mut self) {
+    let mut profiles = Vec::new();
+    if let Ok(entries) = fs::read_dir(&self.directory) {
+      for entry in entries.filter_map( |entry| entry.ok() ) {
+        let path = entry.path();
+        if path.extension().and_then( |ext| ext.to_str() ) != Some("json") { continue; }
+        let id = if let Some(stem) = path.file_stem().and_then( |stem| stem.to_str() ) {
+          stem.to_string()
+        } else { continue; };
+        let name = match SaveFile::with_path(path.clone()).read() {
+          LoadedSave::Save(data) | LoadedSave::Recovered(data, _) =>
+            data["profile_name"].as_str().unwrap_or(&id).to_string(),
+          LoadedSave::TooNew => id.clone(),
+          LoadedSave::None   => continue,
+        };
+        profiles.push(Profile { id, name });
+      }
+    }
+    profiles.sort_by( |a, b| a.id.cmp(&b.id) );
+    self.profiles = profiles;
+  }
+
+  fn save_file_for(&self, id: &str) -> SaveFile {
+    SaveFile::with_path(self.directory.join(::join_str(id, ".json")))
+  }
+
+  fn next_id(&self) -> String {
+    let mut n = self.profiles.len();
+    loop {
+      let id = format!("profile_{}", n);
+      if !self.profiles.iter().any( |profile| profile.id == id ) {
+        return id;
+      }
+      n += 1;
+    }
+  }
+
+  pub fn profiles(&self) -> &Vec<Profile> {
+    &self.profiles
+  }
+
+  pub fn active_index(&self) -> usize {
+    self.active
+  }
+
+  pub fn active(&self) -> &Profile {
+    &self.profiles[self.active]
+  }
+
+  pub fn save_file(&self) -> SaveFile {
+    self.save_file_for(&self.active().id)
+  }
+
+  // The active profile's recorded ghosts, in a directory next to its save file
+  pub fn ghost_dir(&self) -> PathBuf {
+    self.directory.join(::join_str(&self.active().id, ghosts::DIR_SUFFIX))
+  }
+
+  pub fn select(&mut self, index: usize) -> GameResult<()> {
+    if index >= self.profiles.len() { return Ok(()); }
+    self.active = index;
+    write_active_id(&self.directory, &self.profiles[index].id)
+  }
+
+  fn select_id(&mut self, id: &str) -> GameResult<()> {
+    self.read_profiles();
+    let index = self.profiles.iter().position( |profile| profile.id == id ).unwrap_or(0);
+    self.select(index)
+  }
+
+  pub fn create(&mut self, name: &str) -> GameResult<()> {
+    let id = self.next_id();
+    self.save_file_for(&id).write_profile(&object!{}, name)?;
+    self.select_id(&id)
+  }
+
+  // The active profile's save data, to be written again with changes
+  fn read_active(&self) -> GameResult<JsonValue> {
+    match self.save_file().read() {
+      LoadedSave::Save(data) | LoadedSave::Recovered(data, _) => Ok(data),
+      LoadedSave::None                                         => Ok(object!{}),
+      LoadedSave::TooNew                                       =>
+        Err(GameError::from("Profile is from a newer version of the game".to_string())),
+    }
+  }
+
+  pub fn copy_active(&mut self) -> GameResult<()> {
+    let data = self.read_active()?;
+    let name = format!("{} (copy)", self.active().name);
+    let id = self.next_id();
+    self.save_file_for(&id).write_profile(&data, &name)?;
+    self.select_id(&id)
+  }
+
+  pub fn rename_active(&mut self, name: &str) -> GameResult<()> {
+    let data = self.read_active()?;
+    self.save_file().write_profile(&data, name)?;
+    self.profiles[self.active].name = name.to_string();
+    Ok(())
+  }
+
+  // The last remaining profile can't be deleted.
+  pub fn delete_active(&mut self) -> GameResult<()> {
+    if self.profiles.len() <= 1 {
+      return Err(GameError::from("Can't delete the only profile".to_string()));
+    }
+    self.save_file().delete()?;
+    let ghost_dir = self.ghost_dir();
+    if ghost_dir.exists() {
+      if let Err(e) = fs::remove_dir_all(&ghost_dir) {
+        eprintln!("Couldn't delete ghosts {}: {}", ghost_dir.display(), e);
+      }
+    }
+    self.read_profiles();
+    self.select(0)
+  }
+}
+
+fn read_active_id(directory: &PathBuf) -> Option<String> {
+  let mut id = String::new();
+  File::open(directory.join(ACTIVE_PROFILE_FILE)).ok()?
+    .read_to_string(&mut id).ok()?;
+  Some(id.trim().to_string())
+}
+
+fn write_active_id(directory: &PathBuf, id: &str) -> GameResult<()> {
+  json_file::write_str(&directory.join(ACTIVE_PROFILE_FILE), id)
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use ggez::{
+  GameResult,
+  GameError,
+};
+use json::JsonValue;
+
+use settings::save::*;
+use json_file;
+
+pub enum LoadedSave {
+  // No save exists yet
+  None,
+  Save(JsonValue),
+  // The save couldn't be read; the JSON is the backup or an empty profile,
+  // the String describes what went wrong.
+  Recovered(JsonValue, String),
+  // The save is from a newer version of the game; it is neither loaded nor overwritten.
+  TooNew,
+}
+
+// A save is written to a temporary file first, which then replaces the save file,
+// so a crash while saving can't leave a half-written save behind.
+// The previous save is kept as a backup.
+pub struct SaveFile {
+  filepath: PathBuf,
+}
+
+impl SaveFile {
+  pub fn with_path(filepath: PathBuf) -> Self {
+    Self { filepath }
+  }
+
+  fn backup_path(&self) -> PathBuf {
+    self.filepath.with_extension(BACKUP_EXTENSION)
+  }
+
+  pub fn write(&self, data: &JsonValue) -> GameResult<()> {
+    let mut data = data.clone();
+    data["version"] = VERSION.into();
+    match read_json(&self.filepath) {
+      Ok(ref old) if is_too_new(old) =>
+        return Err(GameError::from("Save file is from a newer version of the game".to_string())),
+      // Only a readable save may replace the backup
+      Ok(_)  => { fs::copy(&self.filepath, self.backup_path())?; },
+      Err(_) => (),
+    }
+    json_file::write(&self.filepath, &data)
+  }
+
+  pub fn write_profile(&self, data: &JsonValue, profile_name: &str) -> GameResult<()> {
+    let mut data = data.clone();
+    data["profile_name"] = profile_name.into();
+    self.write(&data)
+  }
+
+  pub fn delete(&self) -> GameResult<()> {
+    fs::remove_file(&self.filepath)?;
+    let backup_path = self.backup_path();
+    if backup_path.exists() {
+      fs::remove_file(backup_path)?;
+    }
+    Ok(())
+  }
+
+  pub fn read(&self) -> LoadedSave {
+    if !self.filepath.exists() {
+      return LoadedSave::None;
+    }
+    match read_json(&self.filepath) {
+      Ok(ref data) if is_too_new(data) => LoadedSave::TooNew,
+      Ok(data) => LoadedSave::Save(data),
+      Err(e)   => {
+        eprintln!("Couldn't read save file {}: {}", self.filepath.display(), e);
+        match read_json(&self.backup_path()) {
+          Ok(ref backup) if is_too_new(backup) => LoadedSave::TooNew,
+          Ok(backup) => LoadedSave::Recovered(backup, "Save file was damaged, restored the backup.".to_string()),
+          Err(_)     => LoadedSave::Recovered(object!{}, "Save file was damaged, starting with an empty profile.".to_string()),
+        }
+      }
+    }
+  }
+}
+
+fn read_json(filepath: &Path) -> GameResult<JsonValue> {
+  let data = json_file::read(filepath)?;
+  if !data.is_object() {
+    return Err(GameError::from("Save data must be an object".to_string()));
+  }
+  Ok(data)
+}
+
+fn is_too_new(data: &JsonValue) -> bool {
+  data["version"].as_u32().unwrap_or(0) > VERSION
+}
+
+// Saves used to be written to the current working directory, without a version.
+pub fn read_legacy() -> Option<JsonValue> {
+  read_json(Path::new(LEGACY_FILENAME)).ok()
+    .filter( |data| !is_too_new(data) )
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use ggez::event::{ Button, Axis };
+use noframe::geo::mask::misc::Side;
+
+use settings::gamepad::*;
+use action::Action;
+use menu::MenuInput;
+
+// Tracks the state of all connected gamepads and turns it into actions,
+// the same ones the keyboard bindings produce.
+pub struct Gamepad {
+  buttons: Vec<Button>,
+  axes:    HashMap<Axis, i16>,
+  held:    Vec<Action>,
+  down:    Vec<Action>,
+  up:      Vec<Action>,
+}
+
+impl Gamepad {
+  pub fn new() -> Self {
+    Self {
+      buttons: Vec::new(),
+      axes:    HashMap::new(),
+      held:    Vec::new(),
+      down:    Vec::new(),
+      up:      Vec::new(),
+    }
+  }
+
+  pub fn button_down(&mut self, button: Button) {
+    if !self.buttons.contains(&button) {
+      self.buttons.push(button);
+    }
+  }
+
+  pub fn button_up(&mut self, button: Button) {
+    self.buttons.retain( |&b| b != button );
+  }
+
+  pub fn axis(&mut self, axis: Axis, value: i16) {
+    self.axes.insert(axis, value);
+  }
+
+  // Should be called once per update, before the actions are read.
+  pub fn update(&mut self) {
+    let held = self.held_actions();
+    self.down = held.iter().filter( |action| !self.held.contains(action) ).cloned().collect();
+    self.up   = self.held.iter().filter( |action| !held.contains(action) ).cloned().collect();
+    self.held = held;
+  }
+
+  pub fn actions_pressed(&self) -> &Vec<Action> {
+    &self.held
+  }
+
+  pub fn actions_down(&self) -> &Vec<Action> {
+    &self.down
+  }
+
+  pub fn actions_up(&self) -> &Vec<Action> {
+    &self.up
+  }
+
+  fn axis_value(&self, axis: Axis) -> i16 {
+    self.axes.get(&axis).cloned().unwrap_or(0)
+  }
+
+  fn held_actions(&self) -> Vec<Action> {
+    let mut actions = Vec::new();
+    for &button in &self.buttons {
+      if let Some(action) = button_action(button) {
+        actions.push(action);
+      }
+    }
+    let left_x  = self.axis_value(Axis::LeftX);
+    let right_x = self.axis_value(Axis::RightX);
+    let right_y = self.axis_value(Axis::RightY);
+    for &(held, action) in [
+      (left_x  < -STICK_DEADZONE,                               Action::MoveLeft),
+      (left_x  >  STICK_DEADZONE,                               Action::MoveRight),
+      (right_x < -STICK_DEADZONE,                               Action::CameraLeft),
+      (right_x >  STICK_DEADZONE,                               Action::CameraRight),
+      (right_y < -STICK_DEADZONE,                               Action::CameraUp),
+      (right_y >  STICK_DEADZONE,                               Action::CameraDown),
+      (self.axis_value(Axis::TriggerLeft)  > TRIGGER_THRESHOLD, Action::CommandLeft),
+      (self.axis_value(Axis::TriggerRight) > TRIGGER_THRESHOLD, Action::CommandRight),
+    ].iter() {
+      if held && !actions.contains(&action) {
+        actions.push(action);
+      }
+    }
+    actions
+  }
+}
+
+fn button_action(button: Button) -> Option<Action> {
+  match button {
+    Button::DPadLeft        => Some(Action::MoveLeft),
+    Button::DPadRight       => Some(Action::MoveRight),
+    controls::JUMP          => Some(Action::Jump),
+    controls::PAUSE         => Some(Action::Pause),
+    controls::RESET         => Some(Action::Reset),
+    controls::NEXT          => Some(Action::Next),
+    controls::TO_TITLE      => Some(Action::ToTitle),
+    controls::CENTER_CAMERA => Some(Action::CenterCamera),
+    controls::PREV_CHILD    => Some(Action::PrevChild),
+    controls::NEXT_CHILD    => Some(Action::NextChild),
+    _                       => None,
+  }
+}
+
+pub fn menu_input(button: Button) -> Option<MenuInput> {
+  match button {
+    Button::DPadUp         => Some(MenuInput::Move(Side::Top)),
+    Button::DPadDown       => Some(MenuInput::Move(Side::Bottom)),
+    Button::DPadLeft       => Some(MenuInput::Move(Side::Left)),
+    Button::DPadRight      => Some(MenuInput::Move(Side::Right)),
+    controls::MENU_CONFIRM => Some(MenuInput::Confirm),
+    controls::MENU_BACK    => Some(MenuInput::Back),
+    _                      => None,
+  }
+}
@@ -0,0 +1,189 @@
+use std::path::Path;
+
+use ggez::{
+  Context,
+  GameResult,
+  GameError,
+  graphics,
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+
+use settings::ghosts::*;
+use persons::{ AnimState, PersonAnimations };
+use persons::player::Player;
+use persons::children::{ Child, ChildType };
+use animation::Facing;
+use score::TickType;
+use json_file;
+
+const PLAYER: &str = "player";
+
+#[derive(Clone, Copy)]
+enum GhostPerson {
+  Player,
+  Child(ChildType),
+}
+
+struct GhostFrame {
+  point:  Point,
+  state:  AnimState,
+  facing: Facing,
+}
+
+impl GhostFrame {
+  // Stored as `[x, y, animation state, facing]`
+  fn from_json(json: &JsonValue) -> Option<Self> {
+    Some(Self {
+      point:  Point::new(json[0].as_f32()?, json[1].as_f32()?),
+      state:  match json[2].as_u8()? {
+        0 => AnimState::Idle,
+        1 => AnimState::Walk,
+        2 => AnimState::Jump,
+        _ => AnimState::Fall,
+      },
+      facing: if json[3].as_i8()? < 0 { Facing::Left } else { Facing::Right },
+    })
+  }
+
+  fn as_json(&self) -> JsonValue {
+    let state = match self.state {
+      AnimState::Idle => 0,
+      AnimState::Walk => 1,
+      AnimState::Jump => 2,
+      AnimState::Fall => 3,
+    };
+    array![self.point.x.round(), self.point.y.round(), state, self.facing.num()]
+  }
+}
+
+struct GhostTrack {
+  person: GhostPerson,
+  size:   Size,
+  frames: Vec<GhostFrame>,
+}
+
+impl GhostTrack {
+  fn new(person: GhostPerson, size: &Size) -> Self {
+    Self {
+      person,
+      size:   size.clone(),
+      frames: Vec::new(),
+    }
+  }
+
+  fn from_json(json: &JsonValue) -> Option<Self> {
+    let person = match json["person"].as_str()? {
+      PLAYER => GhostPerson::Player,
+      short  => GhostPerson::Child(ChildType::from_short(short)?),
+    };
+    Some(Self {
+      person,
+      size:   Size::new(json["size"][0].as_f32()?, json["size"][1].as_f32()?),
+      frames: json["frames"].members().filter_map(GhostFrame::from_json).collect(),
+    })
+  }
+
+  fn as_json(&self) -> JsonValue {
+    let person = match self.person {
+      GhostPerson::Player            => PLAYER.to_string(),
+      GhostPerson::Child(child_type) => child_type.short(),
+    };
+    object!{
+      "person" => person,
+      "size"   => array![self.size.w, self.size.h],
+      "frames" => JsonValue::Array(self.frames.iter().map( |frame| frame.as_json() ).collect()),
+    }
+  }
+
+  // A finished track stays where its run ended
+  fn frame(&self, index: usize) -> Option<&GhostFrame> {
+    self.frames.get(index).or(self.frames.last())
+  }
+}
+
+// The persons of the current attempt at a level, recorded once per update.
+// The player's track comes first, then one for every child, in the level's order.
+pub struct GhostRecording {
+  tracks: Vec<GhostTrack>,
+}
+
+impl GhostRecording {
+  pub fn new(player: &Player, children: &Vec<Child>) -> Self {
+    let mut tracks = vec![GhostTrack::new(GhostPerson::Player, player.size())];
+    tracks.extend(children.iter().map( |child| GhostTrack::new(GhostPerson::Child(child.child_type), child.size()) ));
+    Self { tracks }
+  }
+
+  pub fn record(&mut self, player: &Player, children: &Vec<Child>) {
+    let frames = Some(GhostFrame {
+      point:  player.point().clone(),
+      state:  player.anim_state(),
+      facing: player.facing().clone(),
+    }).into_iter().chain(children.iter().map( |child| GhostFrame {
+      point:  child.point().clone(),
+      state:  child.anim_state(),
+      facing: child.facing().clone(),
+    }));
+    for (track, frame) in self.tracks.iter_mut().zip(frames) {
+      track.frames.push(frame);
+    }
+  }
+
+  pub fn save(&self, filepath: &Path) -> GameResult<()> {
+    json_file::write(filepath, &object!{
+      "tracks" => JsonValue::Array(self.tracks.iter().map( |track| track.as_json() ).collect()),
+    })
+  }
+}
+
+// A recorded run, played back as translucent persons next to the live ones.
+pub struct Ghost {
+  tracks:     Vec<GhostTrack>,
+  animations: Vec<PersonAnimations>,
+  frame:      usize,
+}
+
+impl Ghost {
+  pub fn load(ctx: &mut Context, filepath: &Path) -> GameResult<Self> {
+    let data = json_file::read(filepath)?;
+    let tracks = data["tracks"].members()
+      .filter_map(GhostTrack::from_json)
+      .collect::<Vec<GhostTrack>>();
+    if tracks.is_empty() {
+      return Err(GameError::from(format!("Ghost {} has no tracks", filepath.display())));
+    }
+    let animations = tracks.iter().map( |track| match track.person {
+      GhostPerson::Player            => PersonAnimations::new_player_animations(ctx),
+      GhostPerson::Child(child_type) => PersonAnimations::new_child_animations(ctx, &child_type),
+    }).collect();
+    Ok(Self {
+      tracks,
+      animations,
+      frame: 0,
+    })
+  }
+
+  // Follows the level's timer, so the ghost starts over whenever the level is reset
+  pub fn update(&mut self, ticks: TickType) -> GameResult<()> {
+    self.frame = (ticks as usize).max(1) - 1;
+    for (track, animations) in self.tracks.iter().zip(self.animations.iter_mut()) {
+      if let Some(frame) = track.frame(self.frame) {
+        animations.get_by_state_mut(&frame.state).update()?;
+      }
+    }
+    Ok(())
+  }
+
+  pub fn draw_offset(&self, ctx: &mut Context, offset: &Point) -> GameResult<()> {
+    let prev_color = graphics::get_color(ctx);
+    graphics::set_color(ctx, COLOR.into())?;
+    for (track, animations) in self.tracks.iter().zip(self.animations.iter()) {
+      if let Some(frame) = track.frame(self.frame) {
+        animations.get_by_state(&frame.state).draw_offset(ctx, &frame.point, &track.size, &frame.facing, offset)?;
+      }
+    }
+    graphics::set_color(ctx, prev_color)?;
+    Ok(())
+  }
+}
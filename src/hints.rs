@@ -0,0 +1,221 @@
+use std::time::Instant;
+
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ self, Font, Text, DrawMode },
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use settings::res;
+use settings::hints::*;
+use sfx::SoundEvent;
+use text_box::prelude::*;
+use layout::Layout;
+
+// What a hint's arrow points at
+#[derive(Clone, Debug)]
+pub enum HintTarget {
+  World(Point),  // A point in the level
+  Toolbox,       // The toolbox's child buttons
+  NextLevel,     // The toolbox's next level button
+}
+
+// What makes a hint go away. Everything a player does in a level makes a sound,
+// so the hints listen for those; any hint can also be clicked away.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dismiss {
+  Event(SoundEvent),
+  Click,
+}
+
+struct Hint {
+  id:      String,
+  text:    String,
+  arrow:   Option<HintTarget>,
+  dismiss: Dismiss,
+}
+
+// The hint being shown
+struct Shown {
+  index:    usize,
+  text_box: TextBox,
+  shown_at: Instant,
+}
+
+// A level's tutorial hints, from its optional `hints` array, e.g.
+// `[{ "id": "command", "text": "Click an arrow to send a child walking.", "arrow": { "ui": "toolbox" }, "dismiss": "child_commanded" }]`.
+// They are shown one at a time, in order; hints the player has seen before are skipped.
+pub struct Hints {
+  hints:       Vec<Hint>,
+  seen:        Vec<String>,
+  new_seen:    Vec<String>,
+  shown:       Option<Shown>,
+  font:        Font,
+  window_size: Size,
+}
+
+impl Hints {
+  // `level_name` makes up ids for hints without one
+  pub fn new(ctx: &mut Context, window_size: &Size, data: &JsonValue, level_name: &str) -> GameResult<Self> {
+    let hints = data.members().enumerate().filter_map( |(i, hint)| Some(Hint {
+      id:      hint["id"].as_str()
+        .map( |id| id.to_string() )
+        .unwrap_or(format!("{}/{}", level_name, i)),
+      text:    hint["text"].as_str()?.to_string(),
+      arrow:   parse_target(&hint["arrow"])?,
+      dismiss: parse_dismiss(&hint["dismiss"])?,
+    })).collect();
+    Ok(Self {
+      hints,
+      seen:        Vec::new(),
+      new_seen:    Vec::new(),
+      shown:       None,
+      font:        Font::new(ctx, res::fonts::DEFAULT, FONT_SIZE)?,
+      window_size: window_size.clone(),
+    })
+  }
+
+  // Checks everything `new` expects, for `validate_level_json`
+  pub fn is_valid(data: &JsonValue) -> bool {
+    data.is_null() || (data.is_array() && data.members().all( |hint|
+      hint["text"].is_string()
+        && (hint["id"].is_null() || hint["id"].is_string())
+        && parse_target(&hint["arrow"]).is_some()
+        && parse_dismiss(&hint["dismiss"]).is_some()
+    ))
+  }
+
+  pub fn skip_seen(&mut self, seen: &Vec<String>) {
+    self.seen = seen.clone();
+    let is_seen = self.shown.as_ref()
+      .map( |shown| self.seen.contains(&self.hints[shown.index].id) )
+      .unwrap_or(false);
+    if is_seen {
+      self.shown = None;
+    }
+  }
+
+  // The ids of the hints dismissed since the last call, to be saved by the LevelManager
+  pub fn drain_seen(&mut self) -> Vec<String> {
+    self.new_seen.drain(..).collect()
+  }
+
+  pub fn mouse_down(&mut self, x: i32, y: i32) {
+    let clicked = self.shown.as_ref()
+      .map( |shown| shown.text_box.intersects_point(&Point::new(x as NumType, y as NumType)) )
+      .unwrap_or(false);
+    if clicked {
+      self.dismiss();
+    }
+  }
+
+  // `sounds` are the level's sound events of this update
+  pub fn update(&mut self, ctx: &mut Context, sounds: &Vec<SoundEvent>) -> GameResult<()> {
+    let dismissed = self.shown.as_ref().map( |shown| match self.hints[shown.index].dismiss {
+      Dismiss::Event(event) => sounds.contains(&event),
+      Dismiss::Click        => false,
+    }).unwrap_or(false);
+    if dismissed {
+      self.dismiss();
+    }
+    if self.shown.is_none() {
+      self.show_next(ctx)?;
+    }
+    Ok(())
+  }
+
+  fn dismiss(&mut self) {
+    if let Some(shown) = self.shown.take() {
+      let id = self.hints[shown.index].id.clone();
+      self.seen.push(id.clone());
+      self.new_seen.push(id);
+    }
+  }
+
+  fn show_next(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let index = if let Some(index) = self.hints.iter().position( |hint| !self.seen.contains(&hint.id) ) {
+      index
+    } else { return Ok(()); };
+    let text = Text::new(ctx, &self.hints[index].text, &self.font)?;
+    let size = Size::new(text.width() as NumType + PADDING * 2.0, text.height() as NumType + PADDING * 2.0);
+    self.shown = Some(Shown {
+      index,
+      text_box: TextBoxBuilder::new()
+        .point(Layout::new(&self.window_size).at(Origin::TopCenter, 0.0, OFFSET_Y + size.h * 0.5))
+        .size(size)
+        .origin(Origin::Center)
+        .bg_color(BG_COLOR)
+        .text(text)
+        .text_color(FONT_COLOR)
+        .text_origin(TextOrigin::Center)
+        .build(),
+      shown_at: Instant::now(),
+    });
+    Ok(())
+  }
+
+  // The current hint's arrow target, for the Level to find on the screen
+  pub fn arrow(&self) -> Option<&HintTarget> {
+    self.shown.as_ref().and_then( |shown| self.hints[shown.index].arrow.as_ref() )
+  }
+
+  // `arrow_point` is where on the screen the arrow points to, if the hint has one
+  pub fn draw(&self, ctx: &mut Context, arrow_point: Option<Point>) -> GameResult<()> {
+    let shown = if let Some(shown) = &self.shown {
+      shown
+    } else { return Ok(()); };
+    shown.text_box.draw(ctx)?;
+    if let Some(point) = arrow_point {
+      let elapsed = shown.shown_at.elapsed();
+      let secs = elapsed.as_secs() as f32 + elapsed.subsec_millis() as f32 / 1000.0;
+      let tip  = Point::new(point.x, point.y - ARROW_BOB * (1.0 + (secs * ARROW_BOB_SPEED).sin()));
+      let prev_color = graphics::get_color(ctx);
+      graphics::set_color(ctx, ARROW_COLOR.into())?;
+      graphics::polygon(ctx, DrawMode::Fill, &[
+        graphics::Point2::new(tip.x, tip.y),
+        graphics::Point2::new(tip.x - ARROW_SIZE * 0.5, tip.y - ARROW_SIZE),
+        graphics::Point2::new(tip.x + ARROW_SIZE * 0.5, tip.y - ARROW_SIZE),
+      ])?;
+      graphics::set_color(ctx, prev_color)?;
+    }
+    Ok(())
+  }
+}
+
+// `Some(None)` for no arrow, `None` if the arrow is invalid
+fn parse_target(data: &JsonValue) -> Option<Option<HintTarget>> {
+  if data.is_null() {
+    return Some(None);
+  }
+  if let Some(ui) = data["ui"].as_str() {
+    return match ui {
+      "toolbox"    => Some(Some(HintTarget::Toolbox)),
+      "next_level" => Some(Some(HintTarget::NextLevel)),
+      _            => None,
+    };
+  }
+  match (data["x"].as_f32(), data["y"].as_f32()) {
+    (Some(x), Some(y)) => Some(Some(HintTarget::World(Point::new(x, y)))),
+    _                  => None,
+  }
+}
+
+fn parse_dismiss(data: &JsonValue) -> Option<Dismiss> {
+  if data.is_null() {
+    return Some(Dismiss::Click);
+  }
+  Some(match data.as_str()? {
+    "click"           => Dismiss::Click,
+    "child_commanded" => Dismiss::Event(SoundEvent::ChildCommanded),
+    "child_stopped"   => Dismiss::Event(SoundEvent::ChildBump),
+    "switch_pressed"  => Dismiss::Event(SoundEvent::Switch),
+    "door_opened"     => Dismiss::Event(SoundEvent::DoorOpen),
+    "jump_pad"        => Dismiss::Event(SoundEvent::JumpPad),
+    "jumped"          => Dismiss::Event(SoundEvent::PlayerJump),
+    "goal_entered"    => Dismiss::Event(SoundEvent::GoalEntered),
+    _                 => return None,
+  })
+}
@@ -0,0 +1,90 @@
+use ggez::{
+  Context,
+  GameResult
+};
+
+use noframe::geo::prelude::*;
+use noframe::entity::prelude::*;
+
+use super::Interactable;
+use persons::Person;
+use id_generator::prelude::*;
+
+// An invisible zone that starts a dialogue script when the player walks into it.
+// Unless `once` is false, the script only plays the first time;
+// the Level remembers which triggers played, so that survives a reset.
+pub struct DialogueTrigger {
+  point:       Point,
+  size:        Size,
+  origin:      Origin,
+  intersected: Vec<IdType>,
+  id:          IdType,
+  script:      String,
+  once:        bool,
+}
+
+impl DialogueTrigger {
+  pub fn new(_ctx: &mut Context, point: Point, size: Size, script: &str, once: bool) -> Self {
+    Self {
+      point,
+      size,
+      origin:      Origin::TopLeft,
+      intersected: Vec::new(),
+      id:          generate_id(),
+      script:      script.to_string(),
+      once,
+    }
+  }
+
+  // The script to play, after the trigger was triggered
+  pub fn script(&self, played: bool) -> Option<String> {
+    if self.once && played { return None; }
+    Some(self.script.clone())
+  }
+}
+
+impl Mask for DialogueTrigger {
+  fn point(&self)         -> &Point { &self.point }
+  fn point_mut(&mut self) -> &mut Point { &mut self.point }
+  fn size(&self)          -> &Size { &self.size }
+  fn origin(&self)        -> &Origin { &self.origin }
+}
+
+impl Entity for DialogueTrigger {
+  fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+    Ok(())
+  }
+
+  fn draw(&self, _ctx: &mut Context) -> GameResult<()> {
+    Ok(())
+  }
+
+  fn draw_offset(&self, _ctx: &mut Context, _offset: &Point) -> GameResult<()> {
+    Ok(())
+  }
+}
+
+impl IdGenerator for DialogueTrigger {
+  fn id(&self) -> IdType {
+    self.id
+  }
+  fn set_id(&mut self, id: IdType) {
+    self.id = id;
+  }
+}
+
+impl Interactable for DialogueTrigger {
+  fn get_intersected(&self) -> &Vec<IdType> {
+    &self.intersected
+  }
+  fn add_intersected(&mut self, id: IdType) {
+    self.intersected.push(id);
+  }
+  fn rm_intersected_at(&mut self, index: usize) {
+    self.intersected.remove(index);
+  }
+
+  // The Level takes the script, the person isn't affected
+  fn trigger<T: Person>(&mut self, _person: &mut T) {
+  }
+}
@@ -6,6 +6,7 @@ pub mod door;
 pub mod one_way;
 pub mod solidifier;
 pub mod goal;
+pub mod dialogue_trigger;
 
 pub mod prelude {
   pub use super::Interactable;
@@ -15,6 +16,7 @@ pub mod prelude {
   pub use super::one_way::{ OneWay, self };
   pub use super::solidifier::{ Solidifier, self };
   pub use super::goal::{ Goal, self };
+  pub use super::dialogue_trigger::DialogueTrigger;
 }
 
 use noframe::entity::prelude::*;
@@ -45,9 +47,11 @@ pub trait Interactable: Entity {
       .any( |&p| person.has_id(p) )
   }
 
-  fn trigger_once<T: Person>(&mut self, person: &mut T) {
-    if self.is_intersected(person) { return; }
+  // Returns true if the person wasn't intersecting yet and the interactable was triggered
+  fn trigger_once<T: Person>(&mut self, person: &mut T) -> bool {
+    if self.is_intersected(person) { return false; }
     self.set_intersected(person, true);
     self.trigger(person);
+    true
   }
 }
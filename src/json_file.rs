@@ -0,0 +1,41 @@
+use std::fs::{ self, File };
+use std::io::prelude::*;
+use std::path::Path;
+
+use ggez::{
+  GameResult,
+  GameError,
+};
+use json::{ self, JsonValue };
+
+use settings::save::TEMP_EXTENSION;
+
+pub fn read(filepath: &Path) -> GameResult<JsonValue> {
+  let mut file = File::open(filepath)?;
+  let mut json_raw = String::new();
+  file.read_to_string(&mut json_raw)?;
+  match json::parse(&json_raw) {
+    Ok(data) => Ok(data),
+    Err(e)   => Err(GameError::from(e.to_string()))
+  }
+}
+
+// The data is written to a temporary file first, which then replaces the file,
+// so a crash while writing can't leave a half-written file behind.
+pub fn write(filepath: &Path, data: &JsonValue) -> GameResult<()> {
+  write_str(filepath, &data.dump())
+}
+
+pub fn write_str(filepath: &Path, contents: &str) -> GameResult<()> {
+  if let Some(dir) = filepath.parent() {
+    fs::create_dir_all(dir)?;
+  }
+  let temp_path = filepath.with_extension(TEMP_EXTENSION);
+  {
+    let mut file = File::create(&temp_path)?;
+    write!(file, "{}", contents)?;
+    file.sync_all()?;
+  }
+  fs::rename(&temp_path, filepath)?;
+  Ok(())
+}
@@ -0,0 +1,50 @@
+use noframe::geo::prelude::*;
+
+// An area of the virtual screen that UI elements are placed in.
+// Elements are placed relative to one of the area's corners, sides or its center,
+// so they keep their place when the area's size changes.
+pub struct Layout {
+  area: Rect,
+}
+
+impl Layout {
+  pub fn new(size: &Size) -> Self {
+    Self::from_rect(Point::new(0.0, 0.0), size)
+  }
+
+  pub fn from_rect(top_left: Point, size: &Size) -> Self {
+    Self {
+      area: Rect::new(top_left, size.clone(), Origin::TopLeft),
+    }
+  }
+
+  pub fn size(&self) -> &Size {
+    self.area.size()
+  }
+
+  // The area, shrunk by `x` on the left and right and by `y` on the top and bottom
+  pub fn inset(&self, x: NumType, y: NumType) -> Rect {
+    let size = self.size();
+    Rect::new(
+      self.at(Origin::TopLeft, x, y),
+      Size::new(size.w - x * 2.0, size.h - y * 2.0),
+      Origin::TopLeft
+    )
+  }
+
+  // The point at `anchor`, moved by `x` and `y`
+  pub fn at(&self, anchor: Origin, x: NumType, y: NumType) -> Point {
+    let point = match anchor {
+      Origin::TopLeft      => self.area.top_left(),
+      Origin::TopRight     => self.area.top_right(),
+      Origin::TopCenter    => self.area.top_center(),
+      Origin::BottomLeft   => self.area.bottom_left(),
+      Origin::BottomRight  => self.area.bottom_right(),
+      Origin::BottomCenter => self.area.bottom_center(),
+      Origin::CenterLeft   => self.area.center_left(),
+      Origin::CenterRight  => self.area.center_right(),
+      Origin::Center       => self.area.center(),
+    };
+    point + Point::new(x, y)
+  }
+}
@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::prelude::*;
+use std::path::Path;
 
 use ggez::{
   GameResult,
@@ -18,7 +17,14 @@ use interactables::prelude::*;
 use persons::player::Player;
 use persons::children::{ Child, ChildType };
 use wall::{ Wall, Walls };
+use decoration::Decoration;
+use particles::Particles;
+use popups::Popups;
+use hints::Hints;
+use ghost::GhostRecording;
 use menu::toolbox::ToolboxMenu;
+use layout::Layout;
+use json_file;
 use score::prelude::*;
 
 pub struct InteractablesContainer {
@@ -27,7 +33,8 @@ pub struct InteractablesContainer {
   pub doors:       Vec<Door>,
   pub one_ways:    Vec<OneWay>,
   pub solidifiers: Vec<Solidifier>,
-  pub goal:        Option<Goal>
+  pub goal:        Option<Goal>,
+  pub dialogues:   Vec<DialogueTrigger>,
 }
 
 impl InteractablesContainer {
@@ -38,7 +45,8 @@ impl InteractablesContainer {
       doors:       Vec::new(),
       one_ways:    Vec::new(),
       solidifiers: Vec::new(),
-      goal:        None
+      goal:        None,
+      dialogues:   Vec::new(),
     }
   }
 
@@ -47,29 +55,35 @@ impl InteractablesContainer {
   }
 }
 
-pub fn new_level(ctx: &mut Context, window_size: Size, filename: &str, level_index: usize) -> GameResult<Level> {
+pub fn new_level(ctx: &mut Context, window_size: Size, filepath: &Path, level_index: usize) -> GameResult<Level> {
+  let filename = filepath.file_stem().and_then( |stem| stem.to_str() ).unwrap_or("");
+  let data = json_file::read(filepath)?;
+  new_level_from_json(ctx, window_size, filename, data, level_index)
+}
+
+pub fn new_level_from_json(ctx: &mut Context, window_size: Size, filename: &str, data: json::JsonValue, level_index: usize) -> GameResult<Level> {
+  validate_level_json(&data)?;
   let font = graphics::Font::new(ctx, res::fonts::DEFAULT, NAME_FONT_SIZE)?;
   let level_name = ::semantic(filename);
   let level_name_text = graphics::Text::new(ctx, &::semantic(filename), &font)?;
   let score_text = graphics::Text::new(ctx, "Score: 0", &font)?;
+  // Overrides the song from the level pack
+  let song = data["song"].as_str().map( |song| song.to_string() );
+  // The level's start dialogue is played once it is loaded
+  let dialogue = data["dialogue"]["start"].as_str()
+    .map( |script| vec![script.to_string()] )
+    .unwrap_or(Vec::new());
+  let hints = Hints::new(ctx, &window_size, &data["hints"], &level_name)?;
 
-  let filename = &::join_str(filename, ".json");
-  let level_filepath = &::join_str(res::LEVELS, filename);
-  let mut level_file = File::open(level_filepath)?;
-  let mut json_raw = String::new();
-  level_file.read_to_string(&mut json_raw)?;
-  let data = match json::parse(&json_raw) {
-    Ok(d)  => d,
-    Err(e) => return Err(ggez::GameError::from(e.to_string()))
-  };
-
-  let (player, children, walls, interactables) = load_json(ctx, &data, level_index)?;
+  let (player, children, walls, interactables, decorations) = load_json(ctx, &data, level_index)?;
+  // The toolbox sits along the bottom edge of the screen
   let toolbox = ToolboxMenu::new(
     ctx,
-    Point::new(0.0, window_size.h - 96.0),
+    Layout::new(&window_size).at(Origin::BottomLeft, 0.0, -96.0),
     Size::new(window_size.w, 64.0),
     children.iter().map( |c| c.child_type.clone() ).collect()
   );
+  let recording = GhostRecording::new(&player, &children);
 
   let mut lvl = Level {
     json_data:   data,
@@ -78,9 +92,17 @@ pub fn new_level(ctx: &mut Context, window_size: Size, filename: &str, level_ind
     camera:      Camera::new(window_size.clone()),
     camera_rect: Rect::new(Point::new(0.0, 0.0), window_size.clone(), Origin::TopLeft),
     player,
+    selected_child: first_child_type(&children),
     children,
     walls,
     interactables,
+    decorations,
+    particles:   Particles::new(ctx)?,
+    popups:      Popups::new(ctx)?,
+    hints,
+    recording,
+    ghost:       None,
+    player_walking: false,
     toolbox,
     next_level:  false,
     font,
@@ -89,6 +111,11 @@ pub fn new_level(ctx: &mut Context, window_size: Size, filename: &str, level_ind
     score:       Score::new(),
     prev_score:  0,
     score_text,
+    song,
+    sounds:      Vec::new(),
+    stats_events: Vec::new(),
+    dialogue,
+    played_triggers: Vec::new(),
     dt:          Deltatime::new()
   };
 
@@ -98,11 +125,131 @@ pub fn new_level(ctx: &mut Context, window_size: Size, filename: &str, level_ind
   Ok(lvl)
 }
 
-pub fn load_json(ctx: &mut Context, data: &json::JsonValue, level_index: usize) -> GameResult<(Player, Vec<Child>, Walls, InteractablesContainer)> {
+// Checks everything `load_instances` expects, so a malformed level returns an error instead of panicking.
+pub fn validate_level_json(data: &json::JsonValue) -> GameResult<()> {
+  validate_level_contents(data)?;
+  if !data["instances"].members().any( |instance| instance["type"] == "Player" ) {
+    return Err(ggez::GameError::from("Level has no Player".to_string()));
+  }
+  Ok(())
+}
+
+// Like `validate_level_json`, but a level without a Player is fine, as it is in the Editor.
+pub fn validate_level_contents(data: &json::JsonValue) -> GameResult<()> {
+  let err = |i: usize, msg: &str| Err(ggez::GameError::from(format!("Instance {}: {}", i, msg)));
+  if !data["instances"].is_array() {
+    return Err(ggez::GameError::from("Level JSON data must have an `instances` array".to_string()));
+  }
+  for (i, instance) in data["instances"].members().enumerate() {
+    let instance_type = if let Some(instance_type) = instance["type"].as_str() {
+      instance_type
+    } else { return err(i, "missing `type`"); };
+
+    // `load_instances` parses these for every instance that has them, whatever its type
+    if instance.has_key("position") && (instance["position"]["x"].as_f32().is_none() || instance["position"]["y"].as_f32().is_none()) {
+      return err(i, &format!("{} has an invalid `position`", instance_type));
+    }
+    if instance.has_key("size") && (instance["size"]["w"].as_f32().is_none() || instance["size"]["h"].as_f32().is_none()) {
+      return err(i, &format!("{} has an invalid `size`", instance_type));
+    }
+    let additional = &instance["additional"];
+    if additional.has_key("state") && additional["state"].as_str().is_none() {
+      return err(i, &format!("{} has an invalid `state`", instance_type));
+    }
+    if additional.has_key("id") && additional["id"].as_u32().is_none() {
+      return err(i, &format!("{} has an invalid `id`", instance_type));
+    }
+    if additional.has_key("color") && additional["color"].as_str().is_none() {
+      return err(i, &format!("{} has an invalid `color`", instance_type));
+    }
+    if additional.has_key("triggers") && additional["triggers"].members().any( |id| id.as_u32().is_none() ) {
+      return err(i, &format!("{} has invalid `triggers`", instance_type));
+    }
+    if additional.has_key("strength") && additional["strength"].as_f32().is_none() {
+      return err(i, &format!("{} has an invalid `strength`", instance_type));
+    }
+
+    let needs_additional = match instance_type {
+      "Player" | "Child" | "LarryChild" | "ThingChild" | "BloatChild" | "Wall" | "Decoration"
+        | "OneWayInteractable" | "SolidifierInteractable" | "GoalInteractable" | "DialogueTrigger" => false,
+      "JumpPadInteractable" | "SwitchInteractable" | "DoorInteractable" => true,
+      _ => continue,
+    };
+    if instance["position"]["x"].as_f32().is_none() || instance["position"]["y"].as_f32().is_none() {
+      return err(i, &format!("{} has an invalid `position`", instance_type));
+    }
+    if instance["size"]["w"].as_f32().is_none() || instance["size"]["h"].as_f32().is_none() {
+      return err(i, &format!("{} has an invalid `size`", instance_type));
+    }
+    if instance_type == "Decoration" && !is_image_list(&additional["images"]) {
+      return err(i, "Decoration has invalid `images`");
+    }
+    if instance_type == "DialogueTrigger" && (additional["script"].as_str().is_none()
+      || (additional.has_key("once") && additional["once"].as_bool().is_none())) {
+      return err(i, "DialogueTrigger has an invalid `script` or `once`");
+    }
+    if !needs_additional { continue; }
+    if additional["id"].as_u32().is_none() {
+      return err(i, &format!("{} has an invalid `id`", instance_type));
+    }
+    if additional["color"].as_str().is_none() {
+      return err(i, &format!("{} has an invalid `color`", instance_type));
+    }
+    let states: &[&str] = match instance_type {
+      "JumpPadInteractable" => &["Active", "Inactive", "Trigger"],
+      "DoorInteractable"    => &["Open", "Closed", "Opening", "Closing"],
+      _                     => &[],
+    };
+    if !states.is_empty() && !additional["state"].as_str().map( |state| states.contains(&state) ).unwrap_or(false) {
+      return err(i, &format!("{} has an invalid `state`", instance_type));
+    }
+    if instance_type == "SwitchInteractable" && !additional["triggers"].is_array() {
+      return err(i, "SwitchInteractable has invalid `triggers`");
+    }
+  }
+  let background = &data["background"];
+  if !background.is_null() && !(background.is_array() && background.members().all( |layer|
+      is_image_list(&layer["images"])
+        && (layer["parallax"].is_null() || layer["parallax"].as_f32().is_some())
+        && (layer["interval"].is_null() || layer["interval"].as_u64().is_some())
+  )) {
+    return Err(ggez::GameError::from("Level has an invalid `background`".to_string()));
+  }
+  let dialogue = &data["dialogue"];
+  if !dialogue.is_null() && !(dialogue.is_object() && ["start", "clear"].iter().all( |&key|
+      dialogue[key].is_null() || dialogue[key].is_string()
+  )) {
+    return Err(ggez::GameError::from("Level has an invalid `dialogue`".to_string()));
+  }
+  if !Hints::is_valid(&data["hints"]) {
+    return Err(ggez::GameError::from("Level has invalid `hints`".to_string()));
+  }
+  Ok(())
+}
+
+fn is_image_list(images: &json::JsonValue) -> bool {
+  images.is_array() && !images.is_empty() && images.members().all( |image| image.is_string() )
+}
+
+pub fn load_json(ctx: &mut Context, data: &json::JsonValue, level_index: usize) -> GameResult<(Player, Vec<Child>, Walls, InteractablesContainer, Vec<Decoration>)> {
+  let (player_opt, children, walls, interactables, decorations) = load_instances(ctx, data, level_index);
+
+  let player = if let Some(player) = player_opt {
+    player
+  } else {
+    return Err(ggez::GameError::from("Couldn't load player".to_string()));
+  };
+
+  Ok((player, children, walls, interactables, decorations))
+}
+
+// Loads all instances without requiring a Player, used by the Editor.
+pub fn load_instances(ctx: &mut Context, data: &json::JsonValue, level_index: usize) -> (Option<Player>, Vec<Child>, Walls, InteractablesContainer, Vec<Decoration>) {
   let mut player_opt = None;
   let mut children = Vec::new();
   let mut walls = Walls::new(ctx, level_index);
   let mut interactables = InteractablesContainer::new();
+  let mut decorations = Vec::new();
 
   data["instances"].members().for_each( |data| {
     let point_opt = if data.has_key("position") {
@@ -271,15 +418,105 @@ pub fn load_json(ctx: &mut Context, data: &json::JsonValue, level_index: usize)
         ))
       }
 
+      "DialogueTrigger" => {
+        let err_msg = "Couldn't load level JSON data: DialogueTrigger";
+        interactables.dialogues.push(DialogueTrigger::new(
+            ctx,
+            point_opt.expect(err_msg),
+            size_opt.expect(err_msg),
+            data["additional"]["script"].as_str().expect(err_msg),
+            data["additional"]["once"].as_bool().unwrap_or(true)
+        ));
+      }
+
+      "Decoration" => {
+        let err_msg = "Couldn't load level JSON data: Decoration";
+        decorations.push(Decoration::new(
+            ctx,
+            point_opt.expect(err_msg),
+            size_opt.expect(err_msg),
+            &data["additional"]
+        ));
+      }
+
       _ => {}
     }
   });
 
-  let player = if let Some(player) = player_opt {
-    player
-  } else {
-    return Err(ggez::GameError::from("Couldn't load player".to_string()));
-  };
+  (player_opt, children, walls, interactables, decorations)
+}
 
-  Ok((player, children, walls, interactables))
+pub fn first_child_type(children: &Vec<Child>) -> Option<ChildType> {
+  children.iter()
+    .map( |child| child.child_type )
+    .min()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use json::JsonValue;
+
+  fn instance(instance_type: &str) -> JsonValue {
+    object!{
+      "type"     => instance_type,
+      "position" => object!{ "x" => 0, "y" => 0 },
+      "size"     => object!{ "w" => 32, "h" => 32 },
+    }
+  }
+
+  fn level_with(other: JsonValue) -> JsonValue {
+    object!{
+      "instances" => array![instance("Player"), other],
+    }
+  }
+
+  #[test]
+  fn a_level_needs_instances_and_a_player() {
+    assert!(validate_level_json(&object!{}).is_err());
+    assert!(validate_level_json(&object!{ "instances" => array![] }).is_err());
+    assert!(validate_level_contents(&object!{ "instances" => array![] }).is_ok());
+    assert!(validate_level_json(&object!{ "instances" => array![instance("Player")] }).is_ok());
+  }
+
+  #[test]
+  fn known_types_need_a_position_and_size() {
+    let mut wall = instance("Wall");
+    wall.remove("size");
+    assert!(validate_level_json(&level_with(wall)).is_err());
+  }
+
+  #[test]
+  fn unknown_types_are_only_checked_for_the_generic_fields() {
+    let unknown = object!{ "type" => "Unknown" };
+    assert!(validate_level_json(&level_with(unknown)).is_ok());
+    let mut broken = instance("Unknown");
+    broken["position"]["x"] = "left".into();
+    assert!(validate_level_json(&level_with(broken)).is_err());
+  }
+
+  #[test]
+  fn additional_fields_are_checked_for_every_type() {
+    let mut wall = instance("Wall");
+    wall["additional"] = object!{ "id" => "one" };
+    assert!(validate_level_json(&level_with(wall)).is_err());
+    let mut goal = instance("GoalInteractable");
+    goal["additional"] = object!{ "strength" => "strong" };
+    assert!(validate_level_json(&level_with(goal)).is_err());
+  }
+
+  #[test]
+  fn interactables_need_their_additional_fields() {
+    let mut jump_pad = instance("JumpPadInteractable");
+    jump_pad["additional"] = object!{ "id" => 0, "color" => "blue", "state" => "Active" };
+    assert!(validate_level_json(&level_with(jump_pad.clone())).is_ok());
+    jump_pad["additional"]["state"] = "Open".into();
+    assert!(validate_level_json(&level_with(jump_pad)).is_err());
+
+    let mut switch = instance("SwitchInteractable");
+    switch["additional"] = object!{ "id" => 1, "color" => "blue", "triggers" => array![0, 2] };
+    assert!(validate_level_json(&level_with(switch.clone())).is_ok());
+    switch["additional"]["triggers"] = array![0, "door"];
+    assert!(validate_level_json(&level_with(switch)).is_err());
+  }
 }
@@ -1,20 +1,27 @@
 mod helpers;
 
+use std::path::Path;
+
 use ggez::{
   GameResult,
   Context,
   graphics,
-  event::Keycode
 };
 use noframe::deltatime::Deltatime;
 use noframe::camera::Camera;
 use noframe::entity::Entity;
 use noframe::entity::prelude::*;
 use noframe::geo::prelude::*;
+use rand::Rng;
 
 use self::helpers::*;
+pub use self::helpers::{ InteractablesContainer, validate_level_json, validate_level_contents, load_instances };
 use settings::camera::*;
-use settings::level::*;
+use settings::child::{ SELECTED_COLOR, SELECTED_PADDING };
+use settings::score::{ PLAYER_SCORE_REWARD, CHILD_SCORE_REWARD };
+use settings::popups::{ TEXT_COLOR, PENALTY_COLOR, REWARD_COLOR };
+use config;
+use action::Action;
 use persons::Person;
 use persons::player::Player;
 use persons::children::{ Child, ChildType };
@@ -25,6 +32,15 @@ use menu::Menu;
 use menu::buttons::ButtonType;
 use menu::toolbox::ToolboxMenu;
 use score::prelude::*;
+use score::lifetime::StatsEvent;
+use color_rect::ColorRect;
+use layout::Layout;
+use sfx::SoundEvent;
+use decoration::{ Decoration, DecorationLayer };
+use particles::{ Particles, ParticleEffect };
+use popups::Popups;
+use hints::{ Hints, HintTarget };
+use ghost::{ Ghost, GhostRecording };
 
 pub struct Level {
   json_data:       json::JsonValue,
@@ -34,8 +50,16 @@ pub struct Level {
   camera_rect:     Rect,
   player:          Player,
   children:        Vec<Child>,
+  selected_child:  Option<ChildType>,
   walls:           Walls,
   interactables:   InteractablesContainer,
+  decorations:     Vec<Decoration>,
+  particles:       Particles,
+  popups:          Popups,
+  hints:           Hints,
+  recording:       GhostRecording,
+  ghost:           Option<Ghost>,  // The personal best run to race
+  player_walking:  bool,
   toolbox:         ToolboxMenu,
   pub next_level:  bool,
   font:            graphics::Font,
@@ -44,12 +68,21 @@ pub struct Level {
   score:           Score,
   prev_score:      ScoreType,
   score_text:      graphics::Text,
+  song:            Option<String>,
+  sounds:          Vec<SoundEvent>,
+  stats_events:    Vec<StatsEvent>,
+  dialogue:        Vec<String>,  // Scripts to start
+  played_triggers: Vec<usize>,   // Indices of the DialogueTriggers that played, kept on reset
   dt:              Deltatime
 }
 
 impl Level {
-  pub fn new(ctx: &mut Context, window_size: Size, filename: &str, level_index: usize) -> GameResult<Self> {
-    new_level(ctx, window_size, filename, level_index)
+  pub fn new(ctx: &mut Context, window_size: Size, filepath: &Path, level_index: usize) -> GameResult<Self> {
+    new_level(ctx, window_size, filepath, level_index)
+  }
+
+  pub fn from_json(ctx: &mut Context, window_size: Size, filename: &str, data: json::JsonValue, level_index: usize) -> GameResult<Self> {
+    new_level_from_json(ctx, window_size, filename, data, level_index)
   }
 
   pub fn reset(&mut self, ctx: &mut Context) -> GameResult<()> {
@@ -57,17 +90,36 @@ impl Level {
       player,
       children,
       walls,
-      interactables
+      interactables,
+      decorations
     ) = load_json(ctx, &self.json_data, self.level_index)?;
-    self.player        = player;
-    self.children      = children;
-    self.walls         = walls;
-    self.interactables = interactables;
-    self.next_level    = false;
+    self.player         = player;
+    self.selected_child = first_child_type(&children);
+    self.children       = children;
+    self.walls          = walls;
+    self.interactables  = interactables;
+    self.decorations    = decorations;
+    self.recording      = GhostRecording::new(&self.player, &self.children);
+    self.player_walking = false;
+    self.next_level     = false;
+    self.particles.clear();
+    self.popups.clear();
+    self.sounds.clear();
+    self.stats_events.clear();
+    self.dialogue.clear();
     self.score.clear();
     Ok(())
   }
 
+  // The level's own background layers, if it has any
+  pub fn background_data(&self) -> &json::JsonValue {
+    &self.json_data["background"]
+  }
+
+  pub fn song(&self) -> Option<&str> {
+    self.song.as_ref().map( |song| song.as_str() )
+  }
+
   pub fn camera(&self) -> &Camera {
     &self.camera
   }
@@ -76,38 +128,79 @@ impl Level {
     &mut self.camera
   }
 
-  pub fn keys_pressed(&mut self, keycodes: &Vec<Keycode>, dt: &Deltatime) {
-    for key in keycodes {
-      match key {
-        Keycode::Up    => self.camera.point_mut().add(&Point::new(0.0, -CAMERA_SPEED * dt.secs())),
-        Keycode::Down  => self.camera.point_mut().add(&Point::new(0.0,  CAMERA_SPEED * dt.secs())),
-        Keycode::Left  => self.camera.point_mut().add(&Point::new(-CAMERA_SPEED * dt.secs(), 0.0)),
-        Keycode::Right => self.camera.point_mut().add(&Point::new( CAMERA_SPEED * dt.secs(), 0.0)),
-        _              => ()
+  pub fn actions_pressed(&mut self, actions: &Vec<Action>, dt: &Deltatime) {
+    for action in actions {
+      match action {
+        Action::CameraUp    => self.camera.point_mut().add(&Point::new(0.0, -CAMERA_SPEED * dt.secs())),
+        Action::CameraDown  => self.camera.point_mut().add(&Point::new(0.0,  CAMERA_SPEED * dt.secs())),
+        Action::CameraLeft  => self.camera.point_mut().add(&Point::new(-CAMERA_SPEED * dt.secs(), 0.0)),
+        Action::CameraRight => self.camera.point_mut().add(&Point::new( CAMERA_SPEED * dt.secs(), 0.0)),
+        _                   => ()
       };
     }
-    self.player.keys_pressed(keycodes);
+    self.player.actions_pressed(actions);
+  }
+
+  pub fn actions_down(&mut self, actions: &Vec<Action>, _dt: &Deltatime) {
+    for action in actions {
+      match action {
+        Action::CenterCamera => self.center_camera(),
+        Action::PrevChild    => self.cycle_selected_child(false),
+        Action::NextChild    => self.cycle_selected_child(true),
+        Action::SelectChild1 => self.select_child(0),
+        Action::SelectChild2 => self.select_child(1),
+        Action::SelectChild3 => self.select_child(2),
+        Action::CommandLeft  => if let Some(child_type) = self.selected_child {
+          self.child_walk_left(child_type);
+        },
+        Action::CommandRight => if let Some(child_type) = self.selected_child {
+          self.child_walk_right(child_type);
+        },
+        _                    => (),
+      }
+    }
   }
 
-  pub fn keys_down(&mut self, keycodes: &Vec<Keycode>, _dt: &Deltatime) {
-    for &key in keycodes {
-      self.player.key_down(&key);
-      match key {
-        CENTER_KEY => self.center_camera(),
-        SKIP_KEY   => self.next_level(),  // TODO: Temporary! Remove for production version!
-        _          => ()
-      }
+  // Children are numbered in the same order as their closeups in the toolbox
+  fn sorted_child_types(&self) -> Vec<ChildType> {
+    let mut child_types = self.children.iter()
+      .map( |child| child.child_type )
+      .collect::<Vec<ChildType>>();
+    child_types.sort();
+    child_types
+  }
+
+  fn select_child(&mut self, index: usize) {
+    if let Some(&child_type) = self.sorted_child_types().get(index) {
+      self.selected_child = Some(child_type);
     }
   }
 
-  pub fn keys_up(&mut self, keycodes: &Vec<Keycode>, _dt: &Deltatime) {
-    for key in keycodes {
-      self.player.key_up(key);
+  fn cycle_selected_child(&mut self, forward: bool) {
+    let child_types = self.sorted_child_types();
+    if child_types.is_empty() { return; }
+    let len = child_types.len();
+    let index = self.selected_child
+      .and_then( |selected| child_types.iter().position( |&child_type| child_type == selected ))
+      .map( |i| if forward { (i + 1) % len } else { (i + len - 1) % len })
+      .unwrap_or(0);
+    self.selected_child = Some(child_types[index]);
+  }
+
+  pub fn actions_up(&mut self, actions: &Vec<Action>, _dt: &Deltatime) {
+    for action in actions {
+      self.player.action_up(action);
     }
   }
 
+  // TODO: Temporary! Remove for production version!
+  pub fn skip(&mut self) {
+    self.next_level();
+  }
+
   pub fn mouse_down(&mut self, x: i32, y: i32) {
     self.toolbox.mouse_down(x, y);
+    self.hints.mouse_down(x, y);
   }
 
   fn center_camera(&mut self) {
@@ -115,9 +208,57 @@ impl Level {
     self.camera.move_to(&p);
   }
 
+  fn follow_player(&mut self) {
+    let p = self.player.center();
+    self.camera.move_to(&p);
+  }
+
   fn next_level(&mut self) {
     self.add_score();
     self.next_level = true;
+    self.sounds.push(SoundEvent::LevelCleared);
+    if let Some(script) = self.json_data["dialogue"]["clear"].as_str() {
+      self.dialogue.push(script.to_string());
+    }
+  }
+
+  // The sound events since the last call, to be played by the LevelManager
+  pub fn drain_sounds(&mut self) -> Vec<SoundEvent> {
+    self.sounds.drain(..).collect()
+  }
+
+  // The events for the lifetime stats since the last call, recorded by the LevelManager
+  pub fn drain_stats_events(&mut self) -> Vec<StatsEvent> {
+    self.stats_events.drain(..).collect()
+  }
+
+  // Hints the player has seen, in this or any other level, aren't shown again
+  pub fn skip_seen_hints(&mut self, seen: &Vec<String>) {
+    self.hints.skip_seen(seen);
+  }
+
+  // The ids of the hints dismissed since the last call, to be saved by the LevelManager
+  pub fn drain_seen_hints(&mut self) -> Vec<String> {
+    self.hints.drain_seen()
+  }
+
+  // The dialogue scripts to play since the last call, started by the LevelManager
+  pub fn drain_dialogue(&mut self) -> Vec<String> {
+    self.dialogue.drain(..).collect()
+  }
+
+  // The hot reloaded level doesn't replay what the old one already played
+  pub fn keep_played_triggers(&mut self, level: &Level) {
+    self.played_triggers = level.played_triggers.clone();
+  }
+
+  pub fn set_ghost(&mut self, ghost: Option<Ghost>) {
+    self.ghost = ghost;
+  }
+
+  // The current attempt, saved as the level's ghost by the LevelManager when it is the best one
+  pub fn recording(&self) -> &GhostRecording {
+    &self.recording
   }
 
   pub fn score(&self) -> &Score {
@@ -139,10 +280,12 @@ impl Level {
   fn add_score(&mut self) {
     if self.is_player_in_goal() {
       self.score.saved_player();
+      self.stats_events.push(StatsEvent::SavedPlayer);
     }
     let children_types: Vec<ChildType> = self.children_in_goal().iter().map( |child| child.child_type ).collect();
     for child_type in children_types {
       self.score.saved_child(child_type);
+      self.stats_events.push(StatsEvent::SavedChild(child_type));
     }
   }
 
@@ -164,11 +307,35 @@ impl Level {
     }
   }
 
-  pub fn update(&mut self, ctx: &mut Context, _dt: &Deltatime) -> GameResult<()> {
+  pub fn update(&mut self, ctx: &mut Context, dt: &Deltatime) -> GameResult<()> {
+    self.score.tick();
     self.update_interactables(ctx)?;
     self.update_children(ctx)?;
     self.update_player(ctx)?;
+    self.recording.record(&self.player, &self.children);
+    if let Some(ghost) = &mut self.ghost {
+      ghost.update(self.score.ticks())?;
+    }
     self.update_toolbox()?;
+    for decoration in &mut self.decorations {
+      decoration.update(ctx)?;
+    }
+    self.particles.update(dt.secs());
+    {
+      let player   = &self.player;
+      let children = &self.children;
+      self.popups.update(ctx, dt.secs(), |id| {
+        if player.has_id(id) {
+          Some(player.top_center())
+        } else {
+          children.iter().find( |child| child.has_id(id) ).map( |child| child.top_center() )
+        }
+      })?;
+    }
+    if config::with( |c| c.camera_follow ) {
+      self.follow_player();
+    }
+    self.hints.update(ctx, &self.sounds)?;
     //self.dt.update();
     Ok(())
   }
@@ -179,13 +346,17 @@ impl Level {
     for i in 0 .. self.interactables.switches.len() {
       { let mut switch = &mut self.interactables.switches[i];
         if switch.intersects_round(&self.player) {
-          switch.trigger_once(&mut self.player);
+          if switch.trigger_once(&mut self.player) {
+            self.sounds.push(SoundEvent::Switch);
+          }
         } else {
           switch.set_intersected(&self.player, false);
         }
         for child in &mut self.children {
           if switch.intersects_round(child) {
-            switch.trigger_once(child);
+            if switch.trigger_once(child) {
+              self.sounds.push(SoundEvent::Switch);
+            }
           } else {
             switch.set_intersected(&*child, false);
           }
@@ -201,13 +372,19 @@ impl Level {
         jump_pad.toggle_state();
       }
       if jump_pad.intersects_center(&self.player) {
-        jump_pad.trigger_once(&mut self.player);
+        if jump_pad.trigger_once(&mut self.player) {
+          self.sounds.push(SoundEvent::JumpPad);
+          self.particles.emit(ParticleEffect::Burst, &jump_pad.top_center());
+        }
       } else {
         jump_pad.set_intersected(&self.player, false);
       }
       for child in &mut self.children {
         if jump_pad.intersects_center(child) {
-          jump_pad.trigger_once(child);
+          if jump_pad.trigger_once(child) {
+            self.sounds.push(SoundEvent::JumpPad);
+            self.particles.emit(ParticleEffect::Burst, &jump_pad.top_center());
+          }
         } else {
           jump_pad.set_intersected(&*child, false);
         }
@@ -218,6 +395,12 @@ impl Level {
     for door in &mut self.interactables.doors {
       if ids_to_trigger.contains(&door.id()) {
         door.trigger(&mut self.player);  // We don't use the player, but something needs to be passed...
+        if door.is_solid() {
+          self.sounds.push(SoundEvent::DoorClose);
+        } else {
+          self.sounds.push(SoundEvent::DoorOpen);
+          self.particles.emit(ParticleEffect::Puff, &door.center());
+        }
       }
       door.update(ctx)?;
     }
@@ -238,16 +421,40 @@ impl Level {
       self.player.unsolidify();
     }
 
+    for (index, trigger) in self.interactables.dialogues.iter_mut().enumerate() {
+      if trigger.intersects_round(&self.player) {
+        if trigger.trigger_once(&mut self.player) {
+          let played = self.played_triggers.contains(&index);
+          if let Some(script) = trigger.script(played) {
+            if !played {
+              self.played_triggers.push(index);
+            }
+            self.dialogue.push(script);
+          }
+        }
+      } else {
+        trigger.set_intersected(&self.player, false);
+      }
+    }
+
     // Check if persons intersect with goal
     if let Some(goal) = &mut self.interactables.goal {
       if goal.intersects_round(&self.player) {
-        goal.trigger_once(&mut self.player);
+        if goal.trigger_once(&mut self.player) {
+          self.sounds.push(SoundEvent::GoalEntered);
+          self.particles.emit(ParticleEffect::Sparkle, &self.player.center());
+          self.popups.spawn(self.player.id(), format!("+{}", PLAYER_SCORE_REWARD), REWARD_COLOR, &self.player.top_center());
+        }
       } else {
         goal.set_intersected(&self.player, false);
       }
       for child in &mut self.children {
         if child.state_is_still() && goal.intersects_round(&*child) {
-          goal.trigger_once(child);
+          if goal.trigger_once(child) {
+            self.sounds.push(SoundEvent::GoalEntered);
+            self.particles.emit(ParticleEffect::Sparkle, &child.center());
+            self.popups.spawn(child.id(), format!("+{}", CHILD_SCORE_REWARD), REWARD_COLOR, &child.top_center());
+          }
         } else {
           goal.set_intersected(&*child, false);
         }
@@ -285,12 +492,16 @@ impl Level {
       {
         child.set_velocity_x(0.0);
         // Stop walking when hitting a wall AND standing on floor
-        if child.on_floor() {
+        if child.on_floor() && !child.state_is_still() {
           child.stop_walking();
+          self.sounds.push(SoundEvent::ChildBump);
         }
       }
       // Kill y velocity when standing on floor
       if child.velocity().y != 0.0 && new_pos.y == child.point().y {
+        if child.velocity().y > 0.0 && child.land() {
+          self.particles.emit(ParticleEffect::Dust, &child.bottom_center());
+        }
         child.set_velocity_y(0.0);
       }
       // Move to new position
@@ -343,12 +554,27 @@ impl Level {
     }
     // Hacky way to check if a solid block is beneath
     if self.player.velocity().y != 0.0 && new_pos.y == self.player.point().y {
+      if self.player.velocity().y > 0.0 && self.player.land() {
+        self.particles.emit(ParticleEffect::Dust, &self.player.bottom_center());
+      }
       self.player.set_velocity_y(0.0);
       self.player.stop_jumping();
     }
+    // Kick up dust when starting to walk on the floor
+    let walking = self.player.velocity().x != 0.0;
+    if walking && !self.player_walking && self.player.velocity().y == 0.0 {
+      self.particles.emit(ParticleEffect::Dust, &self.player.bottom_center());
+    }
+    self.player_walking = walking;
     if &new_pos != self.player.point() {
       self.player.point_mut().set(&new_pos);
     }
+    for sound in self.player.drain_sounds() {
+      if sound == SoundEvent::PlayerJump {
+        self.stats_events.push(StatsEvent::Jumped);
+      }
+      self.sounds.push(sound);
+    }
     self.player.update(ctx)
   }
 
@@ -368,6 +594,7 @@ impl Level {
     if let Some(goal) = &self.interactables.goal {
       self.toolbox.set_to_save_amount(goal.get_intersected().len());
     }
+    self.toolbox.set_selected(self.selected_child);
     self.toolbox.update()?;
     Ok(())
   }
@@ -394,18 +621,58 @@ impl Level {
 
   fn moved_child(&mut self, child_type: ChildType) {
     self.score.moved_child(child_type);
+    self.sounds.push(SoundEvent::ChildCommanded);
+    self.stats_events.push(StatsEvent::CommandedChild(child_type));
+    let (id, top, bottom) = if let Some(child) = self.child(child_type) {
+      (child.id(), child.top_center(), child.bottom_center())
+    } else { return; };
+    self.particles.emit(ParticleEffect::Dust, &bottom);
+    let lines = child_type.flavour_lines();
+    let name  = child_type.name();
+    let text  = if lines.is_empty() {
+      name
+    } else {
+      format!("{}: {}", name, lines[rand::thread_rng().gen_range(0, lines.len())])
+    };
+    self.popups.spawn(id, text, TEXT_COLOR, &top);
+    self.popups.spawn(id, "-1", PENALTY_COLOR, &top);
   }
 
   pub fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.draw_decorations(ctx, DecorationLayer::Back)?;
     if let Some(goal) = &mut self.interactables.goal {
       self.camera.draw(ctx, goal)?;
     }
     self.draw_walls(ctx)?;
+    self.draw_ghost(ctx)?;
     self.draw_children(ctx)?;
     self.draw_player(ctx)?;
     self.draw_interactables(ctx)?;
+    self.particles.draw_offset(ctx, &self.camera.top_left().inverted())?;
+    self.popups.draw_offset(ctx, &self.camera.top_left().inverted())?;
+    self.draw_decorations(ctx, DecorationLayer::Front)?;
     self.draw_level_name(ctx)?;
     self.toolbox.draw(ctx)?;
+    let arrow_point = self.hint_arrow_point();
+    self.hints.draw(ctx, arrow_point)?;
+    Ok(())
+  }
+
+  // Where on the screen the current hint's arrow points to
+  fn hint_arrow_point(&self) -> Option<Point> {
+    match self.hints.arrow()? {
+      HintTarget::World(point) => Some(Point::combine(vec![point, &self.camera.top_left().inverted()])),
+      HintTarget::Toolbox      => self.toolbox.buttons().first().map( |btn| btn.top_center() ),
+      HintTarget::NextLevel    => Some(self.toolbox.next_level_button().top_center()),
+    }
+  }
+
+  fn draw_decorations(&mut self, ctx: &mut Context, layer: DecorationLayer) -> GameResult<()> {
+    for decoration in &self.decorations {
+      if decoration.layer == layer {
+        self.camera.draw(ctx, decoration)?;
+      }
+    }
     Ok(())
   }
 
@@ -430,20 +697,42 @@ impl Level {
     Ok(())
   }
 
+  fn draw_ghost(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if !config::with( |c| c.ghosts ) { return Ok(()); }
+    if let Some(ghost) = &self.ghost {
+      ghost.draw_offset(ctx, &self.camera.top_left().inverted())?;
+    }
+    Ok(())
+  }
+
   fn draw_children(&mut self, ctx: &mut Context) -> GameResult<()> {
     for child in &self.children {
+      if Some(child.child_type) == self.selected_child {
+        self.draw_selected_highlight(ctx, child)?;
+      }
       self.camera.draw(ctx, child)?;
     }
     Ok(())
   }
 
+  fn draw_selected_highlight(&self, ctx: &mut Context, child: &Child) -> GameResult<()> {
+    if !self.camera.intersects(child) { return Ok(()); }
+    let top_left = Point::combine(vec![&child.top_left(), &self.camera.top_left().inverted()]);
+    ColorRect::new(
+      Point::new(top_left.x - SELECTED_PADDING, top_left.y - SELECTED_PADDING),
+      Size::new(child.size().w + SELECTED_PADDING * 2.0, child.size().h + SELECTED_PADDING * 2.0),
+      Origin::TopLeft,
+      SELECTED_COLOR
+    ).draw(ctx)
+  }
+
   fn draw_player(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.camera.draw(ctx, &self.player)
   }
 
   fn draw_level_name(&mut self, ctx: &mut Context) -> GameResult<()> {
     let dest = graphics::Point2::from(
-      &(Point::new(-8.0, 8.0) + self.window_rect.top_right())
+      &Layout::new(self.window_rect.size()).at(Origin::TopRight, -8.0, 8.0)
     );
     let param = graphics::DrawParam {
       dest,
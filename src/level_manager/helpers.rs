@@ -1,53 +1,40 @@
-use ggez::Context;
+use ggez::{
+  Context,
+  GameResult,
+  GameError,
+  graphics::{ Text, Font },
+};
 use noframe::geo::prelude::*;
 
-use settings::res::BACKGROUND_IMAGES;
 use settings::buttons;
-use animation::prelude::*;
+use settings::level_manager::hot_reload;
+use text_box::prelude::*;
 use menu::buttons::prelude::*;
+use layout::Layout;
 
-pub fn new_background(ctx: &mut Context, n: usize) -> Option<Animation> {
-  let chapter = n / 5;
-  let sub = n % 5;
-  match chapter {
-    0 => match sub {
-      0 => Some("bg1.1.png"),
-      1 => Some("bg1.2.png"),
-      2 => Some("bg1.3.png"),
-      3 => Some("bg1.4.png"),
-      4 => Some("bg1.5.png"),
-      _ => None,
-    },
-    1 => match sub {
-      0 => Some("bg2.1.png"),
-      1 => Some("bg2.2.png"),
-      2 => Some("bg2.3.png"),
-      3 => Some("bg2.4.png"),
-      4 => Some("bg2.5.png"),
-      _ => None,
-    },
-    2 => match sub {
-      0 => Some("bg3.1.png"),
-      1 => Some("bg3.2.png"),
-      2 => Some("bg3.3.png"),
-      3 => Some("bg3.4.png"),
-      4 => Some("bg3.5.png"),
-      _ => None,
-    },
-    _ => Some("bg4.1.png"),
-  } .and_then( |img| Some(Animation::new(
-        ctx,
-        vec![::join_str(BACKGROUND_IMAGES, img)],
-        vec![1000]
-  )))
-}
-
-pub fn new_pause_button(ctx: &mut Context, _window_size: &Size) -> Button {
+pub fn new_pause_button(ctx: &mut Context, window_size: &Size) -> Button {
   ButtonBuilder::new(ctx)
-    .point(Point::new(4.0, 4.0))
+    .point(Layout::new(window_size).at(Origin::TopLeft, 4.0, 4.0))
     .size(Size::new(36.0, 36.0))
     .origin(Origin::TopLeft)
     .button_type(ButtonType::IngamePause)
     .animation_from(vec![::join_str(buttons::IMAGES, "pause.png")], vec![1000])
     .build().expect("Should build IngamePause Button")
 }
+
+pub fn new_reload_error_text(ctx: &mut Context, font: &Font, window_size: &Size, error: &GameError) -> GameResult<TextBox> {
+  let msg = match error {
+    GameError::UnknownError(msg) => msg.clone(),
+    _                            => error.to_string(),
+  };
+  let text = Text::new(ctx, &format!("Couldn't reload level: {}", msg), font)?;
+  Ok(TextBoxBuilder::new()
+     .point(Layout::new(window_size).at(Origin::TopCenter, 0.0, 48.0))
+     .size_from(text.width() as NumType + 16.0, text.height() as NumType + 8.0)
+     .origin(Origin::TopCenter)
+     .bg_color(hot_reload::BG_COLOR)
+     .text(text)
+     .text_color(hot_reload::FONT_COLOR)
+     .text_origin(TextOrigin::TopCenter)
+     .build())
+}
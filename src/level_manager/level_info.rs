@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use score::Score;
+use score::leaderboards::LeaderboardEntry;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LevelStatus {
+  Locked,
+  Unlocked,
+  Cleared,
+}
+
+// Everything the level select menu needs to know about a level.
+#[derive(Clone)]
+pub struct LevelInfo {
+  pub name:        String,
+  pub filepath:    PathBuf,
+  pub status:      LevelStatus,
+  pub highscore:   Option<Score>,
+  pub leaderboard: Vec<LeaderboardEntry>,
+}
@@ -0,0 +1,213 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use ggez::{
+  Context,
+  GameResult,
+  GameError,
+};
+use json::JsonValue;
+
+use settings::res;
+use settings::level_manager::*;
+use json_file;
+
+// Unlocks a level once all levels in `after` and at least `cleared` levels of its pack are cleared.
+#[derive(Clone, Debug)]
+pub struct UnlockRule {
+  pub after:   Vec<String>,
+  pub cleared: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct LevelPack {
+  pub id:           String,
+  pub name:         String,
+  pub directory:    PathBuf,
+  pub level_names:  Vec<String>,
+  pub song_names:   Vec<String>,
+  pub unlock_rules: Vec<Option<UnlockRule>>,
+  pub chapter_size: Option<usize>,
+}
+
+impl LevelPack {
+  pub fn main() -> Self {
+    Self {
+      id:           MAIN_PACK_ID.to_string(),
+      name:         MAIN_PACK_NAME.to_string(),
+      directory:    PathBuf::from(res::LEVELS),
+      level_names:  LEVEL_NAMES.iter().map( |name| name.to_string() ).collect(),
+      song_names:   SONG_NAMES.iter().map( |name| name.to_string() ).collect(),
+      unlock_rules: vec![None; LEVEL_NAMES.len()],
+      chapter_size: Some(CHAPTER_SIZE),
+    }
+  }
+
+  pub fn from_manifest(directory: &Path) -> GameResult<Self> {
+    let id = directory.file_name()
+      .and_then( |name| name.to_str() )
+      .map( |name| name.to_string() )
+      .ok_or(GameError::from(format!("Invalid level pack directory: {}", directory.display())))?;
+    if id == MAIN_PACK_ID {
+      return Err(GameError::from(format!("Level pack id is reserved: {}", id)));
+    }
+
+    let data = json_file::read(&directory.join(res::LEVEL_PACK_MANIFEST))?;
+
+    let mut level_names  = Vec::new();
+    let mut song_names   = Vec::new();
+    let mut unlock_rules = Vec::new();
+    for (i, level) in data["levels"].members().enumerate() {
+      let (name, song) = Self::level_entry(level, i)?;
+      level_names.push(name);
+      song_names.push(song);
+      unlock_rules.push(Self::unlock_rule(&level["unlock"]));
+    }
+    if level_names.is_empty() {
+      return Err(GameError::from(format!("Level pack has no levels: {}", id)));
+    }
+
+    Ok(Self {
+      name: data["name"].as_str().unwrap_or(&id).to_string(),
+      id,
+      directory:    directory.to_path_buf(),
+      level_names,
+      song_names,
+      unlock_rules,
+      chapter_size: data["chapter_size"].as_usize().filter( |&size| size > 0 ),
+    })
+  }
+
+  // A level entry is either just the level's filename (without `.json`),
+  // or an object with a `name` and an optional `song`.
+  fn level_entry(level: &JsonValue, index: usize) -> GameResult<(String, String)> {
+    let default_song = SONG_NAMES[index % SONG_NAMES.len()];
+    if let Some(name) = level.as_str() {
+      Ok(( name.to_string(), default_song.to_string() ))
+    } else if let Some(name) = level["name"].as_str() {
+      Ok(( name.to_string(), level["song"].as_str().unwrap_or(default_song).to_string() ))
+    } else {
+      Err(GameError::from(format!("Invalid level entry in level pack manifest at index {}", index)))
+    }
+  }
+
+  fn unlock_rule(unlock: &JsonValue) -> Option<UnlockRule> {
+    if !unlock.is_object() { return None; }
+    Some(UnlockRule {
+      after:   unlock["after"].members()
+        .filter_map( |name| name.as_str().map( |name| name.to_string() ))
+        .collect(),
+      cleared: unlock["cleared"].as_usize().unwrap_or(0),
+    })
+  }
+
+  // A level is unlocked if it's the first level, if the previous level was cleared,
+  // if its manifest's unlock rule is met, or if it starts a chapter and
+  // enough levels of the previous chapter were cleared.
+  pub fn is_unlocked(&self, index: usize, cleared: &Vec<bool>) -> bool {
+    let is_cleared = |i: usize| cleared.get(i).cloned().unwrap_or(false);
+    if index == 0 || is_cleared(index - 1) {
+      return true;
+    }
+    if let Some(Some(rule)) = self.unlock_rules.get(index) {
+      let after_cleared = rule.after.iter().all( |name| {
+        self.level_names.iter().position( |n| n == name ).map( |i| is_cleared(i) ).unwrap_or(false)
+      });
+      let cleared_count = cleared.iter().filter( |&&c| c ).count();
+      if after_cleared && cleared_count >= rule.cleared {
+        return true;
+      }
+    }
+    if let Some(chapter_size) = self.chapter_size {
+      if index % chapter_size == 0 {
+        let prev_chapter_cleared = (index - chapter_size .. index).filter( |&i| is_cleared(i) ).count();
+        if prev_chapter_cleared >= CHAPTER_UNLOCK_CLEARED.min(chapter_size) {
+          return true;
+        }
+      }
+    }
+    false
+  }
+
+  pub fn is_main(&self) -> bool {
+    self.id == MAIN_PACK_ID
+  }
+
+  pub fn level_filepath(&self, level_name: &str) -> PathBuf {
+    self.directory.join(::join_str(level_name, ".json"))
+  }
+}
+
+pub fn user_level_packs_dir(ctx: &Context) -> PathBuf {
+  ctx.filesystem.get_user_data_dir().join(res::USER_LEVELS)
+}
+
+pub fn discover_level_packs(ctx: &Context) -> Vec<LevelPack> {
+  let mut packs = Vec::new();
+  if let Ok(entries) = fs::read_dir(user_level_packs_dir(ctx)) {
+    for entry in entries.filter_map( |entry| entry.ok() ) {
+      let path = entry.path();
+      if !path.join(res::LEVEL_PACK_MANIFEST).is_file() { continue; }
+      match LevelPack::from_manifest(&path) {
+        Ok(pack) => packs.push(pack),
+        Err(e)   => eprintln!("Couldn't load level pack {}: {}", path.display(), e),
+      }
+    }
+  }
+  packs.sort_by( |a, b| a.name.cmp(&b.name) );
+  packs.insert(0, LevelPack::main());
+  packs
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pack(level_count: usize, chapter_size: Option<usize>) -> LevelPack {
+    LevelPack {
+      id:           "test".to_string(),
+      name:         "Test".to_string(),
+      directory:    PathBuf::new(),
+      level_names:  (0 .. level_count).map( |i| format!("level_{}", i) ).collect(),
+      song_names:   vec![String::new(); level_count],
+      unlock_rules: vec![None; level_count],
+      chapter_size,
+    }
+  }
+
+  #[test]
+  fn the_first_level_and_levels_after_cleared_ones_are_unlocked() {
+    let pack = pack(4, None);
+    let cleared = vec![true, false, false, false];
+    assert!(pack.is_unlocked(0, &cleared));
+    assert!(pack.is_unlocked(1, &cleared));
+    assert!(!pack.is_unlocked(2, &cleared));
+    assert!(pack.is_unlocked(0, &vec![]));
+  }
+
+  #[test]
+  fn unlock_rules_need_their_levels_and_enough_cleared_levels() {
+    let mut pack = pack(5, None);
+    pack.unlock_rules[4] = Some(UnlockRule { after: vec!["level_0".to_string()], cleared: 2 });
+    assert!(!pack.is_unlocked(4, &vec![true, false, false, false, false]));
+    assert!(!pack.is_unlocked(4, &vec![false, true, true, false, false]));
+    assert!(pack.is_unlocked(4, &vec![true, false, true, false, false]));
+  }
+
+  #[test]
+  fn unlock_rules_with_unknown_levels_are_never_met() {
+    let mut pack = pack(3, None);
+    pack.unlock_rules[2] = Some(UnlockRule { after: vec!["missing".to_string()], cleared: 0 });
+    assert!(!pack.is_unlocked(2, &vec![true, false, false]));
+  }
+
+  #[test]
+  fn chapters_unlock_once_enough_of_the_previous_chapter_is_cleared() {
+    let pack = pack(10, Some(5));
+    let cleared = (0 .. 10).map( |i| i < CHAPTER_UNLOCK_CLEARED ).collect::<Vec<bool>>();
+    assert!(pack.is_unlocked(5, &cleared));
+    assert!(!pack.is_unlocked(6, &cleared));
+    let too_few = (0 .. 10).map( |i| i + 1 < CHAPTER_UNLOCK_CLEARED ).collect::<Vec<bool>>();
+    assert!(!pack.is_unlocked(5, &too_few));
+  }
+}
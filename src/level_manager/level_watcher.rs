@@ -0,0 +1,45 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::time::{ Instant, Duration, SystemTime };
+
+use settings::level_manager::hot_reload::INTERVAL_MS;
+
+// Polls the current level's JSON file for changes.
+pub struct LevelWatcher {
+  filepath:    PathBuf,
+  modified:    Option<SystemTime>,
+  last_check:  Instant,
+}
+
+impl LevelWatcher {
+  pub fn new(filepath: &Path) -> Self {
+    Self {
+      filepath:   filepath.to_path_buf(),
+      modified:   modified_time(filepath),
+      last_check: Instant::now(),
+    }
+  }
+
+  pub fn filepath(&self) -> &Path {
+    &self.filepath
+  }
+
+  pub fn changed(&mut self) -> bool {
+    let now = Instant::now();
+    if now - self.last_check < Duration::from_millis(INTERVAL_MS) {
+      return false;
+    }
+    self.last_check = now;
+    let modified = modified_time(&self.filepath);
+    if modified.is_some() && modified != self.modified {
+      self.modified = modified;
+      true
+    } else {
+      false
+    }
+  }
+}
+
+fn modified_time(filepath: &Path) -> Option<SystemTime> {
+  fs::metadata(filepath).and_then( |meta| meta.modified() ).ok()
+}
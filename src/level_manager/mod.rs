@@ -1,30 +1,49 @@
 mod helpers;
+pub mod level_pack;
+pub mod level_info;
+mod level_watcher;
+mod speedrun;
 
 use std::collections::hash_map::HashMap;
+use std::path::PathBuf;
 
 use ggez::{
   GameResult,
   Context,
   graphics,
   event::Keycode,
-  audio,
 };
 use json::JsonValue;
 use noframe::geo::prelude::*;
 use noframe::deltatime::Deltatime;
 
 use self::helpers::*;
+use self::level_pack::{ LevelPack, discover_level_packs };
+use self::level_watcher::LevelWatcher;
+use self::level_info::{ LevelInfo, LevelStatus };
+use self::speedrun::{ Speedrun, splits_from_json };
+use action::Action;
 use level::Level;
+use ghost::Ghost;
+use sfx::Sfx;
+use background::Background;
 use settings::level_manager::*;
 use settings::res;
-use settings::game::{ MUTED, VOLUME };
 use settings::score::HIGHSCORE_COLOR;
-use animation::Animation;
-use animation::Facing;
-use score::Score;
+use score::{ Score, TickType };
+use score::leaderboards::{ Leaderboards, LeaderboardEntry };
+use score::lifetime::{ LifetimeStats, LevelStats, StatsEvent };
+use achievements::{ Achievements, AchievementInfo };
+use text_box::TextBox;
 use menu::buttons::prelude::*;
 use menu::pause::prelude::*;
+use menu::controls::prelude::*;
+use menu::options::prelude::*;
+use menu::MenuInput;
 use menu::stats::prelude::*;
+use dialogue::prelude::*;
+use layout::Layout;
+use config;
 
 struct ToTitleParams {
   pub beat_level:   bool,
@@ -34,19 +53,37 @@ struct ToTitleParams {
 pub struct LevelManager {
   level_index:      usize,
   level:            Option<Level>,
-  level_names:      Vec<&'static str>,
-  song:             Option<audio::Source>,
-  song_names:       Vec<&'static str>,
-  background:       Option<Animation>,
+  level_packs:      Vec<LevelPack>,
+  pack:             LevelPack,
+  packs_data:       JsonValue,
+  level_names:      Vec<String>,
+  song_names:       Vec<String>,
+  sfx:              Sfx,
+  background:       Option<Background>,
   window_size:      Size,
   scores:           HashMap<usize, Score>,
+  seen_hints:       Vec<String>,
   paused:           bool,
   pause_menu:       PauseMenu,
+  controls_menu:    Option<ControlsMenu>,
+  options_menu:     Option<OptionsMenu>,
   pause_button:     Button,
   stats_menu:       Option<StatsMenu>,
   final_stats_menu: Option<StatsMenu>,
   highscore_font:   graphics::Font,
   highscore_text:   Option<StatsText>,
+  level_watcher:    Option<LevelWatcher>,
+  reload_font:      graphics::Font,
+  reload_error:     Option<TextBox>,
+  dialogue:         Option<Dialogue>,
+  dialogue_queue:   Vec<String>,
+  speedrun:         Option<Speedrun>,
+  best_splits:      Vec<TickType>,    // The current level pack's personal best run
+  leaderboards:     Leaderboards,
+  profile_name:     String,           // For the leaderboards
+  ghost_dir:        Option<PathBuf>,  // The profile's recorded ghosts
+  lifetime_stats:   LifetimeStats,
+  achievements:     Achievements,
   pub to_title:     bool,
   pub to_thank_you: bool,
   pub beat_game:    bool,
@@ -56,22 +93,42 @@ pub struct LevelManager {
 
 impl LevelManager {
   pub fn new(ctx: &mut Context, window_size: Size) -> Self {
+    let level_packs = discover_level_packs(ctx);
+    let pack = LevelPack::main();
     Self {
       level_index:      0,
       level:            None,
-      level_names:      LEVEL_NAMES.to_vec(),
-      song:             None,
-      song_names:       SONG_NAMES.to_vec(),
+      level_names:      pack.level_names.clone(),
+      song_names:       pack.song_names.clone(),
+      sfx:              Sfx::new(),
+      level_packs,
+      pack,
+      packs_data:       object!{},
       background:       None,
       window_size:      window_size.clone(),
       scores:           HashMap::new(),
+      seen_hints:       Vec::new(),
       paused:           false,
       pause_menu:       PauseMenu::new(ctx, window_size.clone()),
+      controls_menu:    None,
+      options_menu:     None,
       pause_button:     new_pause_button(ctx, &window_size),
       stats_menu:       None,
       final_stats_menu: None,
       highscore_font:   graphics::Font::new(ctx, res::fonts::DEFAULT, HIGHSCORE_FONT_SIZE).expect("New highscore font"),
       highscore_text:   None,
+      level_watcher:    None,
+      reload_font:      graphics::Font::new(ctx, res::fonts::DEFAULT, hot_reload::FONT_SIZE).expect("New reload error font"),
+      reload_error:     None,
+      dialogue:         None,
+      dialogue_queue:   Vec::new(),
+      speedrun:         None,
+      best_splits:      Vec::new(),
+      leaderboards:     Leaderboards::load(ctx),
+      profile_name:     String::new(),
+      ghost_dir:        None,
+      lifetime_stats:   LifetimeStats::new(),
+      achievements:     Achievements::load(),
       to_title:         false,
       to_thank_you:     false,
       beat_game:        false,
@@ -81,37 +138,184 @@ impl LevelManager {
   }
 
   pub fn load_level_json(&mut self, json: &JsonValue) {
-    // Set level_index
-    if let Some(level_index) = json["level_index"].as_usize() {
-      self.level_index = level_index;
+    // Scores of other level packs are only loaded once their pack is selected
+    if json["packs"].is_object() {
+      self.packs_data = json["packs"].clone();
     }
-    // Delete existing scores (there shouldn't be any, as this should only be called at the start of the game)
+    // Hints are shared by all level packs
+    self.seen_hints = json["hints"].members()
+      .filter_map( |id| id.as_str().map( |id| id.to_string() ) )
+      .collect();
+    // So are the lifetime stats, which keep every level pack's levels apart
+    self.lifetime_stats = LifetimeStats::from_json(&json["stats"]);
+    self.achievements.load_unlocked(&json["achievements"]);
+    self.load_pack_json(json);
+  }
+
+  fn load_pack_json(&mut self, json: &JsonValue) {
+    // Set level_index
+    self.level_index = json["level_index"].as_usize().unwrap_or(0);
+    // Delete existing scores (from the previously selected level pack)
     self.scores = HashMap::new();
     // Load scores from json
     for (name, level_json) in json["levels"].entries() {
       if let Some(score) = Score::from_json(&level_json["score"]) {
-        if let Some(index) = self.level_names.iter().position( |lvlname| lvlname == &name ) {
+        if let Some(index) = self.level_names.iter().position( |lvlname| lvlname == name ) {
           self.scores.insert(index, score);
         }
       }
     }
+    self.best_splits = splits_from_json(&json["speedrun"]["best_splits"]);
   }
 
-  fn save(&mut self) {
+  fn pack_json(&self) -> JsonValue {
     let mut data = object!{
       "level_index" => self.level_index,
       "levels"      => object!{},
     };
     for (&index, score) in &self.scores {
-      let name = self.level_names[index];
-      data["levels"][name] = object!{};
-      if let Some(score_json) = score.as_json() {
-        data["levels"][name]["score"] = score_json;
-      }
+      let name = self.level_names[index].as_str();
+      data["levels"][name] = object!{
+        "score" => score.as_json(),
+      };
+    }
+    if !self.best_splits.is_empty() {
+      data["speedrun"] = object!{
+        "best_splits" => self.best_splits.clone(),
+      };
+    }
+    data
+  }
+
+  // The main campaign is saved at the top level (as `level_index` and `levels`),
+  // all other level packs are saved under `packs`, keyed by their id.
+  fn save(&mut self) {
+    let mut packs = self.packs_data.clone();
+    packs[self.pack.id.as_str()] = self.pack_json();
+    let mut data = packs.remove(MAIN_PACK_ID);
+    if !data.is_object() {
+      data = object!{};
     }
+    data["packs"] = packs;
+    data["hints"] = self.seen_hints.clone().into();
+    data["stats"] = self.lifetime_stats.as_json();
+    data["achievements"] = self.achievements.unlocked_json();
     self.save_data = Some(data);
   }
 
+  // Saving during a level resumes at that level, as going back to the title does
+  fn save_in_level(&mut self) {
+    let next_index = self.level_index;
+    self.level_index = self.get_current_level_index().unwrap_or(0);
+    self.save();
+    self.level_index = next_index;
+  }
+
+  pub fn set_profile_name(&mut self, name: &str) {
+    self.profile_name = name.to_string();
+  }
+
+  pub fn set_ghost_dir(&mut self, dir: PathBuf) {
+    self.ghost_dir = Some(dir);
+  }
+
+  // Every level pack has its own ghosts, e.g. `profile_0.ghosts/main/01_one.json`
+  fn ghost_filepath(&self, level_index: usize) -> Option<PathBuf> {
+    let level_name = self.level_names.get(level_index)?;
+    self.ghost_dir.as_ref().map( |dir| dir.join(&self.pack.id).join(::join_str(level_name, ".json")) )
+  }
+
+  // The level's best run, if one was recorded.
+  // It's loaded even while ghosts are turned off, so they can be turned on during the level.
+  fn load_ghost(&self, ctx: &mut Context, level_index: usize) -> Option<Ghost> {
+    let filepath = self.ghost_filepath(level_index)?;
+    if !filepath.exists() { return None; }
+    match Ghost::load(ctx, &filepath) {
+      Ok(ghost) => Some(ghost),
+      Err(e)    => {
+        eprintln!("Couldn't load ghost {}: {}", filepath.display(), e);
+        None
+      }
+    }
+  }
+
+  pub fn lifetime_stats(&self) -> &LifetimeStats {
+    &self.lifetime_stats
+  }
+
+  // The lifetime stats of every played level, labeled with its name, in the order of the level packs
+  pub fn level_stats(&self) -> Vec<(String, LevelStats)> {
+    let mut levels = Vec::new();
+    for pack in &self.level_packs {
+      for level_name in &pack.level_names {
+        if let Some(stats) = self.lifetime_stats.level(&pack.id, level_name) {
+          let label = if pack.is_main() {
+            ::semantic(level_name)
+          } else {
+            format!("{}: {}", pack.name, ::semantic(level_name))
+          };
+          levels.push((label, stats.clone()));
+        }
+      }
+    }
+    levels
+  }
+
+  pub fn achievements(&self) -> &Achievements {
+    &self.achievements
+  }
+
+  pub fn drain_unlocked_achievements(&mut self) -> Vec<AchievementInfo> {
+    self.achievements.drain_new_unlocked()
+  }
+
+  pub fn level_packs(&self) -> &Vec<LevelPack> {
+    &self.level_packs
+  }
+
+  pub fn pack(&self) -> &LevelPack {
+    &self.pack
+  }
+
+  // The unlocked levels are derived from the saved scores; a level with a score is cleared.
+  pub fn level_infos(&self) -> Vec<LevelInfo> {
+    let cleared = (0 .. self.level_names.len())
+      .map( |i| self.scores.contains_key(&i) )
+      .collect::<Vec<bool>>();
+    self.level_names.iter().enumerate().map( |(i, name)| {
+      let highscore = self.scores.get(&i).map( |score| score.clone() );
+      let status = if cleared[i] {
+        LevelStatus::Cleared
+      } else if self.pack.is_unlocked(i, &cleared) {
+        LevelStatus::Unlocked
+      } else {
+        LevelStatus::Locked
+      };
+      LevelInfo {
+        name:        name.clone(),
+        filepath:    self.pack.level_filepath(name),
+        status,
+        highscore,
+        leaderboard: self.leaderboards.entries(&self.pack.id, name),
+      }
+    }).collect()
+  }
+
+  pub fn select_pack(&mut self, pack_index: usize) {
+    let pack = if let Some(pack) = self.level_packs.get(pack_index) {
+      pack.clone()
+    } else { return; };
+    if pack.id == self.pack.id { return; }
+    let curr_pack_json = self.pack_json();
+    self.packs_data[self.pack.id.as_str()] = curr_pack_json;
+    self.reset();
+    self.level_names = pack.level_names.clone();
+    self.song_names  = pack.song_names.clone();
+    let pack_json = self.packs_data[pack.id.as_str()].clone();
+    self.pack = pack;
+    self.load_pack_json(&pack_json);
+  }
+
   pub fn level(&mut self) -> Option<&mut Level> {
     if let Some(level) = &mut self.level {
       Some(level)
@@ -158,39 +362,32 @@ impl LevelManager {
     self.stats_menu = None;
 
     // Load the next level
+    self.level_watcher = None;
+    self.reload_error  = None;
+    self.close_dialogue();
     if let Some(level_name) = self.level_names.get(self.level_index) {
-      self.level = Some( Level::new(ctx, self.window_size.clone(), level_name, self.level_index)? );
-    } else {
-      self.level = None;
-    }
-    // Load audio
-    let mut muted = false;
-    if let Some(song_name) = self.song_names.get(self.level_index) {
-      let mut is_same = false;
-      let mut curr_song_stopped = true;
-      if let Some(curr_song) = &self.song {
-        if let Some(curr_level_index) = self.get_current_level_index() {
-          if let Some(curr_song_name) = self.song_names.get(curr_level_index) {
-            is_same = curr_song_name == song_name;
-          }
-        }
-        muted = curr_song.paused();
-        curr_song_stopped = curr_song.stopped();
-        if !is_same {
-          curr_song.stop();
-        }
+      // Starting the first level starts a new run
+      if self.level_index == 0 {
+        self.speedrun = if config::with( |c| c.speedrun ) {
+          Some(Speedrun::new(ctx, self.best_splits.clone())?)
+        } else { None };
       }
-      if !is_same || curr_song_stopped {
-        let mut song = audio::Source::new(ctx, format!("{}{}.{}", res::AUDIO, song_name, AUDIO_FORMAT))?;
-        song.set_volume(VOLUME);
-        song.set_repeat(true);
-        song.play()?;
-        if muted || MUTED { song.pause(); }
-        self.song = Some( song );
+      self.achievements.start_level(self.level_index == 0);
+      let filepath = self.pack.level_filepath(level_name);
+      let mut level = Level::new(ctx, self.window_size.clone(), &filepath, self.level_index)?;
+      // The start dialogue opens before the level is updated
+      self.dialogue_queue = level.drain_dialogue();
+      level.skip_seen_hints(&self.seen_hints);
+      level.set_ghost(self.load_ghost(ctx, self.level_index));
+      self.level = Some(level);
+      if config::with( |c| c.hot_reload ) {
+        self.level_watcher = Some(LevelWatcher::new(&filepath));
       }
+    } else {
+      self.level = None;
     }
-    // Load background animation
-    self.background = new_background(ctx, self.level_index);
+    // Load the background, the level's own layers or its chapter's
+    self.background = Some(self.new_background(ctx, self.level_index));
     if self.level.is_some() {
       self.level_index += 1;
     } else {
@@ -207,7 +404,7 @@ impl LevelManager {
     if let Some(highscore) = self.highscore().map( |s| s.clone() ) {
       self.highscore_text = Some(StatsText::new(
           graphics::Text::new(ctx, &highscore.semantic_highscore(), &self.highscore_font)?,
-          Point::new(self.window_size.w / 2.0, 8.0),
+          Layout::new(&self.window_size).at(Origin::TopCenter, 0.0, 8.0),
           TextOrigin::Center,
           Some(HIGHSCORE_COLOR)
       ));
@@ -216,7 +413,15 @@ impl LevelManager {
   }
 
   fn beat_final_level(&mut self, ctx: &mut Context) -> GameResult<()> {
-    self.beat_game = true;
+    if self.pack.is_main() {
+      self.beat_game = true;
+      self.achievements.beat_game();
+    }
+    if let Some(speedrun) = &self.speedrun {
+      if speedrun.is_personal_best(self.level_names.len()) {
+        self.best_splits = speedrun.splits().clone();
+      }
+    }
     self.save();
     self.final_stats_menu = Some(
       StatsMenu::new(
@@ -224,31 +429,39 @@ impl LevelManager {
         self.window_size.clone(),
         self.total_score(),
         None,  // TODO highscore
+        &Vec::new(),
+        None,
         true
       )?
     );
     Ok(())
   }
 
-  pub fn keys_pressed(&mut self, _ctx: &mut Context, keys: &Vec<Keycode>) {
+  pub fn actions_pressed(&mut self, _ctx: &mut Context, actions: &Vec<Action>) {
     if let Some(level) = &mut self.level {
-      level.keys_pressed(keys, &self.dt);
+      level.actions_pressed(actions, &self.dt);
     }
   }
 
-  pub fn keys_down(&mut self, ctx: &mut Context, keys: &Vec<Keycode>) {
-    for &key in keys {
-      match key {
-        controls::MUTE  => self.toggle_mute(),
-        controls::PAUSE => self.toggle_pause(),
-        controls::RESET => self.reset_level(ctx).expect("Should reset level"),
-        controls::NEXT =>
+  pub fn actions_down(&mut self, ctx: &mut Context, actions: &Vec<Action>) {
+    if self.has_submenu() {
+      if actions.iter().any( |&action| action == Action::Pause || action == Action::ToTitle ) {
+        self.close_submenus();
+      }
+      return;
+    }
+
+    for &action in actions {
+      match action {
+        Action::Pause => self.toggle_pause(),
+        Action::Reset => self.reset_level(ctx).expect("Should reset level"),
+        Action::Next  =>
           if self.paused {
             self.toggle_pause();
           } else if self.stats_menu.is_some() {
             self.next_level(ctx).expect("Should load next level")
           },
-        controls::TO_TITLE =>
+        Action::ToTitle =>
           if self.paused || self.stats_menu.is_some() || self.final_stats_menu.is_some() {
             let has_stats_menu = self.stats_menu.is_some();
             let has_final_stats_menu = self.final_stats_menu.is_some();
@@ -263,17 +476,102 @@ impl LevelManager {
     }
 
     if let Some(level) = &mut self.level {
-      level.keys_down(keys, &self.dt);
+      level.actions_down(actions, &self.dt);
+    }
+  }
+
+  pub fn actions_up(&mut self, _ctx: &mut Context, actions: &Vec<Action>) {
+    if let Some(level) = &mut self.level {
+      level.actions_up(actions, &self.dt);
+    }
+  }
+
+  pub fn menu_input(&mut self, input: MenuInput) {
+    if let Some(dialogue) = &mut self.dialogue {
+      dialogue.menu_input(input);
+    } else if self.has_submenu() {
+      if let MenuInput::Back = input {
+        self.close_submenus();
+      } else if let Some(controls_menu) = &mut self.controls_menu {
+        controls_menu.menu_input(input);
+      } else if let Some(options_menu) = &mut self.options_menu {
+        options_menu.menu_input(input);
+      }
+    } else if let Some(final_stats) = &mut self.final_stats_menu {
+      final_stats.menu_input(input);
+    } else if let Some(stats_menu) = &mut self.stats_menu {
+      stats_menu.menu_input(input);
+    } else if self.paused {
+      if let MenuInput::Back = input {
+        self.toggle_pause();
+      } else {
+        self.pause_menu.menu_input(input);
+      }
+    }
+  }
+
+  // While a dialogue is open, the GameState passes all actions here instead
+  pub fn in_dialogue(&self) -> bool {
+    self.dialogue.is_some()
+  }
+
+  pub fn dialogue_actions_down(&mut self, actions: &Vec<Action>) {
+    if let Some(dialogue) = &mut self.dialogue {
+      dialogue.actions_down(actions);
+    }
+  }
+
+  fn close_dialogue(&mut self) {
+    self.dialogue = None;
+    self.dialogue_queue.clear();
+  }
+
+  // The controls or options menu opened from the pause menu
+  fn has_submenu(&self) -> bool {
+    self.controls_menu.is_some() || self.options_menu.is_some()
+  }
+
+  fn close_submenus(&mut self) {
+    self.controls_menu = None;
+    self.options_menu  = None;
+  }
+
+  // The controls menu is waiting for a key to bind
+  pub fn is_rebinding(&self) -> bool {
+    self.controls_menu.as_ref().map( |menu| menu.is_waiting() ).unwrap_or(false)
+  }
+
+  pub fn rebind(&mut self, ctx: &mut Context, keycode: Keycode) -> GameResult<()> {
+    if let Some(controls_menu) = &mut self.controls_menu {
+      if keycode == Keycode::Escape {
+        controls_menu.cancel(ctx)?;
+      } else {
+        controls_menu.bind(ctx, keycode)?;
+      }
     }
+    Ok(())
   }
 
-  pub fn keys_up(&mut self, _ctx: &mut Context, keys: &Vec<Keycode>) {
+  // TODO: Temporary! Only for debugging!
+  pub fn skip_level(&mut self) {
     if let Some(level) = &mut self.level {
-      level.keys_up(keys, &self.dt);
+      level.skip();
     }
   }
 
   pub fn mouse_down(&mut self, x: i32, y: i32) {
+    if let Some(dialogue) = &mut self.dialogue {
+      dialogue.mouse_down(x, y);
+      return;
+    }
+    if let Some(controls_menu) = &mut self.controls_menu {
+      controls_menu.mouse_down(x, y);
+      return;
+    }
+    if let Some(options_menu) = &mut self.options_menu {
+      options_menu.mouse_down(x, y);
+      return;
+    }
     if self.paused {
       self.pause_menu.mouse_down(x, y);
     }
@@ -297,6 +595,16 @@ impl LevelManager {
     }
   }
 
+  // The current level's own song, or the one the level pack assigns to it
+  pub fn song_name(&self) -> Option<String> {
+    if let Some(song) = self.level.as_ref().and_then( |level| level.song() ) {
+      return Some(song.to_string());
+    }
+    self.get_current_level_index()
+      .and_then( |index| self.song_names.get(index) )
+      .cloned()
+  }
+
   fn get_current_level_index(&self) -> Option<usize> {
     if self.level_index > 0 {
       Some(self.level_index - 1)
@@ -305,16 +613,6 @@ impl LevelManager {
     }
   }
 
-  fn toggle_mute(&mut self) {
-    if let Some(song) = &self.song {
-      if song.paused() {
-        song.resume();
-      } else {
-        song.pause();
-      }
-    }
-  }
-
   fn toggle_pause(&mut self) {
     if self.stats_menu.is_some() || self.final_stats_menu.is_some() { return; }
     if self.paused {
@@ -322,6 +620,7 @@ impl LevelManager {
         level.reset_dt(&self.dt);
       }
       self.paused = false;
+      self.close_submenus();
     } else {
       self.paused = true;
     }
@@ -329,55 +628,196 @@ impl LevelManager {
 
   fn reset_level(&mut self, ctx: &mut Context) -> GameResult<()> {
     if self.final_stats_menu.is_some() { return Ok(()); }
+    // The ghost may have been replaced by the attempt that was just finished
+    let ghost = self.get_current_level_index().and_then( |index| self.load_ghost(ctx, index) );
     if let Some(level) = &mut self.level {
       level.reset(ctx)?;
+      level.set_ghost(ghost);
     }
+    if let Some(level_name) = self.get_current_level_index().and_then( |i| self.level_names.get(i) ).cloned() {
+      self.lifetime_stats.record(&self.pack.id, &level_name, StatsEvent::Reset);
+      self.achievements.record(StatsEvent::Reset);
+      self.achievements.check_lifetime(&self.lifetime_stats.total());
+    }
+    self.close_dialogue();
     self.paused = false;
+    self.close_submenus();
     self.stats_menu = None;
     self.set_highscore_text(ctx)?;
+    if self.achievements.has_new_unlocked() {
+      self.save_in_level();
+    }
     Ok(())
   }
 
   pub fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.update_pause_menu(ctx)?;
+    self.update_controls_menu(ctx)?;
+    self.update_options_menu(ctx)?;
     if self.level.is_some() {
       self.pause_button.update()?;
     }
     self.update_stats_menu(ctx)?;
     self.update_final_stats_menu()?;
+    self.hot_reload_level(ctx)?;
+    self.update_dialogue(ctx)?;
     self.update_level(ctx)?;
+    if let Some(speedrun) = &mut self.speedrun {
+      speedrun.update(ctx, &self.window_size)?;
+    }
     self.dt.update();
     Ok(())
   }
 
+  // Opens the queued dialogue scripts one after another.
+  // A script that can't be loaded is skipped, so it doesn't keep the level from being played.
+  fn update_dialogue(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if self.dialogue.as_ref().map( |dialogue| dialogue.is_finished() ).unwrap_or(false) {
+      self.dialogue = None;
+      if let Some(level) = &mut self.level {
+        level.reset_dt(&self.dt);
+      }
+    }
+    while self.dialogue.is_none() && !self.dialogue_queue.is_empty() {
+      let script = self.dialogue_queue.remove(0);
+      match Dialogue::new(ctx, &self.window_size, &script) {
+        Ok(dialogue) => self.dialogue = Some(dialogue),
+        Err(e)       => eprintln!("Couldn't start dialogue: {}", e),
+      }
+    }
+    if let Some(dialogue) = &mut self.dialogue {
+      dialogue.apply_clicked(ctx)?;
+      dialogue.update()?;
+    }
+    Ok(())
+  }
+
   fn update_level(&mut self, ctx: &mut Context) -> GameResult<()> {
-    if self.to_title || self.paused || self.stats_menu.is_some() || self.final_stats_menu.is_some() {
+    if self.to_title || self.paused || self.dialogue.is_some() || self.stats_menu.is_some() || self.final_stats_menu.is_some() {
       return Ok(());
     }
     let mut next_level = false;
+    let mut saw_hints  = false;
     let highscore_opt = self.highscore().map( |s| s.clone() );
+    let level_index_opt = self.get_current_level_index();
+    let level_name_opt  = level_index_opt.and_then( |i| self.level_names.get(i) ).cloned();
+    let ghost_filepath_opt = level_index_opt.and_then( |i| self.ghost_filepath(i) );
+    if let Some(background) = &mut self.background {
+      background.update()?;
+    }
     if let Some(level) = &mut self.level {
       level.update(ctx, &self.dt)?;
+      if let Some(speedrun) = &mut self.speedrun {
+        speedrun.tick();
+      }
+      if let Some(level_name) = &level_name_opt {
+        self.lifetime_stats.tick(&self.pack.id, level_name);
+        self.achievements.tick();
+        let events = level.drain_stats_events();
+        for &event in &events {
+          self.lifetime_stats.record(&self.pack.id, level_name, event);
+          self.achievements.record(event);
+        }
+        if !events.is_empty() {
+          self.achievements.check_lifetime(&self.lifetime_stats.total());
+        }
+      }
+      self.sfx.play_all(ctx, level.drain_sounds())?;
+      self.dialogue_queue.extend(level.drain_dialogue());
+      let seen_hints = level.drain_seen_hints();
+      if !seen_hints.is_empty() {
+        self.seen_hints.extend(seen_hints);
+        saw_hints = true;
+      }
       if level.next_level {
         level.next_level = false;
         next_level = true;
+        let (leaderboard, new_entry) = if let Some(level_name) = &level_name_opt {
+          self.lifetime_stats.record(&self.pack.id, level_name, StatsEvent::Cleared);
+          self.achievements.record(StatsEvent::Cleared);
+          self.achievements.check_lifetime(&self.lifetime_stats.total());
+          let entry = LeaderboardEntry::new(level.score(), &self.profile_name);
+          let new_entry = self.leaderboards.insert(&self.pack.id, level_name, entry);
+          if new_entry.is_some() {
+            if let Err(e) = self.leaderboards.save() {
+              eprintln!("Couldn't save leaderboards: {}", e);
+            }
+          }
+          (self.leaderboards.entries(&self.pack.id, level_name), new_entry)
+        } else { (Vec::new(), None) };
+        // The first clear and every new highscore replace the level's ghost
+        if let Some(filepath) = &ghost_filepath_opt {
+          let is_best = highscore_opt.as_ref().map( |highscore| level.score() > highscore ).unwrap_or(true);
+          if is_best || !filepath.exists() {
+            if let Err(e) = level.recording().save(filepath) {
+              eprintln!("Couldn't save ghost {}: {}", filepath.display(), e);
+            }
+          }
+        }
         self.stats_menu = Some(StatsMenu::new(
             ctx,
             self.window_size.clone(),
             level.score().clone(),
             highscore_opt.map( |s| s.clone() ),
+            &leaderboard,
+            new_entry,
             false
         )?);
+        if let (Some(speedrun), Some(level_index)) = (&mut self.speedrun, level_index_opt) {
+          speedrun.split(ctx, level_index, &self.window_size)?;
+        }
       }
     }
     if next_level {
       // NOTE: insert_level_score() THEN save()
       self.insert_level_score();
       self.save();
+    } else if saw_hints || self.achievements.has_new_unlocked() {
+      self.save_in_level();
+    }
+    Ok(())
+  }
+
+  // Rebuild the current level from disk when its file changed, keeping the camera position.
+  // A malformed file keeps the old level running and displays the error instead.
+  fn hot_reload_level(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let filepath = match &mut self.level_watcher {
+      Some(watcher) => if watcher.changed() {
+        watcher.filepath().to_path_buf()
+      } else { return Ok(()); },
+      None => return Ok(()),
+    };
+    let level_index = if let Some(index) = self.get_current_level_index() {
+      index
+    } else { return Ok(()); };
+    match Level::new(ctx, self.window_size.clone(), &filepath, level_index) {
+      Ok(mut level) => {
+        // The start dialogue isn't replayed for every edit
+        level.drain_dialogue();
+        level.skip_seen_hints(&self.seen_hints);
+        level.set_ghost(self.load_ghost(ctx, level_index));
+        if let Some(curr_level) = &self.level {
+          level.camera_mut().move_to(curr_level.camera().point());
+          level.keep_played_triggers(curr_level);
+        }
+        level.reset_dt(&self.dt);
+        self.level        = Some(level);
+        self.background   = Some(self.new_background(ctx, level_index));
+        self.reload_error = None;
+      }
+      Err(e) => {
+        self.reload_error = Some(new_reload_error_text(ctx, &self.reload_font, &self.window_size, &e)?);
+      }
     }
     Ok(())
   }
 
+  fn new_background(&self, ctx: &mut Context, level_index: usize) -> Background {
+    let null = JsonValue::Null;
+    let data = self.level.as_ref().map( |level| level.background_data() ).unwrap_or(&null);
+    Background::new(ctx, &self.window_size, data, level_index)
+  }
+
   fn highscore(&self) -> Option<&Score> {
     if let Some(level_index) = self.get_current_level_index() {
       self.scores.get(&level_index)
@@ -396,6 +836,12 @@ impl LevelManager {
         ButtonType::PauseReset => {
           self.reset_level(ctx)?;
         }
+        ButtonType::PauseControls => {
+          self.controls_menu = Some(ControlsMenu::new(ctx, self.window_size.clone())?);
+        }
+        ButtonType::PauseOptions => {
+          self.options_menu = Some(OptionsMenu::new(ctx, self.window_size.clone())?);
+        }
         _ => ()
       }
     }
@@ -404,19 +850,60 @@ impl LevelManager {
     Ok(())
   }
 
+  fn update_controls_menu(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let mut close = false;
+    if let Some(controls_menu) = &mut self.controls_menu {
+      if let Some(clicked) = controls_menu.get_clicked().clone() {
+        match clicked {
+          ButtonType::ControlsBack       => close = true,
+          ButtonType::ControlsBinding(i) => if let Some(&action) = Action::all().get(i) {
+            controls_menu.wait_for_key(ctx, action)?;
+          },
+          ButtonType::ControlsDefaults   => controls_menu.reset_defaults(ctx)?,
+          _                              => ()
+        }
+      }
+      controls_menu.update()?;
+    }
+    if close {
+      self.controls_menu = None;
+    }
+    Ok(())
+  }
+
+  fn update_options_menu(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let mut close = false;
+    if let Some(options_menu) = &mut self.options_menu {
+      if let Some(ButtonType::OptionsBack) = options_menu.get_clicked() {
+        close = true;
+      }
+      options_menu.apply_clicked(ctx)?;
+      options_menu.update()?;
+    }
+    if close {
+      self.options_menu = None;
+    }
+    Ok(())
+  }
+
   fn update_stats_menu(&mut self, ctx: &mut Context) -> GameResult<()> {
     let mut next_level = false;
     let mut reset      = false;
     let mut to_title   = false;
+    let mut toggle     = false;
     if let Some(stats_menu) = &mut self.stats_menu {
       if let Some(clicked) = stats_menu.get_clicked() {
         match clicked {
-          ButtonType::StatsNext    => next_level = true,
-          ButtonType::StatsReset   => reset      = true,
-          ButtonType::StatsToTitle => to_title   = true,
+          ButtonType::StatsNext        => next_level = true,
+          ButtonType::StatsReset       => reset      = true,
+          ButtonType::StatsToTitle     => to_title   = true,
+          ButtonType::StatsLeaderboard => toggle     = true,
           _ => ()
         }
       }
+      if toggle {
+        stats_menu.toggle_leaderboard();
+      }
       stats_menu.update()?;
     }
     if next_level {
@@ -455,13 +942,17 @@ impl LevelManager {
   fn reset(&mut self) {
     self.level_index      = 0;
     self.level            = None;
-    self.song             = None;
+    self.level_watcher    = None;
+    self.reload_error     = None;
     self.background       = None;
     //self.scores           = HashMap::new();
     self.paused           = false;
     self.stats_menu       = None;
     self.final_stats_menu = None;
     self.to_title         = false;
+    self.speedrun         = None;
+    self.close_submenus();
+    self.close_dialogue();
   }
 
   fn to_title(&mut self, params: ToTitleParams) {
@@ -471,13 +962,17 @@ impl LevelManager {
     if let Some(level) = &mut self.level {
       level.next_level = false;
     }
-    if let Some(song) = &mut self.song {
-      song.stop();
-    }
     self.stats_menu       = None;
     self.final_stats_menu = None;
     self.paused           = false;
     self.to_title         = true;
+    // Leaving the level pack gives up the run
+    self.speedrun         = None;
+    self.achievements.end_run();
+    self.close_submenus();
+    self.close_dialogue();
+    // Keeps the lifetime stats of the level that was left
+    self.save();
     if params.to_thank_you {
       self.to_thank_you = true;
     }
@@ -485,7 +980,11 @@ impl LevelManager {
 
   pub fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.draw_level(ctx)?;
-    if self.paused {
+    if let Some(controls_menu) = &mut self.controls_menu {
+      controls_menu.draw(ctx)?;
+    } else if let Some(options_menu) = &mut self.options_menu {
+      options_menu.draw(ctx)?;
+    } else if self.paused {
       self.pause_menu.draw(ctx)?;
     }
     if self.level.is_some() {
@@ -498,12 +997,24 @@ impl LevelManager {
     } else if let Some(highscore) = &self.highscore_text {
       highscore.draw(ctx)?;
     }
+    if let Some(speedrun) = &self.speedrun {
+      speedrun.draw(ctx)?;
+    }
+    if let Some(dialogue) = &mut self.dialogue {
+      dialogue.draw(ctx)?;
+    }
+    if let Some(reload_error) = &self.reload_error {
+      reload_error.draw(ctx)?;
+    }
     Ok(())
   }
 
   fn draw_level(&mut self, ctx: &mut Context) -> GameResult<()> {
     if let Some(bg) = &self.background {
-      bg.draw(ctx, &Point::new(0.0, 0.0), &self.window_size, &Facing::Right)?;
+      let camera = self.level.as_ref()
+        .map( |level| level.camera().top_left() )
+        .unwrap_or(Point::new(0.0, 0.0));
+      bg.draw(ctx, &camera)?;
     }
     if let Some(level) = &mut self.level {
       level.draw(ctx)?;
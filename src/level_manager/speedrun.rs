@@ -0,0 +1,111 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+
+use settings::res;
+use settings::level_manager::speedrun::*;
+use score::{ TickType, format_ticks, format_ticks_delta };
+use menu::stats::prelude::*;
+use layout::Layout;
+
+// A run through a whole level pack, started from its first level while the speedrun timer is on.
+// The run's timer counts the updates of every level, including the attempts before a reset,
+// so it stands still whenever the level does: while paused, in dialogue and on the stats menu.
+// Every cleared level adds a split, the run's time at that point,
+// which is compared to the same split of the personal best run.
+pub struct Speedrun {
+  ticks:       TickType,
+  splits:      Vec<TickType>,
+  best_splits: Vec<TickType>,
+  font:        Font,
+  split_font:  Font,
+  shown:       Option<String>,  // The time the text was last made for
+  timer_text:  Option<StatsText>,
+  split_text:  Option<StatsText>,
+}
+
+impl Speedrun {
+  pub fn new(ctx: &mut Context, best_splits: Vec<TickType>) -> GameResult<Self> {
+    Ok(Self {
+      ticks:       0,
+      splits:      Vec::new(),
+      best_splits,
+      font:        Font::new(ctx, res::fonts::DEFAULT, FONT_SIZE)?,
+      split_font:  Font::new(ctx, res::fonts::DEFAULT, SPLIT_FONT_SIZE)?,
+      shown:       None,
+      timer_text:  None,
+      split_text:  None,
+    })
+  }
+
+  pub fn tick(&mut self) {
+    self.ticks += 1;
+  }
+
+  // Clearing a level again (after resetting from the stats menu) replaces its split
+  pub fn split(&mut self, ctx: &mut Context, level_index: usize, window_size: &Size) -> GameResult<()> {
+    self.splits.truncate(level_index);
+    self.splits.push(self.ticks);
+    let best  = self.best_splits.get(level_index).cloned();
+    let label = format!("Split {}: {}", level_index + 1, format_ticks(self.ticks));
+    let (text, color) = match best {
+      Some(best) => (
+        format!("{} ({})", label, format_ticks_delta(self.ticks, best)),
+        if self.ticks <= best { AHEAD_COLOR } else { BEHIND_COLOR }
+      ),
+      None => (label, FONT_COLOR),
+    };
+    self.split_text = Some(StatsText::new(
+      Text::new(ctx, &text, &self.split_font)?,
+      Layout::new(window_size).at(Origin::TopRight, -PADDING, OFFSET_Y + FONT_SIZE as NumType + PADDING),
+      TextOrigin::Right,
+      Some(color)
+    ));
+    Ok(())
+  }
+
+  // A run that cleared all `level_count` levels faster than the personal best
+  pub fn is_personal_best(&self, level_count: usize) -> bool {
+    self.splits.len() == level_count && match self.best_splits.last() {
+      Some(&best) => self.best_splits.len() == level_count && self.ticks < best,
+      None        => true,
+    }
+  }
+
+  pub fn splits(&self) -> &Vec<TickType> {
+    &self.splits
+  }
+
+  pub fn update(&mut self, ctx: &mut Context, window_size: &Size) -> GameResult<()> {
+    let time = format_ticks(self.ticks);
+    if self.shown.as_ref() == Some(&time) {
+      return Ok(());
+    }
+    self.timer_text = Some(StatsText::new(
+      Text::new(ctx, &time, &self.font)?,
+      Layout::new(window_size).at(Origin::TopRight, -PADDING, OFFSET_Y),
+      TextOrigin::Right,
+      Some(FONT_COLOR)
+    ));
+    self.shown = Some(time);
+    Ok(())
+  }
+
+  pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(timer_text) = &self.timer_text {
+      timer_text.draw(ctx)?;
+    }
+    if let Some(split_text) = &self.split_text {
+      split_text.draw(ctx)?;
+    }
+    Ok(())
+  }
+}
+
+pub fn splits_from_json(json: &JsonValue) -> Vec<TickType> {
+  json.members().filter_map( |split| split.as_u32() ).collect()  // as TickType
+}
@@ -4,20 +4,37 @@ extern crate json;
 extern crate ggez;
 extern crate noframe;
 
+mod achievements;
+mod action;
 mod animation;
+mod background;
 mod color_rect;
+mod config;
+mod decoration;
+mod dialogue;
+mod editor;
 mod frames_counter;
 mod game;
+mod gamepad;
+mod ghost;
 mod gravity;
+mod hints;
 mod id_generator;
 mod text_box;
 mod interactables;
+mod json_file;
+mod layout;
 mod level;
 mod level_manager;
 mod menu;
+mod music;
+mod particles;
 mod persons;
+mod popups;
 mod score;
 mod settings;
+mod sfx;
+mod viewport;
 mod wall;
 
 use std::env;
@@ -26,24 +43,28 @@ use std::path;
 use ggez::{
   GameResult,
   graphics,
-  event
+  event,
+  filesystem::Filesystem,
 };
 
 use settings::meta::*;
 use settings::game::*;
+use config::Config;
 use game::GameState;
 
 pub fn run() -> GameResult<()> {
+  let config = Config::load(Filesystem::new(NAME, AUTHORS)?.get_user_config_dir());
+  let window_size = config.window_size.clone();
+
   let mut ctx = ggez::ContextBuilder::new(
     NAME, AUTHORS
   ).window_setup(
-    ggez::conf::WindowSetup::default().title(WINDOW_TITLE)
+    ggez::conf::WindowSetup::default().title(WINDOW_TITLE).resizable(config.resizable)
   ).window_mode(
-    ggez::conf::WindowMode::default().dimensions(
-      WINDOW_SIZE.w as u32,
-      WINDOW_SIZE.h as u32,
-      )
+    config.window_mode()
   ).build()?;
+  config.apply_window_mode(&mut ctx)?;
+  config::set(config);
 
   if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
     let mut path = path::PathBuf::from(manifest_dir);
@@ -52,7 +73,7 @@ pub fn run() -> GameResult<()> {
   }
 
   graphics::set_background_color(&mut ctx, BG_COLOR.into());
-  let mut state = GameState::new(&mut ctx, WINDOW_SIZE)?;
+  let mut state = GameState::new(&mut ctx, window_size)?;
   state.init(&mut ctx)?;
   return event::run(&mut ctx, &mut state);
 }
@@ -5,11 +5,40 @@ pub enum ButtonType {
   // TITLE
   TitleStart,
   TitleLevelSelect,
+  TitleLevelPacks,
+  TitleProfiles,
+  TitleOptions,
+  TitleStatistics,
+  TitleAchievements,
   TitleQuit,
 
   // TITLE/LEVEL_SELECT
   LevelSelectBack,
   LevelSelectLevel(usize),
+  LevelSelectBoard(usize),
+  LevelSelectClose,
+
+  // TITLE/LEVEL_PACKS
+  LevelPacksBack,
+  LevelPacksPack(usize),
+
+  // TITLE/PROFILES
+  ProfilesBack,
+  ProfilesProfile(usize),
+  ProfilesNew,
+  ProfilesRename,
+  ProfilesCopy,
+  ProfilesDelete,
+
+  // TITLE/STATISTICS
+  StatisticsBack,
+  StatisticsPrev,
+  StatisticsNext,
+
+  // TITLE/ACHIEVEMENTS
+  AchievementsBack,
+  AchievementsPrev,
+  AchievementsNext,
 
   // TITLE/THANK_YOU
   ThankYouBack,
@@ -28,12 +57,37 @@ pub enum ButtonType {
   PauseResume,
   PauseToTitle,
   PauseReset,
+  PauseControls,
+  PauseOptions,
+
+  // OPTIONS (TITLE and PAUSE)
+  OptionsBack,
+  OptionsMusicDown,
+  OptionsMusicUp,
+  OptionsMusicSlider,
+  OptionsSfxDown,
+  OptionsSfxUp,
+  OptionsSfxSlider,
+  OptionsFullscreen,
+  OptionsWindowScale,
+  OptionsCameraFollow,
+  OptionsSpeedrun,
+  OptionsGhosts,
+
+  // PAUSE/CONTROLS
+  ControlsBack,
+  ControlsBinding(usize),
+  ControlsDefaults,
 
   // STATS
   StatsNext,
   StatsReset,
   StatsToTitle,
   StatsToThankYou,
+  StatsLeaderboard,
+
+  // DIALOGUE
+  DialogueChoice(usize),
 }
 
 impl fmt::Display for ButtonType {
@@ -42,10 +96,35 @@ impl fmt::Display for ButtonType {
     write!(f, "{}", match self {
       TitleStart          => String::from("TitleStart"),
       TitleLevelSelect    => String::from("TitleLevelSelect"),
+      TitleLevelPacks     => String::from("TitleLevelPacks"),
+      TitleProfiles       => String::from("TitleProfiles"),
+      TitleOptions        => String::from("TitleOptions"),
+      TitleStatistics     => String::from("TitleStatistics"),
+      TitleAchievements   => String::from("TitleAchievements"),
       TitleQuit           => String::from("TitleQuit"),
 
       LevelSelectBack     => String::from("LevelSelectBack"),
       LevelSelectLevel(n) => format!("LevelSelectLevel #{}", n),
+      LevelSelectBoard(n) => format!("LevelSelectBoard #{}", n),
+      LevelSelectClose    => String::from("LevelSelectClose"),
+
+      LevelPacksBack      => String::from("LevelPacksBack"),
+      LevelPacksPack(n)   => format!("LevelPacksPack #{}", n),
+
+      ProfilesBack        => String::from("ProfilesBack"),
+      ProfilesProfile(n)  => format!("ProfilesProfile #{}", n),
+      ProfilesNew         => String::from("ProfilesNew"),
+      ProfilesRename      => String::from("ProfilesRename"),
+      ProfilesCopy        => String::from("ProfilesCopy"),
+      ProfilesDelete      => String::from("ProfilesDelete"),
+
+      StatisticsBack      => String::from("StatisticsBack"),
+      StatisticsPrev      => String::from("StatisticsPrev"),
+      StatisticsNext      => String::from("StatisticsNext"),
+
+      AchievementsBack    => String::from("AchievementsBack"),
+      AchievementsPrev    => String::from("AchievementsPrev"),
+      AchievementsNext    => String::from("AchievementsNext"),
 
       ThankYouBack        => String::from("ThankYouBack"),
 
@@ -61,11 +140,33 @@ impl fmt::Display for ButtonType {
       PauseResume         => String::from("PauseResume"),
       PauseToTitle        => String::from("PauseToTitle"),
       PauseReset          => String::from("PauseReset"),
+      PauseControls       => String::from("PauseControls"),
+      PauseOptions        => String::from("PauseOptions"),
+
+      OptionsBack         => String::from("OptionsBack"),
+      OptionsMusicDown    => String::from("OptionsMusicDown"),
+      OptionsMusicUp      => String::from("OptionsMusicUp"),
+      OptionsMusicSlider  => String::from("OptionsMusicSlider"),
+      OptionsSfxDown      => String::from("OptionsSfxDown"),
+      OptionsSfxUp        => String::from("OptionsSfxUp"),
+      OptionsSfxSlider    => String::from("OptionsSfxSlider"),
+      OptionsFullscreen   => String::from("OptionsFullscreen"),
+      OptionsWindowScale  => String::from("OptionsWindowScale"),
+      OptionsCameraFollow => String::from("OptionsCameraFollow"),
+      OptionsSpeedrun     => String::from("OptionsSpeedrun"),
+      OptionsGhosts       => String::from("OptionsGhosts"),
+
+      ControlsBack        => String::from("ControlsBack"),
+      ControlsBinding(n)  => format!("ControlsBinding #{}", n),
+      ControlsDefaults    => String::from("ControlsDefaults"),
 
       StatsNext           => String::from("StatsNext"),
       StatsReset          => String::from("StatsReset"),
       StatsToTitle        => String::from("StatsToTitle"),
       StatsToThankYou     => String::from("StatsToThankYou"),
+      StatsLeaderboard    => String::from("StatsLeaderboard"),
+
+      DialogueChoice(n)   => format!("DialogueChoice #{}", n),
     })
   }
 }
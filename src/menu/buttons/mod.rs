@@ -15,8 +15,11 @@ use ggez::{
   Context,
 };
 use noframe::geo::prelude::*;
+use noframe::entity::Entity;
 
 use self::button_text::prelude::*;
+use settings::gamepad::FOCUS_COLOR;
+use color_rect::ColorRect;
 use animation::Animation;
 use animation::Facing;
 
@@ -28,6 +31,7 @@ pub struct Button {
   pub button_type: ButtonType,
   facing:          Facing,
   text:            Option<ButtonText>,
+  focused:         bool,
 }
 
 impl Button {
@@ -48,6 +52,7 @@ impl Button {
       button_type,
       facing,
       text,
+      focused: false,
     }
   }
 
@@ -67,6 +72,18 @@ impl Button {
     unimplemented!();  // TODO
   }
 
+  pub fn focus(&mut self) {
+    self.focused = true;
+  }
+
+  pub fn unfocus(&mut self) {
+    self.focused = false;
+  }
+
+  pub fn is_focused(&self) -> bool {
+    self.focused
+  }
+
   pub fn update(&mut self) -> GameResult<()> {
     self.animation_mut().update()
   }
@@ -79,6 +96,9 @@ impl Button {
     if let Some(text) = &mut self.text {
       text.draw(ctx)?;
     }
+    if self.focused {
+      ColorRect::new(top_left, size, Origin::TopLeft, FOCUS_COLOR).draw(ctx)?;
+    }
     Ok(())
   }
 }
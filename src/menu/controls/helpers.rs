@@ -0,0 +1,122 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+use noframe::geo::mask::misc::Side;
+
+use settings::res::fonts;
+use settings::buttons;
+use settings::menus::controls::*;
+use action::Action;
+use config::Bindings;
+use menu::buttons::prelude::*;
+use color_rect::prelude::*;
+use text_box::prelude::*;
+
+pub fn new_color_rect(window_size: &Size) -> ColorRect {
+  ColorRectBuilder::new()
+    .size(window_size.clone())
+    .color([0.0, 0.0, 0.0, 0.9])
+    .build()
+}
+
+fn buttons_area(window_size: &Size) -> Rect {
+  let border_padding = Point::new(64.0, 32.0);
+  Rect::new(
+    Point::new(border_padding.x, border_padding.y),
+    Size::new(
+      window_size.w - border_padding.x * 2.0,
+      window_size.h - border_padding.y * 2.0
+    ),
+    Origin::TopLeft
+  )
+}
+
+pub fn new_title(ctx: &mut Context, window_size: &Size, waiting: Option<Action>) -> GameResult<TextBox> {
+  let font = Font::new(ctx, fonts::DEFAULT, TITLE_FONT_SIZE)?;
+  let title = match waiting {
+    Some(action) => format!("Press a key for {} (Escape to cancel)", action),
+    None         => String::from("Controls"),
+  };
+  let text = Text::new(ctx, &title, &font)?;
+  let area = buttons_area(window_size);
+  Ok(TextBoxBuilder::new()
+     .point(Point::new(area.center().x, area.side(Side::Top)))
+     .size_from(text.width() as NumType + 64.0, 64.0)
+     .origin(Origin::TopCenter)
+     .bg_color(TITLE_BG_COLOR)
+     .text(text)
+     .text_color(TITLE_FONT_COLOR)
+     .text_origin(TextOrigin::Center)
+     .build())
+}
+
+pub fn new_buttons(ctx: &mut Context, window_size: &Size, bindings: &Bindings, waiting: Option<Action>) -> GameResult<Vec<Button>> {
+  let padding      = 16.0;
+  let columns      = 2;
+  let area         = buttons_area(window_size);
+  let size         = Size::new(area.size().w / 2.0 - padding * 2.0, 48.0);
+  let top          = area.side(Side::Top) + 64.0 + padding * 2.0;
+  let text_offset  = Point::new(-size.w / 2.0 + 24.0, 0.0);
+  let font         = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let actions      = Action::all();
+  let rows         = (actions.len() + columns - 1) / columns;
+  let mut buttons  = Vec::new();
+
+  for (i, &action) in actions.iter().enumerate() {
+    let column = i / rows;
+    let row    = i % rows;
+    let center = Point::new(
+      area.center().x + (size.w / 2.0 + padding) * if column == 0 { -1.0 } else { 1.0 },
+      top + size.h / 2.0 + (size.h + padding) * row as NumType
+    );
+    let label = if Some(action) == waiting {
+      format!("{}: ...", action)
+    } else {
+      let key = bindings.key(action)
+        .map( |key| key.name() )
+        .unwrap_or(String::from("-"));
+      format!("{}: {}", action, key)
+    };
+    let text = Text::new(ctx, &label, &font)?;
+    buttons.push(ButtonBuilder::new(ctx)
+                 .point(center.clone())
+                 .size(size.clone())
+                 .origin(Origin::Center)
+                 .button_type(ButtonType::ControlsBinding(i))
+                 .animation_from(vec![::join_str(buttons::IMAGES, &format!("level_select{}.png", i % 3 + 1))], vec![1000])
+                 .text_from(
+                   center + text_offset.clone(),
+                   Size::new(text.width() as NumType, text.height() as NumType),
+                   Origin::CenterLeft,
+                   text
+                 ).build()?);
+  }
+
+  let defaults_point = Point::new(area.center().x, area.side(Side::Bottom));
+  let text = Text::new(ctx, "Defaults", &font)?;
+  buttons.push(ButtonBuilder::new(ctx)
+               .point(defaults_point.clone())
+               .size_from(192.0, 48.0)
+               .origin(Origin::BottomCenter)
+               .button_type(ButtonType::ControlsDefaults)
+               .animation_from(vec![::join_str(buttons::IMAGES, "level_select1.png")], vec![1000])
+               .text_from(
+                 defaults_point - Point::new(0.0, 24.0),
+                 Size::new(text.width() as NumType, text.height() as NumType),
+                 Origin::Center,
+                 text
+               ).build()?);
+
+  buttons.push(ButtonBuilder::new(ctx)
+               .point(area.bottom_left())
+               .size(Size::new(64.0, 64.0))
+               .origin(Origin::BottomLeft)
+               .button_type(ButtonType::ControlsBack)
+               .animation_from(vec![::join_str(buttons::IMAGES, "arrow.png")], vec![1000])
+               .build()?);
+
+  Ok(buttons)
+}
@@ -0,0 +1,127 @@
+pub mod prelude {
+  pub use super::ControlsMenu;
+  pub use super::super::Menu;
+}
+
+mod helpers;
+
+use ggez::{
+  Context,
+  GameResult,
+  event::Keycode,
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use self::helpers::*;
+use super::prelude::*;
+use action::Action;
+use config::{ self, Bindings };
+use color_rect::ColorRect;
+use text_box::TextBox;
+
+// Lists every action with its key. After clicking an action, the next key pressed is bound to it.
+pub struct ControlsMenu {
+  window_size: Size,
+  buttons:     Vec<Button>,
+  clicked:     Option<ButtonType>,
+  title:       TextBox,
+  rect:        ColorRect,
+  waiting:     Option<Action>,
+}
+
+impl ControlsMenu {
+  pub fn new(ctx: &mut Context, window_size: Size) -> GameResult<Self> {
+    Ok(Self {
+      buttons:     config::with( |c| new_buttons(ctx, &window_size, &c.bindings, None) )?,
+      clicked:     None,
+      title:       new_title(ctx, &window_size, None)?,
+      rect:        new_color_rect(&window_size),
+      waiting:     None,
+      window_size,
+    })
+  }
+
+  pub fn is_waiting(&self) -> bool {
+    self.waiting.is_some()
+  }
+
+  pub fn wait_for_key(&mut self, ctx: &mut Context, action: Action) -> GameResult<()> {
+    self.waiting = Some(action);
+    self.rebuild(ctx)
+  }
+
+  pub fn cancel(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.waiting = None;
+    self.rebuild(ctx)
+  }
+
+  pub fn bind(&mut self, ctx: &mut Context, keycode: Keycode) -> GameResult<()> {
+    if let Some(action) = self.waiting.take() {
+      let mut config = config::get();
+      config.bindings.bind(action, keycode);
+      save_config(config);
+    }
+    self.rebuild(ctx)
+  }
+
+  pub fn reset_defaults(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let mut config = config::get();
+    config.bindings = Bindings::default();
+    save_config(config);
+    self.waiting = None;
+    self.rebuild(ctx)
+  }
+
+  fn rebuild(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.buttons = config::with( |c| new_buttons(ctx, &self.window_size, &c.bindings, self.waiting) )?;
+    self.title   = new_title(ctx, &self.window_size, self.waiting)?;
+    Ok(())
+  }
+}
+
+// The new bindings are used even if they couldn't be written to the config file.
+fn save_config(config: config::Config) {
+  if let Err(e) = config.save() {
+    eprintln!("Couldn't save key bindings: {}", e);
+  }
+  config::set(config);
+}
+
+impl Mask for ControlsMenu {
+  fn point(&self)         -> &Point     { self.rect.point()     }
+  fn point_mut(&mut self) -> &mut Point { self.rect.point_mut() }
+  fn size(&self)          -> &Size      { self.rect.size()      }
+  fn origin(&self)        -> &Origin    { self.rect.origin()    }
+}
+
+impl Menu for ControlsMenu {
+  fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.rect.draw(ctx)?;
+    self.title.draw(ctx)?;
+    self.draw_menu(ctx)?;
+    Ok(())
+  }
+
+  fn buttons(&self) -> Vec<&Button> {
+    self.buttons.iter().collect()
+  }
+  fn buttons_mut(&mut self) -> Vec<&mut Button> {
+    self.buttons.iter_mut().collect()
+  }
+  fn animation(&self) -> Option<&Animation> {
+    None
+  }
+  fn animation_mut(&mut self) -> Option<&mut Animation> {
+    None
+  }
+  fn clicked(&mut self, btn_type: ButtonType) {
+    self.clicked = Some(btn_type);
+  }
+  fn get_clicked(&self) -> &Option<ButtonType> {
+    &self.clicked
+  }
+  fn clear_clicked(&mut self) {
+    self.clicked = None;
+  }
+}
@@ -0,0 +1,107 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use settings::res::fonts;
+use settings::leaderboards::*;
+use score::format_ticks;
+use score::leaderboards::LeaderboardEntry;
+use menu::stats::prelude::{ StatsText, TextOrigin };
+use color_rect::ColorRect;
+use layout::Layout;
+
+// Where the rank, profile, score, moves and time columns start, as parts of the table's width.
+// The date is aligned to the right edge.
+const COLUMNS: [f32; 5] = [0.0, 0.07, 0.42, 0.56, 0.7];
+
+// A level's leaderboard as a table, drawn in the given area.
+pub struct LeaderboardTable {
+  texts:     Vec<StatsText>,
+  highlight: Option<ColorRect>,
+}
+
+impl LeaderboardTable {
+  // `highlight` is the rank of the entry to mark, e.g. the run that was just played
+  pub fn new(
+    ctx: &mut Context,
+    title: &str,
+    entries: &Vec<LeaderboardEntry>,
+    highlight: Option<usize>,
+    point: &Point,
+    size: &Size
+  ) -> GameResult<Self> {
+    let font       = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+    let title_font = Font::new_px(ctx, fonts::DEFAULT, TITLE_FONT_SIZE)?;
+    let area       = Layout::from_rect(point.clone(), size).inset(PADDING, PADDING);
+    let left       = area.top_left();
+    let width      = area.size().w;
+    let title_h    = title_font.get_height() as NumType + ROW_HEIGHT * 0.5;
+    let mut texts  = Vec::new();
+
+    texts.push(StatsText::new(
+      Text::new(ctx, title, &title_font)?,
+      left.clone(),
+      TextOrigin::Left,
+      Some(FONT_COLOR)
+    ));
+    if entries.is_empty() {
+      texts.push(StatsText::new(
+        Text::new(ctx, "No runs yet.", &font)?,
+        left.clone() + Point::new(0.0, title_h),
+        TextOrigin::Left,
+        Some(HEADER_COLOR)
+      ));
+      return Ok(Self { texts, highlight: None });
+    }
+
+    let row_point = |row: usize, column: Option<usize>| left.clone() + Point::new(
+      column.map( |c| COLUMNS[c] * width ).unwrap_or(width),
+      title_h + ROW_HEIGHT * row as NumType
+    );
+    let header = ["#", "Profile", "Score", "Moves", "Time", "Date"];
+    let rows = entries.iter().enumerate().map( |(rank, entry)| vec![
+      format!("{}", rank + 1),
+      entry.profile.clone(),
+      format!("{}", entry.score),
+      format!("{}", entry.moves),
+      if entry.ticks > 0 { format_ticks(entry.ticks) } else { "-".to_string() },
+      entry.semantic_date(),
+    ]).collect::<Vec<Vec<String>>>();
+
+    let header_row = header.iter().map( |s| s.to_string() ).collect::<Vec<String>>();
+    for (row, cells) in Some(&header_row).into_iter().chain(rows.iter()).enumerate() {
+      let color = if row == 0 { HEADER_COLOR } else { FONT_COLOR };
+      for (column, cell) in cells.iter().enumerate() {
+        let (point, origin) = if column < COLUMNS.len() {
+          (row_point(row, Some(column)), TextOrigin::Left)
+        } else {
+          (row_point(row, None), TextOrigin::Right)
+        };
+        texts.push(StatsText::new(Text::new(ctx, cell, &font)?, point, origin, Some(color)));
+      }
+    }
+
+    let highlight = highlight.filter( |&rank| rank < entries.len() ).map( |rank| ColorRect::new(
+      row_point(rank + 1, Some(0)) + Point::new(-PADDING * 0.25, -(ROW_HEIGHT - FONT_SIZE as NumType) * 0.5),
+      Size::new(width + PADDING * 0.5, ROW_HEIGHT),
+      Origin::TopLeft,
+      HIGHLIGHT_COLOR
+    ));
+
+    Ok(Self { texts, highlight })
+  }
+
+  pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(highlight) = &self.highlight {
+      highlight.draw(ctx)?;
+    }
+    for text in &self.texts {
+      text.draw(ctx)?;
+    }
+    Ok(())
+  }
+}
@@ -1,5 +1,6 @@
 pub mod prelude {
   pub use super::Menu;
+  pub use super::MenuInput;
   pub use super::buttons::prelude::*;
   pub use animation::prelude::*;
 }
@@ -7,21 +8,35 @@ pub mod prelude {
 pub mod title_menu_manager;
 pub mod toolbox;
 pub mod pause;
+pub mod controls;
+pub mod options;
 pub mod stats;
+pub mod leaderboard;
 
 pub mod buttons;
 
+use std::cmp::Ordering;
+
 use ggez::{
   GameResult,
   Context,
 };
 use noframe::geo::prelude::*;
+use noframe::geo::mask::misc::Side;
 
 use self::buttons::Button;
 use self::buttons::ButtonType;
 use animation::Animation;
 use animation::Facing;
 
+// Menu navigation without the mouse
+#[derive(Debug)]
+pub enum MenuInput {
+  Move(Side),
+  Confirm,
+  Back,
+}
+
 pub trait Menu: Mask {
   fn buttons(&self) -> Vec<&Button>;
   fn buttons_mut(&mut self) -> Vec<&mut Button>;
@@ -52,6 +67,56 @@ pub trait Menu: Mask {
     }
   }
 
+  // Moving focuses the closest button in that direction, confirming clicks the focused button.
+  // Going back is up to the menu's owner.
+  fn menu_input(&mut self, input: MenuInput) {
+    match input {
+      MenuInput::Move(side) => self.move_focus(side),
+      MenuInput::Confirm    => self.click_focused(),
+      MenuInput::Back       => (),
+    }
+  }
+
+  fn move_focus(&mut self, side: Side) {
+    let mut buttons = self.buttons_mut();
+    if buttons.is_empty() { return; }
+    let focused = buttons.iter().position( |btn| btn.is_focused() );
+    let next = if let Some(i) = focused {
+      let from = buttons[i].center();
+      buttons.iter().enumerate()
+        .filter( |&(j, _)| j != i )
+        .filter_map( |(j, btn)| {
+          let to = btn.center();
+          let (main, cross) = match side {
+            Side::Top    => (from.y - to.y, to.x - from.x),
+            Side::Bottom => (to.y - from.y, to.x - from.x),
+            Side::Left   => (from.x - to.x, to.y - from.y),
+            Side::Right  => (to.x - from.x, to.y - from.y),
+          };
+          if main > 0.0 {
+            Some((j, main + cross.abs() * 2.0))
+          } else { None }
+        })
+        .min_by( |a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal) )
+        .map( |(j, _)| j )
+    } else { Some(0) };
+    if let Some(n) = next {
+      for btn in buttons.iter_mut() {
+        btn.unfocus();
+      }
+      buttons[n].focus();
+    }
+  }
+
+  fn click_focused(&mut self) {
+    let focused = self.buttons().iter()
+      .find( |btn| btn.is_focused() )
+      .map( |btn| btn.button_type.clone() );
+    if let Some(btn_type) = focused {
+      self.clicked(btn_type);
+    }
+  }
+
   fn buttons_intersecting_point(&self, point: Point) -> Vec<&Button> {
     let mut btns = Vec::new();
     for btn in self.buttons() {
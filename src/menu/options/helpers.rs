@@ -0,0 +1,146 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+
+use settings::res::fonts;
+use settings::buttons;
+use settings::menus::options::*;
+use config::Config;
+use menu::buttons::prelude::*;
+use animation::Facing;
+use color_rect::prelude::*;
+use text_box::prelude::*;
+use layout::Layout;
+
+const ROWS: usize = 7;
+
+pub fn new_color_rect(window_size: &Size) -> ColorRect {
+  ColorRectBuilder::new()
+    .size(window_size.clone())
+    .color([0.0, 0.0, 0.0, 0.9])
+    .build()
+}
+
+pub fn new_title(ctx: &mut Context, window_size: &Size) -> GameResult<TextBox> {
+  let font = Font::new(ctx, fonts::DEFAULT, TITLE_FONT_SIZE)?;
+  Ok(TextBoxBuilder::new()
+     .point(Point::new(window_size.center().x, rows_top(window_size) - 64.0))
+     .size_from(256.0, 96.0)
+     .origin(Origin::BottomCenter)
+     .bg_color(TITLE_BG_COLOR)
+     .text(Text::new(ctx, "Options", &font)?)
+     .text_color(TITLE_FONT_COLOR)
+     .text_origin(TextOrigin::Center)
+     .build())
+}
+
+fn row_size() -> Size {
+  Size::new(384.0, 64.0)
+}
+
+fn rows_top(window_size: &Size) -> NumType {
+  let padding = 16.0;
+  window_size.center().y - (row_size().h + padding) * ROWS as NumType / 2.0
+}
+
+fn row_center(window_size: &Size, row: usize) -> Point {
+  let padding = 16.0;
+  Point::new(
+    window_size.center().x,
+    rows_top(window_size) + row_size().h / 2.0 + (row_size().h + padding) * row as NumType
+  )
+}
+
+fn on_off(value: bool) -> &'static str {
+  if value { "On" } else { "Off" }
+}
+
+// Every option row, in order: two volume sliders with arrows on either side, then five toggles.
+pub fn new_buttons(ctx: &mut Context, window_size: &Size, config: &Config) -> GameResult<Vec<Button>> {
+  let padding    = 16.0;
+  let arrow_size = Size::new(64.0, 64.0);
+  let row_size   = row_size();
+  let wide_size  = Size::new(row_size.w + (arrow_size.w + padding) * 2.0, row_size.h);
+  let arrow_x    = row_size.w / 2.0 + padding + arrow_size.w / 2.0;
+  let font       = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let mut btns   = Vec::new();
+
+  let sliders = [
+    (format!("Music: {}%",         (config.volume     * 100.0).round()), ButtonType::OptionsMusicDown, ButtonType::OptionsMusicSlider, ButtonType::OptionsMusicUp),
+    (format!("Sound Effects: {}%", (config.sfx_volume * 100.0).round()), ButtonType::OptionsSfxDown,   ButtonType::OptionsSfxSlider,   ButtonType::OptionsSfxUp),
+  ];
+  for (row, (label, down, slider, up)) in sliders.iter().enumerate() {
+    let center = row_center(window_size, row);
+    btns.push(ButtonBuilder::new(ctx)
+              .point(center.clone() - Point::new(arrow_x, 0.0))
+              .size(arrow_size.clone())
+              .origin(Origin::Center)
+              .button_type(down.clone())
+              .animation_from(vec![::join_str(buttons::IMAGES, "child_arrow.png")], vec![1000])
+              .facing(Facing::Left)
+              .build()?);
+    btns.push(new_text_button(ctx, &font, center.clone(), row_size.clone(), slider.clone(), label)?);
+    btns.push(ButtonBuilder::new(ctx)
+              .point(center + Point::new(arrow_x, 0.0))
+              .size(arrow_size.clone())
+              .origin(Origin::Center)
+              .button_type(up.clone())
+              .animation_from(vec![::join_str(buttons::IMAGES, "child_arrow.png")], vec![1000])
+              .build()?);
+  }
+
+  let toggles = [
+    (format!("Fullscreen: {}",     on_off(config.fullscreen)),    ButtonType::OptionsFullscreen),
+    (format!("Window Scale: {}x",  config.window_scale),          ButtonType::OptionsWindowScale),
+    (format!("Camera Follow: {}",  on_off(config.camera_follow)), ButtonType::OptionsCameraFollow),
+    (format!("Speedrun Timer: {}", on_off(config.speedrun)),      ButtonType::OptionsSpeedrun),
+    (format!("Ghosts: {}",         on_off(config.ghosts)),        ButtonType::OptionsGhosts),
+  ];
+  for (i, (label, button_type)) in toggles.iter().enumerate() {
+    let center = row_center(window_size, sliders.len() + i);
+    btns.push(new_text_button(ctx, &font, center, wide_size.clone(), button_type.clone(), label)?);
+  }
+
+  let border_padding = Point::new(64.0, 32.0);
+  btns.push(ButtonBuilder::new(ctx)
+            .point(Layout::new(window_size).at(Origin::BottomLeft, border_padding.x, -border_padding.y))
+            .size(arrow_size)
+            .origin(Origin::BottomLeft)
+            .button_type(ButtonType::OptionsBack)
+            .animation_from(vec![::join_str(buttons::IMAGES, "arrow.png")], vec![1000])
+            .build()?);
+
+  Ok(btns)
+}
+
+fn new_text_button(ctx: &mut Context, font: &Font, center: Point, size: Size, button_type: ButtonType, label: &str) -> GameResult<Button> {
+  let text = Text::new(ctx, label, font)?;
+  ButtonBuilder::new(ctx)
+    .point(center.clone())
+    .size(size)
+    .origin(Origin::Center)
+    .button_type(button_type)
+    .animation_from(vec![::join_str(buttons::IMAGES, "level_select1.png")], vec![1000])
+    .text_from(
+      center,
+      Size::new(text.width() as NumType, text.height() as NumType),
+      Origin::Center,
+      text
+    ).build()
+}
+
+pub fn step_volume(volume: f32, step: f32) -> f32 {
+  // Round to the step, so repeated steps don't accumulate float errors
+  (((volume + step) / VOLUME_STEP).round() * VOLUME_STEP).max(0.0).min(1.0)
+}
+
+pub fn next_window_scale(scale: f32) -> f32 {
+  let index = WINDOW_SCALES.iter()
+    .position( |&s| (s - scale).abs() < 0.01 )
+    .map( |i| (i + 1) % WINDOW_SCALES.len() )
+    .unwrap_or(0);
+  WINDOW_SCALES[index]
+}
@@ -0,0 +1,174 @@
+pub mod prelude {
+  pub use super::OptionsMenu;
+  pub use super::super::Menu;
+}
+
+mod helpers;
+
+use ggez::{
+  Context,
+  GameResult,
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use self::helpers::*;
+use super::prelude::*;
+use settings::menus::options::*;
+use config;
+use color_rect::ColorRect;
+use text_box::TextBox;
+
+// Audio, display, camera, speedrun and ghost settings, opened from the title and the pause menu.
+// Every change is applied right away and saved to the config file.
+pub struct OptionsMenu {
+  window_size:  Size,
+  buttons:      Vec<Button>,
+  clicked:      Option<ButtonType>,
+  title:        TextBox,
+  rect:         ColorRect,
+  music_volume: f32,
+  sfx_volume:   f32,
+  slider_at:    Option<f32>,  // Where on the clicked slider the mouse was, from 0.0 to 1.0
+}
+
+impl OptionsMenu {
+  pub fn new(ctx: &mut Context, window_size: Size) -> GameResult<Self> {
+    let config = config::get();
+    Ok(Self {
+      buttons:      new_buttons(ctx, &window_size, &config)?,
+      clicked:      None,
+      title:        new_title(ctx, &window_size)?,
+      rect:         new_color_rect(&window_size),
+      music_volume: config.volume,
+      sfx_volume:   config.sfx_volume,
+      slider_at:    None,
+      window_size,
+    })
+  }
+
+  // Applies the clicked option; going back is up to the menu's owner.
+  pub fn apply_clicked(&mut self, ctx: &mut Context) -> GameResult<()> {
+    use self::ButtonType::*;
+    let clicked = if let Some(clicked) = self.clicked.clone() {
+      clicked
+    } else { return Ok(()); };
+    let slider_at = self.slider_at.take();
+    let mut config = config::get();
+    match clicked {
+      OptionsMusicDown    => config.volume     = step_volume(config.volume,     -VOLUME_STEP),
+      OptionsMusicUp      => config.volume     = step_volume(config.volume,      VOLUME_STEP),
+      OptionsSfxDown      => config.sfx_volume = step_volume(config.sfx_volume, -VOLUME_STEP),
+      OptionsSfxUp        => config.sfx_volume = step_volume(config.sfx_volume,  VOLUME_STEP),
+      // Confirming a slider with the gamepad has no position, the arrows are used instead
+      OptionsMusicSlider  => if let Some(at) = slider_at {
+        config.volume = step_volume(at, 0.0);
+      },
+      OptionsSfxSlider    => if let Some(at) = slider_at {
+        config.sfx_volume = step_volume(at, 0.0);
+      },
+      OptionsFullscreen   => {
+        config.fullscreen = !config.fullscreen;
+        config.apply_window_mode(ctx)?;
+      },
+      OptionsWindowScale  => {
+        config.window_scale = next_window_scale(config.window_scale);
+        config.apply_window_mode(ctx)?;
+      },
+      OptionsCameraFollow => config.camera_follow = !config.camera_follow,
+      OptionsSpeedrun     => config.speedrun      = !config.speedrun,
+      OptionsGhosts       => config.ghosts        = !config.ghosts,
+      _                   => return Ok(()),
+    }
+    save_config(config);
+    self.rebuild(ctx)
+  }
+
+  // Keeps the focused button focused, so the options can be changed repeatedly with the gamepad
+  fn rebuild(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let config  = config::get();
+    let focused = self.buttons.iter().position( |btn| btn.is_focused() );
+    self.buttons = new_buttons(ctx, &self.window_size, &config)?;
+    if let Some(btn) = focused.and_then( |i| self.buttons.get_mut(i) ) {
+      btn.focus();
+    }
+    self.music_volume = config.volume;
+    self.sfx_volume   = config.sfx_volume;
+    Ok(())
+  }
+
+  fn draw_sliders(&self, ctx: &mut Context) -> GameResult<()> {
+    for btn in &self.buttons {
+      let value = match btn.button_type {
+        ButtonType::OptionsMusicSlider => self.music_volume,
+        ButtonType::OptionsSfxSlider   => self.sfx_volume,
+        _                              => continue,
+      };
+      ColorRect::new(
+        btn.top_left(),
+        Size::new(btn.size().w * value, btn.size().h),
+        Origin::TopLeft,
+        SLIDER_COLOR
+      ).draw(ctx)?;
+    }
+    Ok(())
+  }
+}
+
+// The new options are used even if they couldn't be written to the config file.
+fn save_config(config: config::Config) {
+  if let Err(e) = config.save() {
+    eprintln!("Couldn't save options: {}", e);
+  }
+  config::set(config);
+}
+
+impl Mask for OptionsMenu {
+  fn point(&self)         -> &Point     { self.rect.point()     }
+  fn point_mut(&mut self) -> &mut Point { self.rect.point_mut() }
+  fn size(&self)          -> &Size      { self.rect.size()      }
+  fn origin(&self)        -> &Origin    { self.rect.origin()    }
+}
+
+impl Menu for OptionsMenu {
+  fn mouse_down(&mut self, x: i32, y: i32) {
+    let point = Point::new(x as NumType, y as NumType);
+    let clicked = self.buttons.iter()
+      .find( |btn| btn.intersects_point(&point) )
+      .map( |btn| (btn.button_type.clone(), (point.x - btn.top_left().x) / btn.size().w) );
+    if let Some((btn_type, at)) = clicked {
+      self.slider_at = Some(at.max(0.0).min(1.0));
+      self.clicked(btn_type);
+    }
+  }
+
+  fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.rect.draw(ctx)?;
+    self.title.draw(ctx)?;
+    self.draw_menu(ctx)?;
+    self.draw_sliders(ctx)?;
+    Ok(())
+  }
+
+  fn buttons(&self) -> Vec<&Button> {
+    self.buttons.iter().collect()
+  }
+  fn buttons_mut(&mut self) -> Vec<&mut Button> {
+    self.buttons.iter_mut().collect()
+  }
+  fn animation(&self) -> Option<&Animation> {
+    None
+  }
+  fn animation_mut(&mut self) -> Option<&mut Animation> {
+    None
+  }
+  fn clicked(&mut self, btn_type: ButtonType) {
+    self.clicked = Some(btn_type);
+  }
+  fn get_clicked(&self) -> &Option<ButtonType> {
+    &self.clicked
+  }
+  fn clear_clicked(&mut self) {
+    self.clicked = None;
+  }
+}
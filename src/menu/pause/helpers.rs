@@ -10,6 +10,7 @@ use settings::menus::pause::*;
 use menu::buttons::prelude::*;
 use color_rect::prelude::*;
 use text_box::prelude::*;
+use layout::Layout;
 
 pub fn new_color_rect(window_size: &Size) -> ColorRect {
   ColorRectBuilder::new()
@@ -19,40 +20,72 @@ pub fn new_color_rect(window_size: &Size) -> ColorRect {
 }
 
 pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> Vec<Button> {
+  let layout = Layout::new(window_size);
   let size = Size::new(64.0, 64.0);
   let padding = 32.0;
 
   let mut btns = Vec::new();
 
   btns.push(ButtonBuilder::new(ctx)
-            .point(window_size.center() + Point::new(size.w + padding, 0.0))
+            .point(layout.at(Origin::Center, size.w + padding, 0.0))
             .size(size.clone())
             .origin(Origin::Center)
             .button_type(ButtonType::PauseResume)
             .animation_from(vec![::join_str(buttons::IMAGES, "play.png")], vec![1000])
             .build().expect("Should build PauseResume Button"));
   btns.push(ButtonBuilder::new(ctx)
-            .point(window_size.center())
+            .point(layout.at(Origin::Center, 0.0, 0.0))
             .size(size.clone())
             .origin(Origin::Center)
             .button_type(ButtonType::PauseReset)
             .animation_from(vec![::join_str(buttons::IMAGES, "retry.png")], vec![1000])
             .build().expect("Should build PauseReset Button"));
   btns.push(ButtonBuilder::new(ctx)
-            .point(window_size.center() - Point::new(size.w + padding, 0.0))
+            .point(layout.at(Origin::Center, -(size.w + padding), 0.0))
             .size(size.clone())
             .origin(Origin::Center)
             .button_type(ButtonType::PauseToTitle)
             .animation_from(vec![::join_str(buttons::IMAGES, "arrow.png")], vec![1000])
             .build().expect("Should build PauseToTitle Button"));
 
+  let controls_point = layout.at(Origin::Center, 0.0, size.h + padding * 1.5);
+  let options_point  = controls_point.clone() + Point::new(0.0, size.h * 0.75 + padding * 0.5);
+  let font = Font::new_px(ctx, fonts::DEFAULT, CONTROLS_FONT_SIZE).expect("Should load font");
+  let text = Text::new(ctx, "Controls", &font).expect("Should create text");
+  btns.push(ButtonBuilder::new(ctx)
+            .point(controls_point.clone())
+            .size_from((size.w + padding) * 2.0 + size.w, size.h * 0.75)
+            .origin(Origin::Center)
+            .button_type(ButtonType::PauseControls)
+            .animation_from(vec![::join_str(buttons::IMAGES, "level_select1.png")], vec![1000])
+            .text_from(
+              controls_point,
+              Size::new(text.width() as NumType, text.height() as NumType),
+              Origin::Center,
+              text
+            ).build().expect("Should build PauseControls Button"));
+
+  let text = Text::new(ctx, "Options", &font).expect("Should create text");
+  btns.push(ButtonBuilder::new(ctx)
+            .point(options_point.clone())
+            .size_from((size.w + padding) * 2.0 + size.w, size.h * 0.75)
+            .origin(Origin::Center)
+            .button_type(ButtonType::PauseOptions)
+            .animation_from(vec![::join_str(buttons::IMAGES, "level_select2.png")], vec![1000])
+            .text_from(
+              options_point,
+              Size::new(text.width() as NumType, text.height() as NumType),
+              Origin::Center,
+              text
+            ).build().expect("Should build PauseOptions Button"));
+
   btns
 }
 
 pub fn new_title(ctx: &mut Context, window_size: &Size) -> TextBox {
   let font = Font::new(ctx, fonts::DEFAULT, TITLE_FONT_SIZE).expect("Create font");
   TextBoxBuilder::new()
-    .point(window_size.center() - Point::new(0.0, 192.0))
+    .point(Layout::new(window_size).at(Origin::Center, 0.0, -192.0))
     .size_from(256.0, 96.0)
     .origin(Origin::Center)
     .bg_color(TITLE_BG_COLOR)
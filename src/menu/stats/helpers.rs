@@ -14,6 +14,7 @@ use animation::prelude::*;
 use menu::buttons::prelude::*;
 use score::prelude::*;
 use color_rect::prelude::*;
+use layout::Layout;
 
 pub enum TextOrigin {
   Left,
@@ -64,6 +65,7 @@ impl StatsText {
 pub struct StatsTexts {
   score:          StatsText,
   highscore:      Option<StatsText>,
+  time:           StatsText,
   saved_player:   Option<StatsText>,
   saved_children: Vec<StatsText>,
 }
@@ -79,13 +81,15 @@ impl StatsTexts {
   ) -> GameResult<Self> {
     let font_score = graphics::Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE_SCORE)?;
     let font_saved = graphics::Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE_SAVED)?;
+    let layout = Layout::from_rect(point.clone(), size);
     let offset = Point::new(32.0, 32.0);
     let score_offset = Point::new(0.0, 8.0);
     let saved_offset = Point::new(0.0, 8.0);
-    let point_score = point.clone() + offset.clone();
-    let point_saved = Point::new(
-      point.x + size.w - offset.x,
-      point_score.y + offset.y + font_score.get_height() as NumType
+    let point_score = layout.at(Origin::TopLeft, offset.x, offset.y);
+    let point_saved = layout.at(
+      Origin::TopRight,
+      -offset.x,
+      offset.y * 2.0 + font_score.get_height() as NumType
     );
 
     let semantic_score = if is_final {
@@ -121,6 +125,22 @@ impl StatsTexts {
       ))
     } else { None };
 
+    let semantic_time = if is_final {
+      format!("Total Best {}", score.semantic_time())
+    } else {
+      score.semantic_time()
+    };
+    let time_text = StatsText::new(
+      graphics::Text::new(ctx, &semantic_time, &font_saved)?,
+      Point::new(
+        point_score.x + score_offset.x,
+        point_score.y + (score_offset.y + font_score.get_height() as NumType)
+          * if highscore_text.is_some() { 2.0 } else { 1.0 }
+      ),
+      TextOrigin::Left,
+      None
+    );
+
     let saved_player = if let Some(score) = &score.semantic_player() {
       Some(StatsText::new(
         graphics::Text::new(ctx, score, &font_saved)?,
@@ -158,6 +178,7 @@ impl StatsTexts {
     Ok(StatsTexts {
       score:     score_text,
       highscore: highscore_text,
+      time:      time_text,
       saved_player,
       saved_children,
     })
@@ -168,6 +189,7 @@ impl StatsTexts {
     if let Some(highscore) = &self.highscore {
       highscore.draw(ctx)?;
     }
+    self.time.draw(ctx)?;
     if let Some(saved_player) = &self.saved_player {
       saved_player.draw(ctx)?;
     }
@@ -192,10 +214,7 @@ pub fn new_color_rect(window_size: Size) -> ColorRect {
 pub fn new_buttons(ctx: &mut Context, point: &Point, size: &Size, is_final: bool) -> Vec<Button> {
   let mut vec = Vec::new();
   let offset = Point::new(32.0, 32.0);
-  let bottom_center = Point::new(
-    point.x + size.w / 2.0,
-    point.y + size.h - offset.y
-  );
+  let bottom_center = Layout::from_rect(point.clone(), size).at(Origin::BottomCenter, 0.0, -offset.y);
   let button_offset = Point::new(128.0, 0.0);
   let button_size = Size::new(64.0, 64.0);
 
@@ -222,6 +241,18 @@ pub fn new_buttons(ctx: &mut Context, point: &Point, size: &Size, is_final: bool
     );
   }
 
+  if !is_final {
+    vec.push(
+      ButtonBuilder::new(ctx)
+      .point(Layout::from_rect(point.clone(), size).at(Origin::TopRight, -offset.x * 0.5, offset.y * 0.5))
+      .size(Size::new(48.0, 48.0))
+      .origin(Origin::TopRight)
+      .button_type(ButtonType::StatsLeaderboard)
+      .animation_from(vec![::join_str(buttons::IMAGES, "level_menu.png")], vec![1000])
+      .build().expect("Should build StatsLeaderboard Button")
+    );
+  }
+
   let (point, button_type) = if is_final {
     (bottom_center, ButtonType::StatsToThankYou)
   } else {
@@ -17,6 +17,8 @@ use noframe::entity::Entity;
 use self::helpers::*;
 use super::prelude::*;
 use score::prelude::*;
+use score::leaderboards::LeaderboardEntry;
+use menu::leaderboard::LeaderboardTable;
 use color_rect::ColorRect;
 
 pub struct StatsMenu {
@@ -24,18 +26,40 @@ pub struct StatsMenu {
   buttons:        Vec<Button>,
   clicked:        Option<ButtonType>,
   texts:          StatsTexts,
+  leaderboard:    Option<LeaderboardTable>,
+  show_board:     bool,
 }
 
 impl StatsMenu {
-  pub fn new(ctx: &mut Context, window_size: Size, score: Score, highscore: Option<Score>, is_final: bool) -> GameResult<Self> {
+  // `leaderboard` is the level's leaderboard, with the run's entry at `new_entry` if it made it on there.
+  // The final stats menu has no leaderboard.
+  pub fn new(
+    ctx: &mut Context,
+    window_size: Size,
+    score: Score,
+    highscore: Option<Score>,
+    leaderboard: &Vec<LeaderboardEntry>,
+    new_entry: Option<usize>,
+    is_final: bool
+  ) -> GameResult<Self> {
     let rect = new_color_rect(window_size.clone());
+    let leaderboard = if is_final { None } else {
+      Some(LeaderboardTable::new(ctx, "Leaderboard", leaderboard, new_entry, rect.point(), rect.size())?)
+    };
     Ok(Self {
       buttons:        new_buttons(ctx, rect.point(), rect.size(), is_final),
       clicked:        None,
       texts:          StatsTexts::new(ctx, score, highscore, rect.point(), rect.size(), is_final)?,
+      leaderboard,
+      show_board:     false,
       rect,
     })
   }
+
+  // Switches between the run's stats and the leaderboard
+  pub fn toggle_leaderboard(&mut self) {
+    self.show_board = !self.show_board && self.leaderboard.is_some();
+  }
 }
 
 impl Mask for StatsMenu {
@@ -61,7 +85,10 @@ impl Menu for StatsMenu {
 
   fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.rect.draw(ctx)?;
-    self.texts.draw(ctx)?;
+    match &self.leaderboard {
+      Some(leaderboard) if self.show_board => leaderboard.draw(ctx)?,
+      _                                    => self.texts.draw(ctx)?,
+    }
     self.draw_menu(ctx)?;
     Ok(())
   }
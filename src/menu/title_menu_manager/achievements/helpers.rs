@@ -0,0 +1,143 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+use noframe::geo::mask::misc::Side;
+
+use settings::res::fonts;
+use settings::buttons;
+use settings::menus::title::achievements::*;
+use menu::prelude::*;
+use menu::stats::prelude::{ StatsText, TextOrigin };
+use achievements::AchievementInfo;
+use color_rect::prelude::*;
+use layout::Layout;
+
+pub fn new_color_rect(window_size: &Size) -> ColorRect {
+  ColorRectBuilder::new()
+    .size(window_size.clone())
+    .color([0.0, 0.0, 0.0, 1.0])
+    .build()
+}
+
+fn area(window_size: &Size) -> Rect {
+  let border_padding = Point::new(64.0, 32.0);
+  Layout::new(window_size).inset(border_padding.x, border_padding.y)
+}
+
+// Below the title, where the achievements start
+fn rows_top(window_size: &Size) -> NumType {
+  area(window_size).side(Side::Top) + TITLE_FONT_SIZE as NumType * 2.0
+}
+
+pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> GameResult<Vec<Button>> {
+  let area       = area(window_size);
+  let arrow_size = Size::new(48.0, 48.0);
+  let page_x     = area.center().x;
+  let page_y     = area.side(Side::Bottom) - arrow_size.h / 2.0;
+  let mut btns   = Vec::new();
+
+  btns.push(ButtonBuilder::new(ctx)
+            .point(Point::new(page_x - arrow_size.w, page_y))
+            .size(arrow_size.clone())
+            .origin(Origin::Center)
+            .button_type(ButtonType::AchievementsPrev)
+            .animation_from(vec![::join_str(buttons::IMAGES, "child_arrow.png")], vec![1000])
+            .facing(Facing::Left)
+            .build()?);
+  btns.push(ButtonBuilder::new(ctx)
+            .point(Point::new(page_x + arrow_size.w, page_y))
+            .size(arrow_size)
+            .origin(Origin::Center)
+            .button_type(ButtonType::AchievementsNext)
+            .animation_from(vec![::join_str(buttons::IMAGES, "child_arrow.png")], vec![1000])
+            .build()?);
+  btns.push(ButtonBuilder::new(ctx)
+            .point(area.bottom_left())
+            .size(Size::new(64.0, 64.0))
+            .origin(Origin::BottomLeft)
+            .button_type(ButtonType::AchievementsBack)
+            .animation_from(vec![::join_str(buttons::IMAGES, "arrow.png")], vec![1000])
+            .build()?);
+
+  Ok(btns)
+}
+
+// The title, and how many of the achievements are unlocked
+pub fn new_title_texts(ctx: &mut Context, window_size: &Size, achievements: &Vec<AchievementInfo>) -> GameResult<Vec<StatsText>> {
+  let font       = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let title_font = Font::new_px(ctx, fonts::DEFAULT, TITLE_FONT_SIZE)?;
+  let area       = area(window_size);
+  let unlocked   = achievements.iter().filter( |achievement| achievement.unlocked ).count();
+
+  Ok(vec![
+    StatsText::new(
+      Text::new(ctx, "Achievements", &title_font)?,
+      area.top_left(),
+      TextOrigin::Left,
+      Some(FONT_COLOR)
+    ),
+    StatsText::new(
+      Text::new(ctx, &format!("{}/{} unlocked", unlocked, achievements.len()), &font)?,
+      area.top_right(),
+      TextOrigin::Right,
+      Some(LOCKED_COLOR)
+    ),
+  ])
+}
+
+// One page of achievements, their names above their descriptions
+pub fn new_page_texts(
+  ctx: &mut Context,
+  window_size: &Size,
+  achievements: &[AchievementInfo],
+  page: usize,
+  page_count: usize
+) -> GameResult<Vec<StatsText>> {
+  let name_font = Font::new_px(ctx, fonts::DEFAULT, NAME_FONT_SIZE)?;
+  let font      = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let area      = area(window_size);
+  let left      = area.side(Side::Left);
+  let right     = area.side(Side::Right);
+  let top       = rows_top(window_size);
+  let mut texts = Vec::new();
+
+  if achievements.is_empty() {
+    texts.push(StatsText::new(
+      Text::new(ctx, "There are no achievements.", &font)?,
+      Point::new(left, top),
+      TextOrigin::Left,
+      Some(LOCKED_COLOR)
+    ));
+    return Ok(texts);
+  }
+
+  for (row, achievement) in achievements.iter().enumerate() {
+    let y = top + ROW_HEIGHT * row as NumType;
+    let (color, status) = if achievement.unlocked {
+      (UNLOCKED_COLOR, "Unlocked")
+    } else {
+      (LOCKED_COLOR, "Locked")
+    };
+    texts.push(StatsText::new(Text::new(ctx, &achievement.name, &name_font)?, Point::new(left, y), TextOrigin::Left, Some(color)));
+    texts.push(StatsText::new(Text::new(ctx, status, &font)?, Point::new(right, y), TextOrigin::Right, Some(color)));
+    texts.push(StatsText::new(
+      Text::new(ctx, &achievement.description, &font)?,
+      Point::new(left, y + NAME_FONT_SIZE as NumType + 4.0),
+      TextOrigin::Left,
+      Some(FONT_COLOR)
+    ));
+  }
+
+  if page_count > 1 {
+    texts.push(StatsText::new(
+      Text::new(ctx, &format!("Page {}/{}", page + 1, page_count), &font)?,
+      Point::new(area.center().x, area.side(Side::Bottom) - 96.0),
+      TextOrigin::Center,
+      Some(LOCKED_COLOR)
+    ));
+  }
+  Ok(texts)
+}
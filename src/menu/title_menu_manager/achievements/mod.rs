@@ -0,0 +1,120 @@
+mod helpers;
+
+use ggez::{
+  Context,
+  GameResult,
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use self::helpers::*;
+use settings::menus::title::achievements::PAGE_ROWS;
+use menu::prelude::*;
+use menu::stats::prelude::StatsText;
+use achievements::AchievementInfo;
+use color_rect::ColorRect;
+
+// Every achievement, unlocked or not, a page at a time.
+pub struct AchievementsMenu {
+  window_size:  Size,
+  buttons:      Vec<Button>,
+  clicked:      Option<ButtonType>,
+  rect:         ColorRect,
+  title_texts:  Vec<StatsText>,
+  achievements: Vec<AchievementInfo>,
+  page:         usize,
+  page_texts:   Vec<StatsText>,
+}
+
+impl AchievementsMenu {
+  pub fn new(ctx: &mut Context, size: Size, achievements: Vec<AchievementInfo>) -> GameResult<Self> {
+    let mut menu = Self {
+      window_size:  size.clone(),
+      buttons:      new_buttons(ctx, &size)?,
+      clicked:      None,
+      rect:         new_color_rect(&size),
+      title_texts:  new_title_texts(ctx, &size, &achievements)?,
+      achievements,
+      page:         0,
+      page_texts:   Vec::new(),
+    };
+    menu.update_page(ctx)?;
+    Ok(menu)
+  }
+
+  fn page_count(&self) -> usize {
+    (self.achievements.len() + PAGE_ROWS - 1) / PAGE_ROWS
+  }
+
+  pub fn prev_page(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if self.page == 0 { return Ok(()); }
+    self.page -= 1;
+    self.update_page(ctx)
+  }
+
+  pub fn next_page(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if self.page + 1 >= self.page_count() { return Ok(()); }
+    self.page += 1;
+    self.update_page(ctx)
+  }
+
+  fn update_page(&mut self, ctx: &mut Context) -> GameResult<()> {
+    let start = (self.page * PAGE_ROWS).min(self.achievements.len());
+    let end   = (start + PAGE_ROWS).min(self.achievements.len());
+    self.page_texts = new_page_texts(ctx, &self.window_size, &self.achievements[start .. end], self.page, self.page_count())?;
+    Ok(())
+  }
+}
+
+impl Mask for AchievementsMenu {
+  fn point(&self)         -> &Point     { self.rect.point()     }
+  fn point_mut(&mut self) -> &mut Point { self.rect.point_mut() }
+  fn size(&self)          -> &Size      { self.rect.size()      }
+  fn origin(&self)        -> &Origin    { self.rect.origin()    }
+}
+
+impl Menu for AchievementsMenu {
+  fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.rect.draw(ctx)?;
+    self.draw_menu(ctx)?;
+    for text in self.title_texts.iter().chain(self.page_texts.iter()) {
+      text.draw(ctx)?;
+    }
+    Ok(())
+  }
+
+  // The page arrows are only there with more than one page
+  fn buttons(&self) -> Vec<&Button> {
+    let paged = self.page_count() > 1;
+    self.buttons.iter()
+      .filter( |button| match button.button_type {
+        ButtonType::AchievementsPrev | ButtonType::AchievementsNext => paged,
+        _                                                           => true,
+      })
+      .collect()
+  }
+  fn buttons_mut(&mut self) -> Vec<&mut Button> {
+    let paged = self.page_count() > 1;
+    self.buttons.iter_mut()
+      .filter( |button| match button.button_type {
+        ButtonType::AchievementsPrev | ButtonType::AchievementsNext => paged,
+        _                                                           => true,
+      })
+      .collect()
+  }
+  fn animation(&self) -> Option<&Animation> {
+    None
+  }
+  fn animation_mut(&mut self) -> Option<&mut Animation> {
+    None
+  }
+  fn clicked(&mut self, button_type: ButtonType) {
+    self.clicked = Some(button_type);
+  }
+  fn get_clicked(&self) -> &Option<ButtonType> {
+    &self.clicked
+  }
+  fn clear_clicked(&mut self) {
+    self.clicked = None;
+  }
+}
@@ -0,0 +1,65 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+use noframe::geo::mask::misc::Side;
+
+use settings::res::fonts;
+use settings::buttons;
+use settings::menus::title::level_packs::*;
+use menu::prelude::*;
+use color_rect::prelude::*;
+use layout::Layout;
+
+pub fn new_color_rect(window_size: &Size) -> ColorRect {
+  ColorRectBuilder::new()
+    .size(window_size.clone())
+    .color([0.0, 0.0, 0.0, 1.0])
+    .build()
+}
+
+pub fn new_buttons(ctx: &mut Context, window_size: &Size, pack_names: &Vec<String>) -> GameResult<Vec<Button>> {
+  let padding          = 24.0;
+  let border_padding   = Point::new(64.0, 32.0);
+  let buttons_area     = Layout::new(window_size).inset(border_padding.x, border_padding.y);
+  let size         = Size::new(buttons_area.size().w / 2.0, 64.0);
+  let column_point = Point::new(
+    buttons_area.center().x,
+    buttons_area.side(Side::Top)
+  );
+  let text_offset  = Point::new(-size.w / 2.0 + 32.0, 0.0);
+  let font         = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let mut buttons  = Vec::new();
+
+  for (i, name) in pack_names.iter().enumerate() {
+    let center = Point::new(
+      column_point.x,
+      column_point.y + size.h / 2.0 + (size.h + padding) * i as NumType
+    );
+    let text = Text::new(ctx, name, &font)?;
+    buttons.push(ButtonBuilder::new(ctx)
+                 .point(center.clone())
+                 .size(size.clone())
+                 .origin(Origin::Center)
+                 .button_type(ButtonType::LevelPacksPack(i))
+                 .animation_from(vec![::join_str(buttons::IMAGES, &format!("level_select{}.png", i % 3 + 1))], vec![1000])
+                 .text_from(
+                   center + text_offset.clone(),
+                   Size::new(text.width() as NumType, text.height() as NumType),
+                   Origin::CenterLeft,
+                   text
+                 ).build()?);
+  }
+
+  buttons.push(ButtonBuilder::new(ctx)
+               .point(buttons_area.bottom_left())
+               .size(Size::new(64.0, 64.0))
+               .origin(Origin::BottomLeft)
+               .button_type(ButtonType::LevelPacksBack)
+               .animation_from(vec![::join_str(buttons::IMAGES, "arrow.png")], vec![1000])
+               .build()?);
+
+  Ok(buttons)
+}
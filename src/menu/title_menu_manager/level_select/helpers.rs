@@ -5,13 +5,38 @@ use ggez::{
 };
 use noframe::geo::prelude::*;
 use noframe::geo::mask::misc::Side;
+use noframe::entity::Entity;
 
+use super::thumbnail::Thumbnails;
 use settings::res::fonts;
 use settings::buttons;
-use settings::level_manager::LEVEL_NAMES;
 use settings::menus::title::level_select::*;
+use settings::leaderboards;
+use level_manager::level_info::{ LevelInfo, LevelStatus };
 use menu::prelude::*;
 use color_rect::prelude::*;
+use text_box::prelude::*;
+use layout::Layout;
+
+// Thumbnail, status text and lock overlay, drawn on top of a LevelSelectLevel button.
+pub struct LevelPreview {
+  thumbnail: Option<TextBox>,
+  status:    TextBox,
+  lock:      Option<ColorRect>,
+}
+
+impl LevelPreview {
+  pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(thumbnail) = &self.thumbnail {
+      thumbnail.draw(ctx)?;
+    }
+    self.status.draw(ctx)?;
+    if let Some(lock) = &self.lock {
+      lock.draw(ctx)?;
+    }
+    Ok(())
+  }
+}
 
 pub fn new_color_rect(window_size: &Size) -> ColorRect {
   ColorRectBuilder::new()
@@ -20,19 +45,12 @@ pub fn new_color_rect(window_size: &Size) -> ColorRect {
     .build()
 }
 
-pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> GameResult<Vec<Button>> {
-  let columns = 3;
+pub fn new_buttons(ctx: &mut Context, window_size: &Size, levels: &Vec<LevelInfo>) -> GameResult<Vec<Button>> {
   let rows    = 5;
+  let columns = ((levels.len() + rows - 1) / rows).max(1);
   let padding          = Point::new(48.0, 32.0);
   let border_padding   = Point::new(64.0, 32.0);
-  let buttons_area     = Rect::new(
-    Point::new(border_padding.x, border_padding.y),
-    Size::new(
-      window_size.w - border_padding.x * 2.0,
-      window_size.h - border_padding.y * 2.0
-    ),
-    Origin::TopLeft
-  );
+  let buttons_area     = Layout::new(window_size).inset(border_padding.x, border_padding.y);
   let size             = Size::new(
     (buttons_area.size().w - padding.x * (columns - 1) as NumType) / columns as NumType,
     80.0
@@ -41,15 +59,17 @@ pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> GameResult<Vec<Butt
     buttons_area.side(Side::Left) + size.w / 2.0,
     buttons_area.side(Side::Top)
   );
-  let text_offset  = Point::new(-80.0, 0.0);
+  let text_offset  = Point::new(-80.0, -12.0);
   let font         = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
   let mut buttons  = Vec::new();
 
   for col in 0 .. columns {
     for row in 0 .. rows {
       let i    = rows * col + row;
-      let name = LEVEL_NAMES[i];
-      let image_filename = &format!("level_select{}.png", col + 1);
+      let name = if let Some(level) = levels.get(i) {
+        &level.name
+      } else { break; };
+      let image_filename = &format!("level_select{}.png", col % 3 + 1);
       let center = Point::new(
         column_point.x + (size.w + padding.x) * col as NumType,
         column_point.y + size.h / 2.0 + (size.h + padding.y) * row as NumType
@@ -81,3 +101,86 @@ pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> GameResult<Vec<Butt
 
   Ok(buttons)
 }
+
+// A small button on every level with a leaderboard, over the right end of its level button
+pub fn new_leaderboard_buttons(ctx: &mut Context, buttons: &Vec<Button>, levels: &Vec<LevelInfo>) -> GameResult<Vec<Button>> {
+  let size = Size::new(40.0, 40.0);
+  buttons.iter().filter_map( |button| match button.button_type {
+    ButtonType::LevelSelectLevel(i) if !levels[i].leaderboard.is_empty() => Some((i, button)),
+    _                                                                    => None,
+  }).map( |(i, button)| {
+    ButtonBuilder::new(ctx)
+      .point_from(button.side(Side::Right) - 8.0, button.center().y)
+      .size(size.clone())
+      .origin(Origin::CenterRight)
+      .button_type(ButtonType::LevelSelectBoard(i))
+      .animation_from(vec![::join_str(buttons::IMAGES, "level_menu.png")], vec![1000])
+      .build()
+  }).collect()
+}
+
+// The panel a level's leaderboard is shown on, and its close button
+pub fn new_leaderboard_panel(ctx: &mut Context, window_size: &Size) -> GameResult<(ColorRect, Button)> {
+  let rect = ColorRectBuilder::new()
+    .point(Layout::new(window_size).at(Origin::Center, 0.0, 0.0))
+    .size(leaderboards::PANEL_SIZE)
+    .origin(Origin::Center)
+    .color(leaderboards::BG_COLOR)
+    .build();
+  let close = ButtonBuilder::new(ctx)
+    .point(rect.top_right() + Point::new(-8.0, 8.0))
+    .size(Size::new(40.0, 40.0))
+    .origin(Origin::TopRight)
+    .button_type(ButtonType::LevelSelectClose)
+    .animation_from(vec![::join_str(buttons::IMAGES, "exit.png")], vec![1000])
+    .build()?;
+  Ok((rect, close))
+}
+
+pub fn new_previews(ctx: &mut Context, buttons: &Vec<Button>, levels: &Vec<LevelInfo>, thumbnails: &mut Thumbnails) -> GameResult<Vec<LevelPreview>> {
+  let status_offset = Point::new(-80.0, 18.0);
+  let font          = Font::new_px(ctx, fonts::DEFAULT, STATUS_FONT_SIZE)?;
+  let mut previews  = Vec::new();
+
+  for button in buttons {
+    let level = if let ButtonType::LevelSelectLevel(i) = button.button_type {
+      &levels[i]
+    } else { continue; };
+    let thumbnail = thumbnails.get(ctx, &level.filepath).map( |image| {
+      TextBoxBuilder::new()
+        .point_from(button.side(Side::Left) + 8.0, button.center().y)
+        .size_from(thumbnail::WIDTH as NumType, thumbnail::HEIGHT as NumType)
+        .origin(Origin::CenterLeft)
+        .image(image)
+        .build()
+    });
+    let status = match level.status {
+      LevelStatus::Locked   => "Locked".to_string(),
+      LevelStatus::Unlocked => "Not cleared".to_string(),
+      LevelStatus::Cleared  => level.highscore.as_ref()
+        .map( |score| score.semantic_highscore() )
+        .unwrap_or("Cleared".to_string()),
+    };
+    let text = Text::new(ctx, &status, &font)?;
+    let lock = if level.status == LevelStatus::Locked {
+      Some(ColorRectBuilder::new()
+           .point(button.top_left())
+           .size(button.size().clone())
+           .color(LOCKED_COLOR)
+           .build())
+    } else { None };
+    previews.push(LevelPreview {
+      thumbnail,
+      status: TextBoxBuilder::new()
+        .point(button.center() + status_offset.clone())
+        .size_from(text.width() as NumType, text.height() as NumType)
+        .origin(Origin::CenterLeft)
+        .text(text)
+        .text_origin(TextOrigin::CenterLeft)
+        .build(),
+      lock,
+    });
+  }
+
+  Ok(previews)
+}
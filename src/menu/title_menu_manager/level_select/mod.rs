@@ -1,4 +1,5 @@
 mod helpers;
+pub mod thumbnail;
 
 use ggez::{
   Context,
@@ -8,23 +9,71 @@ use noframe::geo::prelude::*;
 use noframe::entity::Entity;
 
 use self::helpers::*;
+use self::thumbnail::Thumbnails;
+use level_manager::level_info::{ LevelInfo, LevelStatus };
+use score::leaderboards::LeaderboardEntry;
 use menu::prelude::*;
+use menu::leaderboard::LeaderboardTable;
 use color_rect::ColorRect;
 
 pub struct LevelSelectMenu {
-  buttons:   Vec<Button>,
-  clicked:   Option<ButtonType>,
-  rect:      ColorRect,
+  buttons:      Vec<Button>,
+  previews:     Vec<LevelPreview>,
+  locked:       Vec<usize>,
+  clicked:      Option<ButtonType>,
+  rect:         ColorRect,
+  leaderboards: Vec<(String, Vec<LeaderboardEntry>)>,  // Every level's name and leaderboard
+  board:        Option<LeaderboardTable>,                // The leaderboard being shown, over the levels
+  board_rect:   ColorRect,
+  board_close:  Button,
 }
 
 impl LevelSelectMenu {
-  pub fn new(ctx: &mut Context, size: Size) -> GameResult<Self> {
+  pub fn new(ctx: &mut Context, size: Size, levels: &Vec<LevelInfo>, thumbnails: &mut Thumbnails) -> GameResult<Self> {
+    let mut buttons = new_buttons(ctx, &size, levels)?;
+    let previews    = new_previews(ctx, &buttons, levels, thumbnails)?;
+    let board_buttons = new_leaderboard_buttons(ctx, &buttons, levels)?;
+    buttons.extend(board_buttons);
+    let (board_rect, board_close) = new_leaderboard_panel(ctx, &size)?;
     Ok(Self {
-      buttons:   new_buttons(ctx, &size)?,
-      clicked:   None,
-      rect:      new_color_rect(&size),
+      buttons,
+      previews,
+      locked:       levels.iter().enumerate()
+        .filter( |(_, level)| level.status == LevelStatus::Locked )
+        .map( |(i, _)| i )
+        .collect(),
+      clicked:      None,
+      rect:         new_color_rect(&size),
+      leaderboards: levels.iter().enumerate()
+        .map( |(i, level)| (format!("{}) {}", i + 1, ::semantic(&level.name)), level.leaderboard.clone()) )
+        .collect(),
+      board:        None,
+      board_rect,
+      board_close,
     })
   }
+
+  pub fn show_leaderboard(&mut self, ctx: &mut Context, level_index: usize) -> GameResult<()> {
+    if let Some((name, entries)) = self.leaderboards.get(level_index) {
+      self.board = Some(LeaderboardTable::new(
+        ctx,
+        &format!("Leaderboard: {}", name),
+        entries,
+        None,
+        &self.board_rect.top_left(),
+        self.board_rect.size()
+      )?);
+    }
+    Ok(())
+  }
+
+  pub fn hide_leaderboard(&mut self) {
+    self.board = None;
+  }
+
+  pub fn is_showing_leaderboard(&self) -> bool {
+    self.board.is_some()
+  }
 }
 
 impl Mask for LevelSelectMenu {
@@ -37,14 +86,31 @@ impl Mask for LevelSelectMenu {
 impl Menu for LevelSelectMenu {
   fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.rect.draw(ctx)?;
-    self.draw_menu(ctx)?;
+    for button in &mut self.buttons {
+      button.draw(ctx)?;
+    }
+    for preview in &self.previews {
+      preview.draw(ctx)?;
+    }
+    if let Some(board) = &self.board {
+      self.board_rect.draw(ctx)?;
+      board.draw(ctx)?;
+      self.board_close.draw(ctx)?;
+    }
     Ok(())
   }
 
+  // Only the close button can be used while a leaderboard is shown
   fn buttons(&self) -> Vec<&Button> {
+    if self.board.is_some() {
+      return vec![&self.board_close];
+    }
     self.buttons.iter().map( |button| button ).collect()
   }
   fn buttons_mut(&mut self) -> Vec<&mut Button> {
+    if self.board.is_some() {
+      return vec![&mut self.board_close];
+    }
     self.buttons.iter_mut().map( |button| button ).collect()
   }
   fn animation(&self) -> Option<&Animation> {
@@ -54,6 +120,10 @@ impl Menu for LevelSelectMenu {
     None
   }
   fn clicked(&mut self, button_type: ButtonType) {
+    // Locked levels are visible, but can't be selected
+    if let ButtonType::LevelSelectLevel(i) = button_type {
+      if self.locked.contains(&i) { return; }
+    }
     self.clicked = Some(button_type);
   }
   fn get_clicked(&self) -> &Option<ButtonType> {
@@ -0,0 +1,108 @@
+use std::collections::hash_map::HashMap;
+use std::path::{ Path, PathBuf };
+
+use ggez::{
+  Context,
+  GameResult,
+  graphics::Image,
+};
+use json::JsonValue;
+use noframe::geo::prelude::*;
+
+use settings::menus::title::level_select::thumbnail::*;
+use json_file;
+
+// Rendered level previews, cached by level filepath.
+pub struct Thumbnails {
+  images: HashMap<PathBuf, Image>,
+}
+
+impl Thumbnails {
+  pub fn new() -> Self {
+    Self {
+      images: HashMap::new(),
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.images.clear();
+  }
+
+  pub fn get(&mut self, ctx: &mut Context, filepath: &Path) -> Option<Image> {
+    if let Some(image) = self.images.get(filepath) {
+      return Some(image.clone());
+    }
+    let image = match json_file::read(filepath).and_then( |data| render_thumbnail(ctx, &data) ) {
+      Ok(image) => image,
+      Err(e)    => {
+        eprintln!("Couldn't render thumbnail for {}: {}", filepath.display(), e);
+        return None;
+      }
+    };
+    self.images.insert(filepath.to_path_buf(), image.clone());
+    Some(image)
+  }
+}
+
+// Instances are drawn in layers, so persons and interactables aren't hidden behind walls.
+fn instance_layer(instance_type: &str) -> Option<(usize, [u8; 4])> {
+  match instance_type {
+    "GoalInteractable"                                                => Some((0, GOAL_COLOR)),
+    "Wall"                                                            => Some((1, WALL_COLOR)),
+    "JumpPadInteractable" | "SwitchInteractable" | "DoorInteractable"
+      | "OneWayInteractable"                                          => Some((2, INTERACTABLE_COLOR)),
+    "Player" | "Child" | "LarryChild" | "ThingChild" | "BloatChild"   => Some((3, PERSON_COLOR)),
+    _                                                                 => None,
+  }
+}
+
+fn instance_rect(instance: &JsonValue) -> Option<Rect> {
+  Some(Rect::new(
+      Point::new(instance["position"]["x"].as_f32()?, instance["position"]["y"].as_f32()?),
+      Size::new(instance["size"]["w"].as_f32()?, instance["size"]["h"].as_f32()?),
+      Origin::TopLeft
+  ))
+}
+
+fn level_size(data: &JsonValue) -> Size {
+  if let (Some(w), Some(h)) = (data["size"]["w"].as_f32(), data["size"]["h"].as_f32()) {
+    return Size::new(w.max(1.0), h.max(1.0));
+  }
+  data["instances"].members()
+    .filter_map(instance_rect)
+    .fold(Size::new(1.0, 1.0), |size, rect| {
+      let bottom_right = rect.bottom_right();
+      Size::new(size.w.max(bottom_right.x), size.h.max(bottom_right.y))
+    })
+}
+
+pub fn render_thumbnail(ctx: &mut Context, data: &JsonValue) -> GameResult<Image> {
+  let (width, height) = (WIDTH as usize, HEIGHT as usize);
+  let mut pixels = BG_COLOR.iter().cloned().cycle().take(width * height * 4).collect::<Vec<u8>>();
+  let size  = level_size(data);
+  let scale = (width as f32 / size.w).min(height as f32 / size.h);
+
+  for layer in 0 .. 4 {
+    for instance in data["instances"].members() {
+      let color = match instance["type"].as_str().and_then(instance_layer) {
+        Some((l, color)) if l == layer => color,
+        _                              => continue,
+      };
+      if let Some(rect) = instance_rect(instance) {
+        let top_left = rect.top_left();
+        let left   = (top_left.x * scale).max(0.0) as usize;
+        let top    = (top_left.y * scale).max(0.0) as usize;
+        let right  = (((top_left.x + rect.size().w) * scale).ceil() as usize).max(left + 1).min(width);
+        let bottom = (((top_left.y + rect.size().h) * scale).ceil() as usize).max(top + 1).min(height);
+        for y in top .. bottom {
+          for x in left .. right {
+            let i = (y * width + x) * 4;
+            pixels[i .. i + 4].copy_from_slice(&color);
+          }
+        }
+      }
+    }
+  }
+
+  Image::from_rgba8(ctx, WIDTH, HEIGHT, &pixels)
+}
@@ -2,11 +2,20 @@ pub mod prelude {
   pub use super::TitleMenuManager;
   pub use super::title::TitleMenu;
   pub use super::level_select::LevelSelectMenu;
+  pub use super::level_packs::LevelPacksMenu;
+  pub use super::profiles::ProfilesMenu;
+  pub use super::ProfileAction;
+  pub use super::statistics::StatisticsMenu;
+  pub use super::achievements::AchievementsMenu;
   pub use super::thank_you::ThankYouMenu;
 }
 
 pub mod title;
 pub mod level_select;
+pub mod level_packs;
+pub mod profiles;
+pub mod statistics;
+pub mod achievements;
 pub mod thank_you;
 
 use ggez::{
@@ -17,35 +26,107 @@ use noframe::geo::prelude::*;
 
 use self::title::TitleMenu;
 use self::level_select::LevelSelectMenu;
+use self::level_select::thumbnail::Thumbnails;
+use self::level_packs::LevelPacksMenu;
+use self::profiles::ProfilesMenu;
+use self::statistics::StatisticsMenu;
+use self::achievements::AchievementsMenu;
 use self::thank_you::ThankYouMenu;
+use level_manager::level_info::LevelInfo;
 use menu::prelude::*;
+use menu::options::OptionsMenu;
+use score::lifetime::LevelStats;
+use achievements::AchievementInfo;
 
 #[derive(Debug, PartialEq)]
 enum MenuType {
   Title,
   LevelSelect,
+  LevelPacks,
+  Profiles,
+  Options,
+  Statistics,
+  Achievements,
   ThankYou,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub enum ProfileAction {
+  Select(usize),
+  New,
+  Rename,
+  Copy,
+  Delete,
+}
+
 pub struct TitleMenuManager {
-  current:           MenuType,
-  pub load_level:    Option<usize>,
-  pub title:         TitleMenu,
-  pub level_select:  LevelSelectMenu,
-  pub thank_you:     Option<ThankYouMenu>,
+  current:            MenuType,
+  window_size:        Size,
+  thumbnails:         Thumbnails,
+  pub load_level:     Option<usize>,
+  pub load_pack:      Option<usize>,
+  pub profile_action: Option<ProfileAction>,
+  pub title:          TitleMenu,
+  pub level_select:   LevelSelectMenu,
+  pub level_packs:    LevelPacksMenu,
+  pub profiles:       ProfilesMenu,
+  pub options:        Option<OptionsMenu>,
+  pub statistics:     Option<StatisticsMenu>,
+  pub achievements:   Option<AchievementsMenu>,
+  pub thank_you:      Option<ThankYouMenu>,
 }
 
 impl TitleMenuManager {
-  pub fn new(ctx: &mut Context, window_size: Size) -> GameResult<Self> {
+  pub fn new(ctx: &mut Context, window_size: Size, levels: &Vec<LevelInfo>, pack_names: &Vec<String>, profile_names: &Vec<String>, active_profile: usize) -> GameResult<Self> {
+    let mut title = TitleMenu::new(ctx, window_size.clone());
+    if pack_names.len() > 1 {
+      title.show_level_packs();
+    }
+    let mut thumbnails = Thumbnails::new();
+    let level_select   = LevelSelectMenu::new(ctx, window_size.clone(), levels, &mut thumbnails)?;
     Ok(Self {
-      current:           MenuType::Title,
-      load_level:        None,
-      title:             TitleMenu::new(ctx, window_size.clone()),
-      level_select:      LevelSelectMenu::new(ctx, window_size.clone())?,
-      thank_you:         None,
+      current:        MenuType::Title,
+      window_size:    window_size.clone(),
+      thumbnails,
+      load_level:     None,
+      load_pack:      None,
+      profile_action: None,
+      title,
+      level_select,
+      level_packs:    LevelPacksMenu::new(ctx, window_size.clone(), pack_names)?,
+      profiles:       ProfilesMenu::new(ctx, window_size.clone(), profile_names, active_profile)?,
+      options:        None,
+      statistics:     None,
+      achievements:   None,
+      thank_you:      None,
     })
   }
 
+  pub fn set_levels(&mut self, ctx: &mut Context, levels: &Vec<LevelInfo>) -> GameResult<()> {
+    self.level_select = LevelSelectMenu::new(ctx, self.window_size.clone(), levels, &mut self.thumbnails)?;
+    Ok(())
+  }
+
+  pub fn set_profiles(&mut self, ctx: &mut Context, profile_names: &Vec<String>, active_profile: usize) -> GameResult<()> {
+    self.profiles = ProfilesMenu::new(ctx, self.window_size.clone(), profile_names, active_profile)?;
+    Ok(())
+  }
+
+  pub fn set_statistics(&mut self, ctx: &mut Context, total: &LevelStats, levels: Vec<(String, LevelStats)>) -> GameResult<()> {
+    self.statistics = Some(StatisticsMenu::new(ctx, self.window_size.clone(), total, levels)?);
+    Ok(())
+  }
+
+  pub fn set_achievements(&mut self, ctx: &mut Context, achievements: Vec<AchievementInfo>) -> GameResult<()> {
+    self.achievements = Some(AchievementsMenu::new(ctx, self.window_size.clone(), achievements)?);
+    Ok(())
+  }
+
+  // Levels may have been changed in the editor
+  pub fn clear_thumbnails(&mut self) {
+    self.thumbnails.clear();
+  }
+
   pub fn in_title_menu(&self) -> bool {
     self.current == MenuType::Title
   }
@@ -54,6 +135,14 @@ impl TitleMenuManager {
     self.current == MenuType::LevelSelect
   }
 
+  pub fn in_level_packs_menu(&self) -> bool {
+    self.current == MenuType::LevelPacks
+  }
+
+  pub fn in_profiles_menu(&self) -> bool {
+    self.current == MenuType::Profiles
+  }
+
   pub fn in_thank_you_menu(&self) -> bool {
     self.current == MenuType::ThankYou
   }
@@ -66,6 +155,14 @@ impl TitleMenuManager {
     self.current = MenuType::LevelSelect;
   }
 
+  pub fn to_level_packs_menu(&mut self) {
+    self.current = MenuType::LevelPacks;
+  }
+
+  pub fn to_profiles_menu(&mut self) {
+    self.current = MenuType::Profiles;
+  }
+
   pub fn to_thank_you_menu(&mut self) {
     self.current = MenuType::ThankYou;
   }
@@ -82,16 +179,35 @@ impl TitleMenuManager {
     self.title.show_level_select();
   }
 
+  pub fn hide_level_select(&mut self) {
+    self.title.hide_level_select();
+  }
+
   pub fn is_level_select_available(&self) -> bool {
     self.title.is_level_select_available()
   }
 
+  pub fn is_level_packs_available(&self) -> bool {
+    self.title.is_level_packs_available()
+  }
+
   pub fn get_clicked(&self) -> &Option<ButtonType> {
     use self::MenuType::*;
     match &self.current {
-      Title       => self.title.get_clicked(),
-      LevelSelect => self.level_select.get_clicked(),
-      ThankYou    => if let Some(ty) = &self.thank_you {
+      Title        => self.title.get_clicked(),
+      LevelSelect  => self.level_select.get_clicked(),
+      LevelPacks   => self.level_packs.get_clicked(),
+      Profiles     => self.profiles.get_clicked(),
+      Options      => if let Some(options) = &self.options {
+        options.get_clicked()
+      } else { &None },
+      Statistics   => if let Some(statistics) = &self.statistics {
+        statistics.get_clicked()
+      } else { &None },
+      Achievements => if let Some(achievements) = &self.achievements {
+        achievements.get_clicked()
+      } else { &None },
+      ThankYou     => if let Some(ty) = &self.thank_you {
         ty.get_clicked()
       } else { &None }
     }
@@ -100,31 +216,119 @@ impl TitleMenuManager {
   pub fn mouse_down(&mut self, x: i32, y: i32) {
     use self::MenuType::*;
     match &mut self.current {
-      Title       => self.title.mouse_down(x, y),
-      LevelSelect => self.level_select.mouse_down(x, y),
-      ThankYou    => if let Some(ty) = &mut self.thank_you {
+      Title        => self.title.mouse_down(x, y),
+      LevelSelect  => self.level_select.mouse_down(x, y),
+      LevelPacks   => self.level_packs.mouse_down(x, y),
+      Profiles     => self.profiles.mouse_down(x, y),
+      Options      => if let Some(options) = &mut self.options {
+        options.mouse_down(x, y);
+      },
+      Statistics   => if let Some(statistics) = &mut self.statistics {
+        statistics.mouse_down(x, y);
+      },
+      Achievements => if let Some(achievements) = &mut self.achievements {
+        achievements.mouse_down(x, y);
+      },
+      ThankYou     => if let Some(ty) = &mut self.thank_you {
         ty.mouse_down(x, y);
       }
     };
   }
 
-  pub fn update(&mut self) -> GameResult<()> {
+  pub fn menu_input(&mut self, input: MenuInput) {
+    use self::MenuType::*;
+    match &mut self.current {
+      Title        => self.title.menu_input(input),
+      LevelSelect  => self.level_select.menu_input(input),
+      LevelPacks   => self.level_packs.menu_input(input),
+      Profiles     => self.profiles.menu_input(input),
+      Options      => if let Some(options) = &mut self.options {
+        options.menu_input(input);
+      },
+      Statistics   => if let Some(statistics) = &mut self.statistics {
+        statistics.menu_input(input);
+      },
+      Achievements => if let Some(achievements) = &mut self.achievements {
+        achievements.menu_input(input);
+      },
+      ThankYou     => if let Some(ty) = &mut self.thank_you {
+        ty.menu_input(input);
+      }
+    };
+  }
+
+  pub fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
     use self::ButtonType::*;
     use self::MenuType::*;
     let mut new_current = None;
     if let Some(clicked) = self.get_clicked().clone() {
       match clicked {
         TitleLevelSelect    => new_current = Some(MenuType::LevelSelect),
+        TitleLevelPacks     => new_current = Some(MenuType::LevelPacks),
+        TitleProfiles       => new_current = Some(MenuType::Profiles),
+        TitleOptions        => {
+          self.options = Some(OptionsMenu::new(ctx, self.window_size.clone())?);
+          new_current = Some(MenuType::Options);
+        },
+        TitleStatistics     => if self.statistics.is_some() {
+          new_current = Some(MenuType::Statistics);
+        },
+        TitleAchievements   => if self.achievements.is_some() {
+          new_current = Some(MenuType::Achievements);
+        },
+        OptionsBack         => new_current = Some(MenuType::Title),
         LevelSelectBack     => new_current = Some(MenuType::Title),
         LevelSelectLevel(i) => self.load_level = Some(i),
+        LevelSelectBoard(i) => self.level_select.show_leaderboard(ctx, i)?,
+        LevelSelectClose    => self.level_select.hide_leaderboard(),
+        LevelPacksBack      => new_current = Some(MenuType::Title),
+        LevelPacksPack(i)   => {
+          self.load_pack = Some(i);
+          new_current = Some(MenuType::Title);
+        },
+        ProfilesBack        => {
+          self.profiles.finish_rename();
+          new_current = Some(MenuType::Title);
+        },
+        ProfilesProfile(i)  => self.profile_action = Some(ProfileAction::Select(i)),
+        ProfilesNew         => self.profile_action = Some(ProfileAction::New),
+        ProfilesRename      => self.profile_action = Some(ProfileAction::Rename),
+        ProfilesCopy        => self.profile_action = Some(ProfileAction::Copy),
+        ProfilesDelete      => self.profile_action = Some(ProfileAction::Delete),
+        StatisticsBack      => new_current = Some(MenuType::Title),
+        StatisticsPrev      => if let Some(statistics) = &mut self.statistics {
+          statistics.prev_page(ctx)?;
+        },
+        StatisticsNext      => if let Some(statistics) = &mut self.statistics {
+          statistics.next_page(ctx)?;
+        },
+        AchievementsBack    => new_current = Some(MenuType::Title),
+        AchievementsPrev    => if let Some(achievements) = &mut self.achievements {
+          achievements.prev_page(ctx)?;
+        },
+        AchievementsNext    => if let Some(achievements) = &mut self.achievements {
+          achievements.next_page(ctx)?;
+        },
         ThankYouBack        => new_current = Some(MenuType::Title),
         _                   => (),
       };
     }
     match &self.current {
-      Title       => self.title.update()?,
-      LevelSelect => self.level_select.update()?,
-      ThankYou    => if let Some(ty) = &mut self.thank_you {
+      Title        => self.title.update()?,
+      LevelSelect  => self.level_select.update()?,
+      LevelPacks   => self.level_packs.update()?,
+      Profiles     => self.profiles.update()?,
+      Options      => if let Some(options) = &mut self.options {
+        options.apply_clicked(ctx)?;
+        options.update()?;
+      },
+      Statistics   => if let Some(statistics) = &mut self.statistics {
+        statistics.update()?;
+      },
+      Achievements => if let Some(achievements) = &mut self.achievements {
+        achievements.update()?;
+      },
+      ThankYou     => if let Some(ty) = &mut self.thank_you {
         ty.update()?;
       },
     };
@@ -137,9 +341,20 @@ impl TitleMenuManager {
   pub fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
     use self::MenuType::*;
     match &self.current {
-      Title       => self.title.draw(ctx)?,
-      LevelSelect => self.level_select.draw(ctx)?,
-      ThankYou    => if let Some(ty) = &mut self.thank_you {
+      Title        => self.title.draw(ctx)?,
+      LevelSelect  => self.level_select.draw(ctx)?,
+      LevelPacks   => self.level_packs.draw(ctx)?,
+      Profiles     => self.profiles.draw(ctx)?,
+      Options      => if let Some(options) = &mut self.options {
+        options.draw(ctx)?;
+      },
+      Statistics   => if let Some(statistics) = &mut self.statistics {
+        statistics.draw(ctx)?;
+      },
+      Achievements => if let Some(achievements) = &mut self.achievements {
+        achievements.draw(ctx)?;
+      },
+      ThankYou     => if let Some(ty) = &mut self.thank_you {
         ty.draw(ctx)?;
       },
     };
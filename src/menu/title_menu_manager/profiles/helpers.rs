@@ -0,0 +1,118 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+use noframe::geo::mask::misc::Side;
+
+use settings::res::fonts;
+use settings::buttons;
+use settings::menus::title::profiles::*;
+use menu::prelude::*;
+use color_rect::prelude::*;
+use text_box::prelude::*;
+use layout::Layout;
+
+pub fn new_color_rect(window_size: &Size) -> ColorRect {
+  ColorRectBuilder::new()
+    .size(window_size.clone())
+    .color([0.0, 0.0, 0.0, 1.0])
+    .build()
+}
+
+fn buttons_area(window_size: &Size) -> Rect {
+  let border_padding = Point::new(64.0, 32.0);
+  Layout::new(window_size).inset(border_padding.x, border_padding.y)
+}
+
+pub fn new_buttons(ctx: &mut Context, window_size: &Size, profile_names: &Vec<String>, active: usize) -> GameResult<Vec<Button>> {
+  let padding          = 24.0;
+  let buttons_area     = buttons_area(window_size);
+  let size             = Size::new(buttons_area.size().w / 2.0, 64.0);
+  let column_point     = Point::new(
+    buttons_area.center().x,
+    buttons_area.side(Side::Top)
+  );
+  let text_offset      = Point::new(-size.w / 2.0 + 32.0, 0.0);
+  let font             = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let mut buttons      = Vec::new();
+
+  for (i, name) in profile_names.iter().enumerate() {
+    let center = Point::new(
+      column_point.x,
+      column_point.y + size.h / 2.0 + (size.h + padding) * i as NumType
+    );
+    let label = if i == active {
+      format!("> {}", name)
+    } else {
+      name.clone()
+    };
+    let text = Text::new(ctx, &label, &font)?;
+    buttons.push(ButtonBuilder::new(ctx)
+                 .point(center.clone())
+                 .size(size.clone())
+                 .origin(Origin::Center)
+                 .button_type(ButtonType::ProfilesProfile(i))
+                 .animation_from(vec![::join_str(buttons::IMAGES, &format!("level_select{}.png", i % 3 + 1))], vec![1000])
+                 .text_from(
+                   center + text_offset.clone(),
+                   Size::new(text.width() as NumType, text.height() as NumType),
+                   Origin::CenterLeft,
+                   text
+                 ).build()?);
+  }
+
+  let actions = vec![
+    (ButtonType::ProfilesNew,    "New"),
+    (ButtonType::ProfilesRename, "Rename"),
+    (ButtonType::ProfilesCopy,   "Copy"),
+    (ButtonType::ProfilesDelete, "Delete"),
+  ];
+  let action_size = Size::new(160.0, 48.0);
+  let actions_width = (action_size.w + padding) * actions.len() as NumType - padding;
+  for (i, (button_type, label)) in actions.into_iter().enumerate() {
+    let center = Point::new(
+      buttons_area.center().x - actions_width / 2.0 + action_size.w / 2.0 + (action_size.w + padding) * i as NumType,
+      buttons_area.side(Side::Bottom) - action_size.h / 2.0
+    );
+    let text = Text::new(ctx, label, &font)?;
+    buttons.push(ButtonBuilder::new(ctx)
+                 .point(center.clone())
+                 .size(action_size.clone())
+                 .origin(Origin::Center)
+                 .button_type(button_type)
+                 .animation_from(vec![::join_str(buttons::IMAGES, "level_select1.png")], vec![1000])
+                 .text_from(
+                   center,
+                   Size::new(text.width() as NumType, text.height() as NumType),
+                   Origin::Center,
+                   text
+                 ).build()?);
+  }
+
+  buttons.push(ButtonBuilder::new(ctx)
+               .point(buttons_area.bottom_left())
+               .size(Size::new(64.0, 64.0))
+               .origin(Origin::BottomLeft)
+               .button_type(ButtonType::ProfilesBack)
+               .animation_from(vec![::join_str(buttons::IMAGES, "arrow.png")], vec![1000])
+               .build()?);
+
+  Ok(buttons)
+}
+
+pub fn new_rename_text(ctx: &mut Context, window_size: &Size, name: &str) -> GameResult<TextBox> {
+  let font = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let text = Text::new(ctx, &format!("Name: {}_", name), &font)?;
+  let area = buttons_area(window_size);
+  Ok(TextBoxBuilder::new()
+     .point(Point::new(area.center().x, area.side(Side::Bottom) - 96.0))
+     .size_from(text.width() as NumType + 32.0, text.height() as NumType + 16.0)
+     .origin(Origin::Center)
+     .bg_color(RENAME_BG_COLOR)
+     .text(text)
+     .text_color(RENAME_FONT_COLOR)
+     .text_origin(TextOrigin::Center)
+     .build())
+}
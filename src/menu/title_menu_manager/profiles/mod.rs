@@ -0,0 +1,118 @@
+mod helpers;
+
+use ggez::{
+  Context,
+  GameResult,
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use self::helpers::*;
+use settings::save::MAX_PROFILE_NAME_LEN;
+use menu::prelude::*;
+use color_rect::ColorRect;
+use text_box::TextBox;
+
+pub struct ProfilesMenu {
+  window_size: Size,
+  buttons:     Vec<Button>,
+  clicked:     Option<ButtonType>,
+  rect:        ColorRect,
+  rename:      Option<String>,
+  rename_text: Option<TextBox>,
+}
+
+impl ProfilesMenu {
+  pub fn new(ctx: &mut Context, size: Size, profile_names: &Vec<String>, active: usize) -> GameResult<Self> {
+    Ok(Self {
+      window_size: size.clone(),
+      buttons:     new_buttons(ctx, &size, profile_names, active)?,
+      clicked:     None,
+      rect:        new_color_rect(&size),
+      rename:      None,
+      rename_text: None,
+    })
+  }
+
+  pub fn is_renaming(&self) -> bool {
+    self.rename.is_some()
+  }
+
+  pub fn start_rename(&mut self, ctx: &mut Context, name: &str) -> GameResult<()> {
+    self.rename = Some(name.to_string());
+    self.update_rename_text(ctx)
+  }
+
+  pub fn text_input(&mut self, ctx: &mut Context, text: &str) -> GameResult<()> {
+    if let Some(rename) = &mut self.rename {
+      for c in text.chars().filter( |c| !c.is_control() ) {
+        if rename.chars().count() < MAX_PROFILE_NAME_LEN {
+          rename.push(c);
+        }
+      }
+    }
+    self.update_rename_text(ctx)
+  }
+
+  pub fn backspace(&mut self, ctx: &mut Context) -> GameResult<()> {
+    if let Some(rename) = &mut self.rename {
+      rename.pop();
+    }
+    self.update_rename_text(ctx)
+  }
+
+  // Returns the new name, unless it's empty
+  pub fn finish_rename(&mut self) -> Option<String> {
+    self.rename_text = None;
+    self.rename.take()
+      .map( |name| name.trim().to_string() )
+      .filter( |name| !name.is_empty() )
+  }
+
+  fn update_rename_text(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.rename_text = if let Some(rename) = &self.rename {
+      Some(new_rename_text(ctx, &self.window_size, rename)?)
+    } else { None };
+    Ok(())
+  }
+}
+
+impl Mask for ProfilesMenu {
+  fn point(&self)         -> &Point     { self.rect.point()     }
+  fn point_mut(&mut self) -> &mut Point { self.rect.point_mut() }
+  fn size(&self)          -> &Size      { self.rect.size()      }
+  fn origin(&self)        -> &Origin    { self.rect.origin()    }
+}
+
+impl Menu for ProfilesMenu {
+  fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+    self.rect.draw(ctx)?;
+    self.draw_menu(ctx)?;
+    if let Some(rename_text) = &self.rename_text {
+      rename_text.draw(ctx)?;
+    }
+    Ok(())
+  }
+
+  fn buttons(&self) -> Vec<&Button> {
+    self.buttons.iter().map( |button| button ).collect()
+  }
+  fn buttons_mut(&mut self) -> Vec<&mut Button> {
+    self.buttons.iter_mut().map( |button| button ).collect()
+  }
+  fn animation(&self) -> Option<&Animation> {
+    None
+  }
+  fn animation_mut(&mut self) -> Option<&mut Animation> {
+    None
+  }
+  fn clicked(&mut self, button_type: ButtonType) {
+    self.clicked = Some(button_type);
+  }
+  fn get_clicked(&self) -> &Option<ButtonType> {
+    &self.clicked
+  }
+  fn clear_clicked(&mut self) {
+    self.clicked = None;
+  }
+}
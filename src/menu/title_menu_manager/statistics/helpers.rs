@@ -0,0 +1,174 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ Text, Font },
+};
+use noframe::geo::prelude::*;
+use noframe::geo::mask::misc::Side;
+
+use settings::res::fonts;
+use settings::buttons;
+use settings::menus::title::statistics::*;
+use menu::prelude::*;
+use menu::stats::prelude::{ StatsText, TextOrigin };
+use persons::children::ChildType;
+use score::format_play_time;
+use score::lifetime::LevelStats;
+use color_rect::prelude::*;
+use layout::Layout;
+
+// Where the breakdown's columns start, as parts of its width:
+// level, time, jumps, the commands of every child type, resets, saved and cleared
+const COLUMNS: [f32; 9] = [0.0, 0.3, 0.42, 0.51, 0.6, 0.69, 0.78, 0.86, 0.93];
+
+pub fn new_color_rect(window_size: &Size) -> ColorRect {
+  ColorRectBuilder::new()
+    .size(window_size.clone())
+    .color([0.0, 0.0, 0.0, 1.0])
+    .build()
+}
+
+fn area(window_size: &Size) -> Rect {
+  let border_padding = Point::new(64.0, 32.0);
+  Layout::new(window_size).inset(border_padding.x, border_padding.y)
+}
+
+// Below the title, where the totals and the breakdown start
+fn rows_top(window_size: &Size) -> NumType {
+  area(window_size).side(Side::Top) + TITLE_FONT_SIZE as NumType + ROW_HEIGHT
+}
+
+fn child_types() -> [ChildType; 3] {
+  use self::ChildType::*;
+  let mut children = [Larry, Thing, Bloat];
+  children.sort();
+  children
+}
+
+pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> GameResult<Vec<Button>> {
+  let area       = area(window_size);
+  let arrow_size = Size::new(48.0, 48.0);
+  let page_x     = area.side(Side::Left) + TOTALS_WIDTH + (area.size().w - TOTALS_WIDTH) / 2.0;
+  let page_y     = area.side(Side::Bottom) - arrow_size.h / 2.0;
+  let mut btns   = Vec::new();
+
+  btns.push(ButtonBuilder::new(ctx)
+            .point(Point::new(page_x - arrow_size.w, page_y))
+            .size(arrow_size.clone())
+            .origin(Origin::Center)
+            .button_type(ButtonType::StatisticsPrev)
+            .animation_from(vec![::join_str(buttons::IMAGES, "child_arrow.png")], vec![1000])
+            .facing(Facing::Left)
+            .build()?);
+  btns.push(ButtonBuilder::new(ctx)
+            .point(Point::new(page_x + arrow_size.w, page_y))
+            .size(arrow_size)
+            .origin(Origin::Center)
+            .button_type(ButtonType::StatisticsNext)
+            .animation_from(vec![::join_str(buttons::IMAGES, "child_arrow.png")], vec![1000])
+            .build()?);
+  btns.push(ButtonBuilder::new(ctx)
+            .point(area.bottom_left())
+            .size(Size::new(64.0, 64.0))
+            .origin(Origin::BottomLeft)
+            .button_type(ButtonType::StatisticsBack)
+            .animation_from(vec![::join_str(buttons::IMAGES, "arrow.png")], vec![1000])
+            .build()?);
+
+  Ok(btns)
+}
+
+// The title and the totals of all levels, as labels with their values right-aligned
+pub fn new_totals_texts(ctx: &mut Context, window_size: &Size, total: &LevelStats) -> GameResult<Vec<StatsText>> {
+  let font       = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let title_font = Font::new_px(ctx, fonts::DEFAULT, TITLE_FONT_SIZE)?;
+  let area       = area(window_size);
+  let left       = area.side(Side::Left);
+  let top        = rows_top(window_size);
+  let mut texts  = Vec::new();
+
+  texts.push(StatsText::new(
+    Text::new(ctx, "Statistics", &title_font)?,
+    area.top_left(),
+    TextOrigin::Left,
+    Some(FONT_COLOR)
+  ));
+
+  let mut lines = vec![
+    ("Play Time".to_string(),      format_play_time(total.ticks)),
+    ("Levels Cleared".to_string(), total.cleared.to_string()),
+    ("Persons Saved".to_string(),  total.saved.to_string()),
+    ("Jumps".to_string(),          total.jumps.to_string()),
+    ("Resets".to_string(),         total.resets.to_string()),
+    ("Commands".to_string(),       total.commands().to_string()),
+  ];
+  for &child in child_types().iter() {
+    lines.push((format!("  {}", child.name()), total.commands_for(child).to_string()));
+  }
+  for (row, (label, value)) in lines.iter().enumerate() {
+    let y = top + ROW_HEIGHT * row as NumType;
+    texts.push(StatsText::new(Text::new(ctx, label, &font)?, Point::new(left, y), TextOrigin::Left, Some(HEADER_COLOR)));
+    texts.push(StatsText::new(
+      Text::new(ctx, value, &font)?,
+      Point::new(left + TOTALS_WIDTH - ROW_HEIGHT * 2.0, y),
+      TextOrigin::Right,
+      Some(FONT_COLOR)
+    ));
+  }
+  Ok(texts)
+}
+
+// One page of the per-level breakdown, right of the totals
+pub fn new_page_texts(
+  ctx: &mut Context,
+  window_size: &Size,
+  levels: &[(String, LevelStats)],
+  page: usize,
+  page_count: usize
+) -> GameResult<Vec<StatsText>> {
+  let font      = Font::new_px(ctx, fonts::DEFAULT, FONT_SIZE)?;
+  let area      = area(window_size);
+  let left      = area.side(Side::Left) + TOTALS_WIDTH;
+  let width     = area.size().w - TOTALS_WIDTH;
+  let top       = rows_top(window_size);
+  let mut texts = Vec::new();
+
+  if levels.is_empty() {
+    texts.push(StatsText::new(
+      Text::new(ctx, "No levels played yet.", &font)?,
+      Point::new(left, top),
+      TextOrigin::Left,
+      Some(HEADER_COLOR)
+    ));
+    return Ok(texts);
+  }
+
+  let mut header = vec!["Level".to_string(), "Time".to_string(), "Jumps".to_string()];
+  header.extend(child_types().iter().map( |child| child.name() ));
+  header.extend(vec!["Resets".to_string(), "Saved".to_string(), "Cleared".to_string()]);
+  let rows = levels.iter().map( |(label, stats)| {
+    let mut cells = vec![label.clone(), format_play_time(stats.ticks), stats.jumps.to_string()];
+    cells.extend(child_types().iter().map( |&child| stats.commands_for(child).to_string() ));
+    cells.extend(vec![stats.resets.to_string(), stats.saved.to_string(), stats.cleared.to_string()]);
+    cells
+  }).collect::<Vec<Vec<String>>>();
+
+  for (row, cells) in Some(&header).into_iter().chain(rows.iter()).enumerate() {
+    let color = if row == 0 { HEADER_COLOR } else { FONT_COLOR };
+    let y     = top + ROW_HEIGHT * row as NumType;
+    for (column, cell) in cells.iter().enumerate() {
+      let point = Point::new(left + COLUMNS[column] * width, y);
+      texts.push(StatsText::new(Text::new(ctx, cell, &font)?, point, TextOrigin::Left, Some(color)));
+    }
+  }
+
+  if page_count > 1 {
+    texts.push(StatsText::new(
+      Text::new(ctx, &format!("Page {}/{}", page + 1, page_count), &font)?,
+      Point::new(left + width / 2.0, area.side(Side::Bottom) - 96.0),
+      TextOrigin::Center,
+      Some(HEADER_COLOR)
+    ));
+  }
+  Ok(texts)
+}
@@ -8,6 +8,7 @@ use settings::menus::*;
 use settings::buttons;
 use animation::prelude::*;
 use menu::buttons::prelude::*;
+use layout::Layout;
 
 pub fn new_animation(ctx: &mut Context) -> GameResult<Animation> {
   let mut images = Vec::new();
@@ -30,7 +31,7 @@ pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> GameResult<Vec<Butt
   let mut btns = Vec::new();
 
   btns.push(ButtonBuilder::new(ctx)
-            .point_from(padding.x, window_size.h - padding.y)
+            .point(Layout::new(window_size).at(Origin::BottomLeft, padding.x, -padding.y))
             .size_from(64.0, 64.0)
             .origin(Origin::BottomLeft)
             .button_type(ButtonType::ThankYouBack)
@@ -1,10 +1,16 @@
-use ggez::Context;
+use ggez::{
+  Context,
+  graphics::{ Text, Font },
+};
 use noframe::geo::prelude::*;
 
+use settings::res::fonts;
 use settings::menus::IMAGES;
+use settings::menus::title::level_packs;
 use settings::buttons;
 use menu::prelude::*;
 use animation::Animation;
+use layout::Layout;
 
 pub fn new_animation(ctx: &mut Context) -> Animation {
   Animation::new(
@@ -15,6 +21,7 @@ pub fn new_animation(ctx: &mut Context) -> Animation {
 }
 
 pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> Vec<Button> {
+  let layout = Layout::new(window_size);
   let offset_y = 64.0;
   let padding = 64.0;
   let size = Size::new(64.0, 64.0);
@@ -22,26 +29,102 @@ pub fn new_buttons(ctx: &mut Context, window_size: &Size) -> Vec<Button> {
   let mut btns = Vec::new();
 
   btns.push(ButtonBuilder::new(ctx)
-            .point(window_size.center() + Point::new(size.w + padding, offset_y))
+            .point(layout.at(Origin::Center, size.w + padding, offset_y))
             .size(size.clone())
             .origin(Origin::Center)
             .button_type(ButtonType::TitleStart)
             .animation_from(vec![::join_str(buttons::IMAGES, "play.png")], vec![1000])
             .build().expect("Should build TitleStart Button"));
   btns.push(ButtonBuilder::new(ctx)
-            .point(window_size.center() + Point::new(0.0, offset_y))
+            .point(layout.at(Origin::Center, 0.0, offset_y))
             .size(size.clone())
             .origin(Origin::Center)
             .button_type(ButtonType::TitleLevelSelect)
             .animation_from(vec![::join_str(buttons::IMAGES, "level_menu.png")], vec![1000])
             .build().expect("Should build TitleLevelSelect Button"));
   btns.push(ButtonBuilder::new(ctx)
-            .point(window_size.center() + Point::new(-(size.w + padding), offset_y))
+            .point(layout.at(Origin::Center, -(size.w + padding), offset_y))
             .size(size.clone())
             .origin(Origin::Center)
             .button_type(ButtonType::TitleQuit)
             .animation_from(vec![::join_str(buttons::IMAGES, "exit.png")], vec![1000])
             .build().expect("Should build TitleQuit Button"));
 
+  let level_packs_point  = layout.at(Origin::Center, 0.0, offset_y + size.h + padding * 0.5);
+  let profiles_point     = level_packs_point.clone() + Point::new(0.0, size.h * 0.75 + padding * 0.25);
+  let options_point      = profiles_point.clone()    + Point::new(0.0, size.h * 0.75 + padding * 0.25);
+  let statistics_point   = options_point.clone()     + Point::new(0.0, size.h * 0.75 + padding * 0.25);
+  let achievements_point = statistics_point.clone()  + Point::new(0.0, size.h * 0.75 + padding * 0.25);
+  let font = Font::new_px(ctx, fonts::DEFAULT, level_packs::FONT_SIZE).expect("Should load font");
+  let text = Text::new(ctx, "Level Packs", &font).expect("Should create text");
+  btns.push(ButtonBuilder::new(ctx)
+            .point(level_packs_point.clone())
+            .size_from((size.w + padding) * 2.0 + size.w, size.h * 0.75)
+            .origin(Origin::Center)
+            .button_type(ButtonType::TitleLevelPacks)
+            .animation_from(vec![::join_str(buttons::IMAGES, "level_select1.png")], vec![1000])
+            .text_from(
+              level_packs_point,
+              Size::new(text.width() as NumType, text.height() as NumType),
+              Origin::Center,
+              text
+            ).build().expect("Should build TitleLevelPacks Button"));
+
+  let text = Text::new(ctx, "Profiles", &font).expect("Should create text");
+  btns.push(ButtonBuilder::new(ctx)
+            .point(profiles_point.clone())
+            .size_from((size.w + padding) * 2.0 + size.w, size.h * 0.75)
+            .origin(Origin::Center)
+            .button_type(ButtonType::TitleProfiles)
+            .animation_from(vec![::join_str(buttons::IMAGES, "level_select2.png")], vec![1000])
+            .text_from(
+              profiles_point,
+              Size::new(text.width() as NumType, text.height() as NumType),
+              Origin::Center,
+              text
+            ).build().expect("Should build TitleProfiles Button"));
+
+  let text = Text::new(ctx, "Options", &font).expect("Should create text");
+  btns.push(ButtonBuilder::new(ctx)
+            .point(options_point.clone())
+            .size_from((size.w + padding) * 2.0 + size.w, size.h * 0.75)
+            .origin(Origin::Center)
+            .button_type(ButtonType::TitleOptions)
+            .animation_from(vec![::join_str(buttons::IMAGES, "level_select3.png")], vec![1000])
+            .text_from(
+              options_point,
+              Size::new(text.width() as NumType, text.height() as NumType),
+              Origin::Center,
+              text
+            ).build().expect("Should build TitleOptions Button"));
+
+  let text = Text::new(ctx, "Statistics", &font).expect("Should create text");
+  btns.push(ButtonBuilder::new(ctx)
+            .point(statistics_point.clone())
+            .size_from((size.w + padding) * 2.0 + size.w, size.h * 0.75)
+            .origin(Origin::Center)
+            .button_type(ButtonType::TitleStatistics)
+            .animation_from(vec![::join_str(buttons::IMAGES, "level_select1.png")], vec![1000])
+            .text_from(
+              statistics_point,
+              Size::new(text.width() as NumType, text.height() as NumType),
+              Origin::Center,
+              text
+            ).build().expect("Should build TitleStatistics Button"));
+
+  let text = Text::new(ctx, "Achievements", &font).expect("Should create text");
+  btns.push(ButtonBuilder::new(ctx)
+            .point(achievements_point.clone())
+            .size_from((size.w + padding) * 2.0 + size.w, size.h * 0.75)
+            .origin(Origin::Center)
+            .button_type(ButtonType::TitleAchievements)
+            .animation_from(vec![::join_str(buttons::IMAGES, "level_select2.png")], vec![1000])
+            .text_from(
+              achievements_point,
+              Size::new(text.width() as NumType, text.height() as NumType),
+              Origin::Center,
+              text
+            ).build().expect("Should build TitleAchievements Button"));
+
   btns
 }
@@ -15,6 +15,7 @@ use menu::prelude::*;
 use animation::Animation;
 use text_box::prelude::*;
 use score::Score;
+use layout::Layout;
 
 pub struct TitleMenu {
   point:             Point,
@@ -24,7 +25,9 @@ pub struct TitleMenu {
   buttons:           Vec<Button>,
   clicked:           Option<ButtonType>,
   show_level_select: bool,
+  show_level_packs:  bool,
   score_text:        Option<TextBox>,
+  profile_text:      Option<TextBox>,
 }
 
 impl TitleMenu {
@@ -37,7 +40,9 @@ impl TitleMenu {
       buttons:           new_buttons(ctx, &size),
       clicked:           None,
       show_level_select: false,
+      show_level_packs:  false,
       score_text:        None,
+      profile_text:      None,
     }
   }
 
@@ -49,11 +54,19 @@ impl TitleMenu {
     self.show_level_select
   }
 
+  pub fn show_level_packs(&mut self) {
+    self.show_level_packs = true;
+  }
+
+  pub fn is_level_packs_available(&self) -> bool {
+    self.show_level_packs
+  }
+
   pub fn display_score(&mut self, ctx: &mut Context, score: &Score) -> GameResult<()> {
     let font = Font::new(ctx, fonts::DEFAULT, TOTAL_SCORE_FONT_SIZE)?;
     self.score_text = Some(
       TextBoxBuilder::new()
-      .point_from(8.0, 8.0)
+      .point(Layout::new(&self.size).at(Origin::TopLeft, 8.0, 8.0))
       .text(Text::new(ctx, &format!("Total Best Score: {}", score), &font)?)
       .text_color(HIGHSCORE_COLOR)
       .build()
@@ -61,10 +74,37 @@ impl TitleMenu {
     Ok(())
   }
 
+  pub fn hide_score(&mut self) {
+    self.score_text = None;
+  }
+
+  pub fn hide_level_select(&mut self) {
+    self.show_level_select = false;
+  }
+
+  pub fn display_profile(&mut self, ctx: &mut Context, name: &str) -> GameResult<()> {
+    let font = Font::new(ctx, fonts::DEFAULT, profiles::NAME_FONT_SIZE)?;
+    let text = Text::new(ctx, &format!("Profile: {}", name), &font)?;
+    self.profile_text = Some(
+      TextBoxBuilder::new()
+      .point(Layout::new(&self.size).at(Origin::TopRight, -8.0, 8.0))
+      .size_from(text.width() as NumType, text.height() as NumType)
+      .origin(Origin::TopRight)
+      .text(text)
+      .text_color(HIGHSCORE_COLOR)
+      .text_origin(TextOrigin::TopRight)
+      .build()
+    );
+    Ok(())
+  }
+
   fn draw_score(&mut self, ctx: &mut Context) -> GameResult<()> {
     if let Some(score) = &self.score_text {
       score.draw(ctx)?;
     }
+    if let Some(profile) = &self.profile_text {
+      profile.draw(ctx)?;
+    }
     Ok(())
   }
 }
@@ -85,17 +125,22 @@ impl Menu for TitleMenu {
 
   fn buttons(&self) -> Vec<&Button> {
     self.buttons.iter()
-      .filter( |button| if let ButtonType::TitleLevelSelect = button.button_type {
-        self.show_level_select
-      } else { true })
+      .filter( |button| match button.button_type {
+        ButtonType::TitleLevelSelect => self.show_level_select,
+        ButtonType::TitleLevelPacks  => self.show_level_packs,
+        _                            => true,
+      })
       .collect()
   }
   fn buttons_mut(&mut self) -> Vec<&mut Button> {
     let show_level_select = self.show_level_select;
+    let show_level_packs  = self.show_level_packs;
     self.buttons.iter_mut()
-      .filter( |button| if let ButtonType::TitleLevelSelect = button.button_type {
-        show_level_select
-      } else { true })
+      .filter( |button| match button.button_type {
+        ButtonType::TitleLevelSelect => show_level_select,
+        ButtonType::TitleLevelPacks  => show_level_packs,
+        _                            => true,
+      })
       .collect()
   }
   fn animation(&self) -> Option<&Animation> {
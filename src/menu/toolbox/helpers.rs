@@ -7,10 +7,11 @@ use menu::buttons::prelude::*;
 use super::Closeup;
 use animation::prelude::*;
 use persons::children::ChildType;
+use layout::Layout;
 
-pub fn new_next_level_button(ctx: &mut Context, point: &Point, size: &Size) -> Button {
+pub fn new_next_level_button(ctx: &mut Context, layout: &Layout) -> Button {
   ButtonBuilder::new(ctx)
-    .point_from(size.w - 160.0, point.y)
+    .point(layout.at(Origin::TopRight, -160.0, 0.0))
     .size_from(128.0, 64.0)
     .origin(Origin::TopLeft)
     .button_type(ButtonType::NextLevel)
@@ -19,7 +20,7 @@ pub fn new_next_level_button(ctx: &mut Context, point: &Point, size: &Size) -> B
     .build().expect("Should build NextLevel Button")
 }
 
-pub fn new_buttons(ctx: &mut Context, point: &Point, children: &Vec<ChildType>) -> Vec<Button> {
+pub fn new_buttons(ctx: &mut Context, layout: &Layout, children: &Vec<ChildType>) -> Vec<Button> {
   use self::ChildType::*;
   use self::ButtonType::*;
 
@@ -45,10 +46,10 @@ pub fn new_buttons(ctx: &mut Context, point: &Point, children: &Vec<ChildType>)
   }
 
   button_types.iter().enumerate().map( |(i, button_type)| {
-    let x = offset + spacing * i as NumType;
+    let point = layout.at(Origin::TopLeft, offset + spacing * i as NumType, 0.0);
     if i % 2 == 0 {
       ButtonBuilder::new(ctx)
-        .point_from(x, point.y)
+        .point(point)
         .size_from(64.0, 64.0)
         .origin(Origin::TopLeft)
         .button_type(button_type.clone())
@@ -57,7 +58,7 @@ pub fn new_buttons(ctx: &mut Context, point: &Point, children: &Vec<ChildType>)
         .build()
     } else {
       ButtonBuilder::new(ctx)
-        .point_from(x, point.y)
+        .point(point)
         .size_from(64.0, 64.0)
         .origin(Origin::TopLeft)
         .button_type(button_type.clone())
@@ -67,14 +68,14 @@ pub fn new_buttons(ctx: &mut Context, point: &Point, children: &Vec<ChildType>)
   }).collect()
 }
 
-pub fn new_closeups(ctx: &mut Context, point: &Point, children: &Vec<ChildType>) -> Vec<Closeup> {
+pub fn new_closeups(ctx: &mut Context, layout: &Layout, children: &Vec<ChildType>) -> Vec<Closeup> {
   use self::ChildType::*;
 
   let offset  = 96.0;
   let spacing = 256.0;
 
   children.iter().enumerate().map( |(i, child_type)| {
-    let x = offset + spacing * i as NumType;
+    let point = layout.at(Origin::TopLeft, offset + spacing * i as NumType, 0.0);
     let (images, intervals) = match child_type {
       Larry => (
         vec![::join_str(child::IMAGES, "child_1_closeup_2.png")],
@@ -90,12 +91,13 @@ pub fn new_closeups(ctx: &mut Context, point: &Point, children: &Vec<ChildType>)
       ),
     };
     Closeup::new(
-      Point::new(x, point.y),
+      point,
       Animation::new(
         ctx,
         images,
         intervals
-      )
+      ),
+      *child_type
     )
   }).collect()
 }
@@ -6,32 +6,47 @@ use ggez::{
   graphics
 };
 use noframe::geo::prelude::*;
+use noframe::entity::Entity;
 
 use self::helpers::*;
 use settings::res;
 use settings::fonts::*;
+use settings::child::{ SELECTED_COLOR, SELECTED_PADDING };
 use super::Menu;
 use super::buttons::Button;
 use super::ButtonType;
 use animation::Animation;
 use animation::Facing;
+use color_rect::ColorRect;
 use persons::children::ChildType;
+use layout::Layout;
 
 pub struct Closeup {
-  pub point:     Point,
-  pub size:      Size,
-  pub animation: Animation,
+  pub point:      Point,
+  pub size:       Size,
+  pub animation:  Animation,
+  pub child_type: ChildType,
 }
 
 impl Closeup {
-  pub fn new(point: Point, animation: Animation) -> Self {
+  pub fn new(point: Point, animation: Animation, child_type: ChildType) -> Self {
     Self {
       point,
       size: Size::new(64.0, 64.0),
       animation,
+      child_type,
     }
   }
 
+  pub fn draw_highlight(&self, ctx: &mut Context) -> GameResult<()> {
+    ColorRect::new(
+      Point::new(self.point.x - SELECTED_PADDING, self.point.y - SELECTED_PADDING),
+      Size::new(self.size.w + SELECTED_PADDING * 2.0, self.size.h + SELECTED_PADDING * 2.0),
+      Origin::TopLeft,
+      SELECTED_COLOR
+    ).draw(ctx)
+  }
+
   pub fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
     self.animation.draw(ctx, &self.point, &self.size, &Facing::Right)
   }
@@ -44,6 +59,7 @@ pub struct ToolboxMenu {
   buttons:                 Vec<Button>,
   clicked:                 Option<ButtonType>,
   pub closeups:            Vec<Closeup>,
+  selected:                Option<ChildType>,
   to_save_amount:          usize,
   previous_to_save_amount: usize,
   next_level_button:       Button,
@@ -54,6 +70,7 @@ pub struct ToolboxMenu {
 impl ToolboxMenu {
   pub fn new(ctx: &mut Context, point: Point, size: Size, mut children: Vec<ChildType>) -> Self {
     children.sort();
+    let layout = Layout::from_rect(point.clone(), &size);
     Self {
       point:                   point.clone(),
       size:                    size.clone(),
@@ -62,10 +79,11 @@ impl ToolboxMenu {
       previous_to_save_amount: 0,
       font:                    graphics::Font::new(ctx, ::join_str(res::FONTS, "vcr_osd_mono.ttf"), TO_SAVE_FONT_SIZE).expect("Should load font"),
       to_save_text:            None,
-      next_level_button:       new_next_level_button(ctx, &point, &size),
-      buttons:                 new_buttons(ctx, &point, &children),
+      next_level_button:       new_next_level_button(ctx, &layout),
+      buttons:                 new_buttons(ctx, &layout, &children),
       clicked:                 None,
-      closeups:                new_closeups(ctx, &point, &children),
+      closeups:                new_closeups(ctx, &layout, &children),
+      selected:                None,
     }
   }
 
@@ -73,6 +91,14 @@ impl ToolboxMenu {
     self.to_save_amount = to_save_amount;
   }
 
+  pub fn set_selected(&mut self, selected: Option<ChildType>) {
+    self.selected = selected;
+  }
+
+  pub fn next_level_button(&self) -> &Button {
+    &self.next_level_button
+  }
+
   fn get_to_save_text(&mut self, ctx: &mut Context) -> GameResult<&Option<graphics::Text>> {
     if let None = self.to_save_text {
       self.to_save_text = Some(graphics::Text::new(ctx, &format!("Saving: {}", self.to_save_amount), &self.font)?);
@@ -87,6 +113,9 @@ impl ToolboxMenu {
 
   fn draw_closeups(&mut self, ctx: &mut Context) -> GameResult<()> {
     for closeup in &mut self.closeups {
+      if Some(closeup.child_type) == self.selected {
+        closeup.draw_highlight(ctx)?;
+      }
       closeup.draw(ctx)?;
     }
     Ok(())
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use ggez::{
+  Context,
+  GameResult,
+  audio::Source,
+};
+use noframe::deltatime::Deltatime;
+
+use settings::res;
+use settings::music::*;
+use config;
+
+struct Track {
+  source: Source,
+  fade:   f32,  // 0.0 (silent) to 1.0 (full volume)
+}
+
+// Plays one track at a time, crossfading between tracks.
+// A track that is faded out is only paused, so it resumes where it left off when it's played again.
+// The config's `muted` pauses every track, see `apply_mute`.
+pub struct MusicManager {
+  tracks:  HashMap<String, Track>,
+  current: Option<String>,
+  missing: Vec<String>,
+  dt:      Deltatime,
+}
+
+impl MusicManager {
+  pub fn new() -> Self {
+    Self {
+      tracks:  HashMap::new(),
+      current: None,
+      missing: Vec::new(),
+      dt:      Deltatime::new(),
+    }
+  }
+
+  // Pauses or resumes the tracks after the config's `muted` changed
+  pub fn apply_mute(&self) {
+    let muted = is_muted();
+    for (name, track) in &self.tracks {
+      if muted {
+        track.source.pause();
+      } else if Some(name) == self.current.as_ref() || track.fade > 0.0 {
+        track.source.resume();
+      }
+    }
+  }
+
+  // Does nothing if the track (or its fallback) is already playing.
+  pub fn play(&mut self, ctx: &mut Context, name: &str) -> GameResult<()> {
+    let name = if let Some(name) = self.resolve(ctx, name)? {
+      name
+    } else {
+      return Ok(());
+    };
+    if Some(&name) == self.current.as_ref() { return Ok(()); }
+    if let Some(track) = self.tracks.get(&name) {
+      if !is_muted() {
+        track.source.resume();
+      }
+    }
+    self.current = Some(name);
+    Ok(())
+  }
+
+  // Fades out the current track.
+  pub fn stop(&mut self) {
+    self.current = None;
+  }
+
+  pub fn update(&mut self) {
+    let step = self.dt.secs() / CROSSFADE_SECS;
+    let volume = config::with( |c| c.volume );
+    for (name, track) in &mut self.tracks {
+      let was_audible = track.fade > 0.0;
+      track.fade = if Some(name) == self.current.as_ref() {
+        (track.fade + step).min(1.0)
+      } else {
+        (track.fade - step).max(0.0)
+      };
+      track.source.set_volume(track.fade * volume);
+      if was_audible && track.fade == 0.0 {
+        track.source.pause();
+      }
+    }
+    self.dt.update();
+  }
+
+  // Returns the name of the loaded track to play for `name`,
+  // falling back to the track without its variant suffix if the file is missing.
+  fn resolve(&mut self, ctx: &mut Context, name: &str) -> GameResult<Option<String>> {
+    let mut candidates = vec![name.to_string()];
+    if name.ends_with(VARIANT_SUFFIX) {
+      candidates.push(name[.. name.len() - VARIANT_SUFFIX.len()].to_string());
+    }
+    for candidate in candidates {
+      if self.tracks.contains_key(&candidate) {
+        return Ok(Some(candidate));
+      }
+      if self.missing.contains(&candidate) { continue; }
+      match Source::new(ctx, format!("{}{}.{}", res::AUDIO, candidate, FORMAT)) {
+        Ok(mut source) => {
+          source.set_volume(0.0);
+          source.set_repeat(true);
+          source.play()?;
+          if is_muted() { source.pause(); }
+          self.tracks.insert(candidate.clone(), Track { source, fade: 0.0 });
+          return Ok(Some(candidate));
+        },
+        Err(e) => {
+          eprintln!("Couldn't load music track '{}': {}", candidate, e);
+          self.missing.push(candidate);
+        }
+      }
+    }
+    Ok(None)
+  }
+}
+
+fn is_muted() -> bool {
+  config::with( |c| c.muted )
+}
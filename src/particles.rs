@@ -0,0 +1,159 @@
+use std::f32::consts::PI;
+
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ self, Image, DrawParam, spritebatch::SpriteBatch },
+};
+use noframe::geo::prelude::*;
+use noframe::color::Color;
+use rand::Rng;
+
+use settings::particles::*;
+
+// Everything in a level that gives off particles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParticleEffect {
+  Dust,     // A person lands or starts walking
+  Burst,    // A JumpPad launches a person
+  Sparkle,  // A person enters the Goal
+  Puff,     // A Door opens
+}
+
+// How an effect's particles are spawned; every range is `(min, max)`.
+// Angles are in degrees, where 0 points right and -90 points up.
+struct Emitter {
+  count:    usize,
+  color:    Color,
+  angle:    (f32, f32),
+  speed:    (f32, f32),
+  lifetime: (f32, f32),  // Seconds
+  size:     (f32, f32),
+  gravity:  f32,         // Added to the y velocity per second; negative values float up
+}
+
+impl ParticleEffect {
+  fn emitter(&self) -> Emitter {
+    use self::ParticleEffect::*;
+    match self {
+      Dust    => Emitter {
+        count:    6,
+        color:    [0.8, 0.75, 0.65, 0.8],
+        angle:    (-170.0, -10.0),
+        speed:    (20.0, 60.0),
+        lifetime: (0.3, 0.6),
+        size:     (3.0, 5.0),
+        gravity:  40.0,
+      },
+      Burst   => Emitter {
+        count:    16,
+        color:    [0.9, 0.9, 1.0, 0.9],
+        angle:    (-120.0, -60.0),
+        speed:    (150.0, 300.0),
+        lifetime: (0.4, 0.8),
+        size:     (3.0, 6.0),
+        gravity:  400.0,
+      },
+      Sparkle => Emitter {
+        count:    20,
+        color:    [1.0, 0.95, 0.5, 1.0],
+        angle:    (-180.0, 180.0),
+        speed:    (40.0, 120.0),
+        lifetime: (0.6, 1.2),
+        size:     (2.0, 4.0),
+        gravity:  -20.0,
+      },
+      Puff    => Emitter {
+        count:    12,
+        color:    [0.7, 0.7, 0.7, 0.7],
+        angle:    (-180.0, 180.0),
+        speed:    (30.0, 80.0),
+        lifetime: (0.5, 0.9),
+        size:     (5.0, 9.0),
+        gravity:  -10.0,
+      },
+    }
+  }
+}
+
+struct Particle {
+  point:    Point,
+  velocity: Point,
+  gravity:  f32,
+  age:      f32,
+  lifetime: f32,
+  size:     f32,
+  color:    Color,
+}
+
+// All particles of a level. They are drawn as a single batch of tinted pixels,
+// fading out over their lifetime.
+pub struct Particles {
+  particles:   Vec<Particle>,
+  spritebatch: SpriteBatch,
+}
+
+impl Particles {
+  pub fn new(ctx: &mut Context) -> GameResult<Self> {
+    Ok(Self {
+      particles:   Vec::new(),
+      spritebatch: SpriteBatch::new(Image::solid(ctx, 1, graphics::WHITE)?),
+    })
+  }
+
+  pub fn emit(&mut self, effect: ParticleEffect, point: &Point) {
+    let emitter = effect.emitter();
+    let count = emitter.count.min(MAX_PARTICLES - self.particles.len().min(MAX_PARTICLES));
+    let mut rng = ::rand::thread_rng();
+    for _ in 0 .. count {
+      let angle = random_between(&mut rng, emitter.angle) * PI / 180.0;
+      let speed = random_between(&mut rng, emitter.speed);
+      self.particles.push(Particle {
+        point:    point.clone(),
+        velocity: Point::new(angle.cos() * speed, angle.sin() * speed),
+        gravity:  emitter.gravity,
+        age:      0.0,
+        lifetime: random_between(&mut rng, emitter.lifetime),
+        size:     random_between(&mut rng, emitter.size),
+        color:    emitter.color,
+      });
+    }
+  }
+
+  pub fn update(&mut self, secs: f32) {
+    for particle in &mut self.particles {
+      particle.velocity.y += particle.gravity * secs;
+      particle.point.x    += particle.velocity.x * secs;
+      particle.point.y    += particle.velocity.y * secs;
+      particle.age        += secs;
+    }
+    self.particles.retain( |particle| particle.age < particle.lifetime );
+  }
+
+  pub fn clear(&mut self) {
+    self.particles.clear();
+  }
+
+  pub fn draw_offset(&mut self, ctx: &mut Context, offset: &Point) -> GameResult<()> {
+    if self.particles.is_empty() { return Ok(()); }
+    for particle in &self.particles {
+      let mut color = particle.color;
+      color[3] *= 1.0 - particle.age / particle.lifetime;
+      self.spritebatch.add(DrawParam {
+        dest:   graphics::Point2::from(&particle.point),
+        scale:  graphics::Point2::new(particle.size, particle.size),
+        offset: graphics::Point2::new(0.5, 0.5),
+        color:  Some(color.into()),
+        .. Default::default()
+      });
+    }
+    let param = DrawParam { dest: graphics::Point2::from(offset), .. Default::default() };
+    graphics::draw_ex(ctx, &self.spritebatch, param)?;
+    self.spritebatch.clear();
+    Ok(())
+  }
+}
+
+fn random_between<R: Rng>(rng: &mut R, (min, max): (f32, f32)) -> f32 {
+  if max > min { rng.gen_range(min, max) } else { min }
+}
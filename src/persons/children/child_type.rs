@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 
-use settings::child::{ names, shorts };
+use settings::child::{ names, shorts, flavour };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ChildType {
@@ -31,6 +31,15 @@ impl ChildType {
     }
   }
 
+  pub fn flavour_lines(&self) -> &'static [&'static str] {
+    use self::ChildType::*;
+    match self {
+      Larry => flavour::LARRY,
+      Bloat => flavour::BLOAT,
+      Thing => flavour::THING,
+    }
+  }
+
   pub fn name(&self) -> String {
     use self::names::*;
     use self::ChildType::*;
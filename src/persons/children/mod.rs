@@ -15,7 +15,7 @@ use noframe::geo::prelude::*;
 use noframe::entity::prelude::*;
 use noframe::deltatime::Deltatime;
 
-use settings::child::*;
+use config::{ self, Physics };
 use super::Person;
 use super::Axis;
 use super::AnimState;
@@ -40,30 +40,41 @@ pub struct Child {
   pub child_type:   ChildType,
   id:               IdType,
   solid:            bool,
+  physics:          Physics,
   dt:               Deltatime
 }
 
 impl Child {
   pub fn new(ctx: &mut Context, point: Point, size: Size, child_type: ChildType) -> Self {
+    let physics = config::with( |c| c.child );
     Self {
       point,
       size,
       origin:           Origin::TopLeft,
       velocity:         Point::new(0.0, 0.0),
-      max_velocity:     Point::new(MAX_VELOCITY_X, MAX_VELOCITY_Y),
+      max_velocity:     Point::new(physics.max_velocity_x, physics.max_velocity_y),
       has_moved:        Vec::new(),
       animations:       PersonAnimations::new_child_animations(ctx, &child_type),
       anim_state:       AnimState::Idle,
       walk_direction:   WalkDirection::Still,
       facing:           Facing::default(),
-      gravity_increase: Point::new(0.0, GRAVITY_INCREASE),
+      gravity_increase: Point::new(0.0, physics.gravity_increase),
       child_type,
       id:               generate_id(),
       solid:            false,
+      physics,
       dt:               Deltatime::new()
     }
   }
 
+  pub fn anim_state(&self) -> AnimState {
+    self.anim_state
+  }
+
+  pub fn facing(&self) -> &Facing {
+    &self.facing
+  }
+
   pub fn is_not_walking(&self) -> bool {
     WalkDirection::Still == self.walk_direction && self.velocity.x == 0.0 && self.on_floor()
   }
@@ -100,19 +111,25 @@ impl Child {
     }
   }
 
+  // Like `Player::land`, returns true if the child was falling
+  pub fn land(&self) -> bool {
+    if let AnimState::Fall = self.anim_state { true } else { false }
+  }
+
   pub fn stop_walking(&mut self) {
     self.walk_direction = WalkDirection::Still;
   }
 
   fn handle_walk(&mut self) {
     if self.is_solid() { return; }
+    let speed_increase = self.physics.speed_increase;
     match self.walk_direction {
       WalkDirection::Left  => {
-        self.add_velocity(&Point::new(-SPEED_INCREASE, 0.0));
+        self.add_velocity(&Point::new(-speed_increase, 0.0));
         self.moved_on_axis(Axis::X);
       },
       WalkDirection::Right => {
-        self.add_velocity(&Point::new( SPEED_INCREASE, 0.0));
+        self.add_velocity(&Point::new( speed_increase, 0.0));
         self.moved_on_axis(Axis::X);
       },
       WalkDirection::Still => ()
@@ -211,7 +228,7 @@ impl Person for Child {
     self.has_moved.clear();
   }
   fn speed_decrease(&self) -> Point {
-    Point::new(SPEED_DECREASE_X * self.dt.secs(), SPEED_DECREASE_Y * self.dt.secs())
+    Point::new(self.physics.speed_decrease_x * self.dt.secs(), self.physics.speed_decrease_y * self.dt.secs())
   }
 
   fn is_solid(&self) -> bool {
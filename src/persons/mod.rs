@@ -2,6 +2,8 @@ pub mod player;
 pub mod children;
 mod person_animations;
 
+pub use self::person_animations::PersonAnimations;
+
 use noframe::geo::prelude::*;
 use noframe::entity::prelude::*;
 use noframe::deltatime::Deltatime;
@@ -9,6 +11,7 @@ use noframe::deltatime::Deltatime;
 use gravity::Gravity;
 use id_generator::IdGenerator;
 
+#[derive(Clone, Copy)]
 pub enum AnimState {
   Walk,
   Idle,
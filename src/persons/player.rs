@@ -1,14 +1,14 @@
 use ggez::{
   GameResult,
   Context,
-  event::Keycode
 };
 
 use noframe::geo::prelude::*;
 use noframe::entity::prelude::*;
 use noframe::deltatime::Deltatime;
 
-use settings::player::*;
+use config::{ self, Physics };
+use action::Action;
 use super::Person;
 use super::Axis;
 use super::AnimState;
@@ -17,6 +17,7 @@ use super::person_animations::PersonAnimations;
 use animation::Facing;
 use gravity::Gravity;
 use id_generator::prelude::*;
+use sfx::SoundEvent;
 
 pub struct Player {
   point:            Point,
@@ -34,47 +35,52 @@ pub struct Player {
   has_jumped:       bool,
   id:               IdType,
   solid:            bool,
+  physics:          Physics,
+  sounds:           Vec<SoundEvent>,
   dt:               Deltatime
 }
 
 impl Player {
   pub fn new(ctx: &mut Context, point: Point, size: Size) -> Self {
+    let physics = config::with( |c| c.player );
     Self {
       point,
       size,
       origin:           Origin::TopLeft,
       velocity:         Point::new(0.0, 0.0),
-      max_velocity:     Point::new(MAX_VELOCITY_X, MAX_VELOCITY_Y),
+      max_velocity:     Point::new(physics.max_velocity_x, physics.max_velocity_y),
       has_moved:        Vec::new(),
       animations:       PersonAnimations::new_player_animations(ctx),
       anim_state:       AnimState::Idle,
       walk_direction:   WalkDirection::Still,
       facing:           Facing::Right,
-      gravity_increase: Point::new(0.0, GRAVITY_INCREASE),
+      gravity_increase: Point::new(0.0, physics.gravity_increase),
       is_jumping:       false,
       has_jumped:       false,
       id:               generate_id(),
       solid:            false,
+      physics,
+      sounds:           Vec::new(),
       dt:               Deltatime::new()
     }
   }
 
-  pub fn keys_pressed(&mut self, keycodes: &Vec<Keycode>) {
-    for keycode in keycodes {
-      if let Some(point) = match keycode {
-        &controls::LEFT => {
+  pub fn actions_pressed(&mut self, actions: &Vec<Action>) {
+    for action in actions {
+      if let Some(point) = match action {
+        Action::MoveLeft => {
           if !self.has_moved(Axis::X) {
             self.moved_on_axis(Axis::X);
-            Some(Point::new( -SPEED_INCREASE * self.dt.secs(), 0.0 ))
+            Some(Point::new( -self.physics.speed_increase * self.dt.secs(), 0.0 ))
           } else { None }
         }
-        &controls::RIGHT => {
+        Action::MoveRight => {
           if !self.has_moved(Axis::X) {
             self.moved_on_axis(Axis::X);
-            Some(Point::new( SPEED_INCREASE * self.dt.secs(), 0.0 ))
+            Some(Point::new( self.physics.speed_increase * self.dt.secs(), 0.0 ))
           } else { None }
         }
-        &controls::JUMP => {
+        Action::Jump => {
           if !self.has_jumped && self.on_floor() {
             self.jump();
           }
@@ -87,13 +93,12 @@ impl Player {
     }
   }
 
-  pub fn key_down(&mut self, _keycode: &Keycode) { }
-
-  pub fn key_up(&mut self, keycode: &Keycode) {
-    if let &controls::JUMP = keycode {
+  pub fn action_up(&mut self, action: &Action) {
+    if let Action::Jump = action {
       self.has_jumped = false;
       if self.is_jumping && self.velocity.y < 0.0 {
-        self.add_velocity(&Point::new(0.0, JUMP_KILL_VELOCITY));
+        let jump_kill_velocity = self.physics.jump_kill_velocity;
+        self.add_velocity(&Point::new(0.0, jump_kill_velocity));
         if self.velocity.y > 0.0 {
           self.set_velocity_y(0.0);
         }
@@ -105,13 +110,37 @@ impl Player {
     if self.is_jumping { return; }
     self.has_jumped = true;
     self.is_jumping = true;
-    self.add_velocity(&Point::new(0.0, -JUMP_SPEED));
+    let jump_speed = self.physics.jump_speed;
+    self.add_velocity(&Point::new(0.0, -jump_speed));
+    self.sounds.push(SoundEvent::PlayerJump);
   }
 
   pub fn stop_jumping(&mut self) {
     self.is_jumping = false;
   }
 
+  // Called when a solid block stops the player from falling, returns true if the player actually landed.
+  // Standing still also kills the (gravity) velocity every frame,
+  // but then the player never gets to the Fall animation state.
+  pub fn land(&mut self) -> bool {
+    if let AnimState::Fall = self.anim_state {
+      self.sounds.push(SoundEvent::PlayerLand);
+      true
+    } else { false }
+  }
+
+  pub fn anim_state(&self) -> AnimState {
+    self.anim_state
+  }
+
+  pub fn facing(&self) -> &Facing {
+    &self.facing
+  }
+
+  pub fn drain_sounds(&mut self) -> Vec<SoundEvent> {
+    self.sounds.drain(..).collect()
+  }
+
   fn handle_anim_state(&mut self) {
     self.anim_state = match self.velocity.as_tup() {
       (_x, y) if y <  0.0 => AnimState::Jump,
@@ -209,7 +238,7 @@ impl Person for Player {
     self.has_moved.clear();
   }
   fn speed_decrease(&self) -> Point {
-    Point::new(SPEED_DECREASE_X * self.dt.secs(), SPEED_DECREASE_Y * self.dt.secs())
+    Point::new(self.physics.speed_decrease_x * self.dt.secs(), self.physics.speed_decrease_y * self.dt.secs())
   }
 
   fn is_solid(&self) -> bool {
@@ -0,0 +1,95 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics::{ self, Font, Text, DrawParam },
+};
+use noframe::geo::prelude::*;
+use noframe::color::Color;
+
+use settings::res;
+use settings::popups::*;
+use id_generator::IdType;
+
+struct Popup {
+  anchor: IdType,
+  point:  Point,           // The anchor's last known top center
+  string: String,
+  text:   Option<Text>,    // Created on the next update, where a Context is available
+  color:  Color,
+  line:   usize,           // Stacks popups spawned on the same anchor at once
+  age:    f32,
+}
+
+// Text floating above persons in the level, e.g. when a child is commanded.
+// A popup follows its anchor entity, rises and fades out over its lifetime.
+pub struct Popups {
+  popups: Vec<Popup>,
+  font:   Font,
+}
+
+impl Popups {
+  pub fn new(ctx: &mut Context) -> GameResult<Self> {
+    Ok(Self {
+      popups: Vec::new(),
+      font:   Font::new(ctx, res::fonts::DEFAULT, FONT_SIZE)?,
+    })
+  }
+
+  pub fn spawn<T: ToString>(&mut self, anchor: IdType, text: T, color: Color, point: &Point) {
+    let line = self.popups.iter()
+      .filter( |popup| popup.anchor == anchor && popup.age == 0.0 )
+      .count();
+    self.popups.push(Popup {
+      anchor,
+      point:  point.clone(),
+      string: text.to_string(),
+      text:   None,
+      color,
+      line,
+      age:    0.0,
+    });
+  }
+
+  // `anchor_point` returns the current top center of the entity with the given id,
+  // if it still exists; otherwise the popup stays where it was last seen.
+  pub fn update<F>(&mut self, ctx: &mut Context, secs: f32, anchor_point: F) -> GameResult<()>
+    where F: Fn(IdType) -> Option<Point> {
+    for popup in &mut self.popups {
+      if popup.text.is_none() {
+        popup.text = Some(Text::new(ctx, &popup.string, &self.font)?);
+      } else {
+        popup.age += secs;
+      }
+      if let Some(point) = anchor_point(popup.anchor) {
+        popup.point = point;
+      }
+    }
+    self.popups.retain( |popup| popup.age < LIFETIME );
+    Ok(())
+  }
+
+  pub fn clear(&mut self) {
+    self.popups.clear();
+  }
+
+  pub fn draw_offset(&self, ctx: &mut Context, offset: &Point) -> GameResult<()> {
+    for popup in &self.popups {
+      let text = if let Some(text) = &popup.text {
+        text
+      } else { continue; };
+      let rise = OFFSET_Y
+        + popup.line as NumType * (FONT_SIZE as NumType + LINE_SPACING)
+        + RISE_SPEED * popup.age;
+      let mut color = popup.color;
+      color[3] *= ((LIFETIME - popup.age) / FADE_SECS).min(1.0).max(0.0);
+      let param = DrawParam {
+        dest:   graphics::Point2::new(popup.point.x + offset.x, popup.point.y + offset.y - rise),
+        offset: graphics::Point2::new(0.5, 1.0),
+        color:  Some(color.into()),
+        .. Default::default()
+      };
+      graphics::draw_ex(ctx, text, param)?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use ggez::{
+  Context,
+  GameResult,
+};
+use json::JsonValue;
+
+use settings::leaderboards::*;
+use game::save_file::{ SaveFile, LoadedSave };
+use super::{ Score, ScoreType, TickType, compare_ticks };
+
+#[derive(Clone, Debug)]
+pub struct LeaderboardEntry {
+  pub score:   ScoreType,
+  pub moves:   ScoreType,
+  pub ticks:   TickType,
+  pub date:    u64,  // Seconds since the unix epoch
+  pub profile: String,
+}
+
+impl LeaderboardEntry {
+  pub fn new(score: &Score, profile: &str) -> Self {
+    Self {
+      score:   score.score(),
+      moves:   score.moves(),
+      ticks:   score.ticks(),
+      date:    SystemTime::now().duration_since(UNIX_EPOCH).map( |d| d.as_secs() ).unwrap_or(0),
+      profile: profile.to_string(),
+    }
+  }
+
+  fn from_json(json: &JsonValue) -> Option<Self> {
+    Some(Self {
+      score:   json["score"].as_u32()?,  // as ScoreType
+      moves:   json["moves"].as_u32().unwrap_or(0),
+      ticks:   json["ticks"].as_u32().unwrap_or(0),
+      date:    json["date"].as_u64().unwrap_or(0),
+      profile: json["profile"].as_str().unwrap_or("").to_string(),
+    })
+  }
+
+  fn as_json(&self) -> JsonValue {
+    object!{
+      "score"   => self.score,
+      "moves"   => self.moves,
+      "ticks"   => self.ticks,
+      "date"    => self.date,
+      "profile" => self.profile.as_str(),
+    }
+  }
+
+  // Ranked like highscores: by points, then by time
+  fn cmp_rank(&self, other: &Self) -> Ordering {
+    other.score.cmp(&self.score).then_with( || compare_ticks(other.ticks, self.ticks) )
+  }
+
+  // The date as `YYYY-MM-DD`, in UTC
+  pub fn semantic_date(&self) -> String {
+    // Days to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z     = self.date / 86400 + 719468;
+    let era   = z / 146097;
+    let doe   = z - era * 146097;
+    let yoe   = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy   = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp    = (5 * doy + 2) / 153;
+    let day   = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year  = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{}-{:02}-{:02}", year, month, day)
+  }
+}
+
+// The best runs of every level, of all profiles, in their own file in the user data directory.
+// Levels are keyed by their level pack's id and their name, e.g. `main/01_one`.
+// The file is written like a profile's save, so it is recovered from its backup the same way.
+pub struct Leaderboards {
+  save_file: SaveFile,
+  levels:    HashMap<String, Vec<LeaderboardEntry>>,
+}
+
+impl Leaderboards {
+  pub fn load(ctx: &Context) -> Self {
+    let save_file = SaveFile::with_path(ctx.filesystem.get_user_data_dir().join(FILENAME));
+    let data = match save_file.read() {
+      LoadedSave::None                 => object!{},
+      LoadedSave::Save(data)           => data,
+      LoadedSave::Recovered(data, msg) => {
+        eprintln!("Leaderboards: {}", msg);
+        data
+      }
+      // Saving refuses to overwrite it, too
+      LoadedSave::TooNew               => {
+        eprintln!("Leaderboards are from a newer version of the game, they won't be saved.");
+        object!{}
+      }
+    };
+    let mut levels = HashMap::new();
+    for (key, entries) in data["levels"].entries() {
+      let mut entries = entries.members()
+        .filter_map(LeaderboardEntry::from_json)
+        .collect::<Vec<LeaderboardEntry>>();
+      entries.sort_by( |a, b| a.cmp_rank(b) );
+      entries.truncate(MAX_ENTRIES);
+      levels.insert(key.to_string(), entries);
+    }
+    Self { save_file, levels }
+  }
+
+  pub fn entries(&self, pack_id: &str, level_name: &str) -> Vec<LeaderboardEntry> {
+    self.levels.get(&level_key(pack_id, level_name)).cloned().unwrap_or(Vec::new())
+  }
+
+  // Returns the new entry's rank, if it made it onto the leaderboard.
+  // Equal runs keep their order, the older one ranks higher.
+  pub fn insert(&mut self, pack_id: &str, level_name: &str, entry: LeaderboardEntry) -> Option<usize> {
+    let entries = self.levels.entry(level_key(pack_id, level_name)).or_insert(Vec::new());
+    let rank = entries.iter()
+      .position( |other| entry.cmp_rank(other) == Ordering::Less )
+      .unwrap_or(entries.len());
+    if rank >= MAX_ENTRIES {
+      return None;
+    }
+    entries.insert(rank, entry);
+    entries.truncate(MAX_ENTRIES);
+    Some(rank)
+  }
+
+  pub fn save(&self) -> GameResult<()> {
+    let mut levels = object!{};
+    for (key, entries) in &self.levels {
+      levels[key.as_str()] = JsonValue::Array(entries.iter().map( |entry| entry.as_json() ).collect());
+    }
+    self.save_file.write(&object!{ "levels" => levels })
+  }
+}
+
+pub fn level_key(pack_id: &str, level_name: &str) -> String {
+  format!("{}/{}", pack_id, level_name)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::PathBuf;
+
+  fn leaderboards() -> Leaderboards {
+    Leaderboards {
+      save_file: SaveFile::with_path(PathBuf::from("leaderboards.json")),
+      levels:    HashMap::new(),
+    }
+  }
+
+  fn entry(score: ScoreType, ticks: TickType, profile: &str) -> LeaderboardEntry {
+    LeaderboardEntry { score, moves: 0, ticks, date: 0, profile: profile.to_string() }
+  }
+
+  fn scores(leaderboards: &Leaderboards) -> Vec<(ScoreType, TickType)> {
+    leaderboards.entries("main", "01")
+      .iter()
+      .map( |entry| (entry.score, entry.ticks) )
+      .collect()
+  }
+
+  #[test]
+  fn insert_ranks_by_points_then_time() {
+    let mut leaderboards = leaderboards();
+    assert_eq!(leaderboards.insert("main", "01", entry(10, 100, "a")), Some(0));
+    assert_eq!(leaderboards.insert("main", "01", entry(20, 100, "a")), Some(0));
+    assert_eq!(leaderboards.insert("main", "01", entry(10, 50,  "a")), Some(1));
+    assert_eq!(scores(&leaderboards), vec![(20, 100), (10, 50), (10, 100)]);
+  }
+
+  #[test]
+  fn an_equal_run_ranks_below_the_older_one() {
+    let mut leaderboards = leaderboards();
+    leaderboards.insert("main", "01", entry(10, 100, "old"));
+    assert_eq!(leaderboards.insert("main", "01", entry(10, 100, "new")), Some(1));
+    assert_eq!(leaderboards.entries("main", "01")[0].profile, "old");
+  }
+
+  #[test]
+  fn a_full_leaderboard_drops_its_worst_run() {
+    let mut leaderboards = leaderboards();
+    for score in 0 .. MAX_ENTRIES as ScoreType {
+      leaderboards.insert("main", "01", entry(score + 1, 100, "a"));
+    }
+    assert_eq!(leaderboards.insert("main", "01", entry(0, 100, "a")), None);
+    assert_eq!(leaderboards.insert("main", "01", entry(100, 100, "a")), Some(0));
+    let entries = leaderboards.entries("main", "01");
+    assert_eq!(entries.len(), MAX_ENTRIES);
+    assert_eq!(entries.last().map( |entry| entry.score ), Some(2));
+  }
+
+  #[test]
+  fn levels_have_their_own_leaderboards() {
+    let mut leaderboards = leaderboards();
+    leaderboards.insert("main", "01", entry(10, 100, "a"));
+    assert!(leaderboards.entries("main", "02").is_empty());
+    assert!(leaderboards.entries("other", "01").is_empty());
+  }
+
+  #[test]
+  fn semantic_date_is_the_utc_day() {
+    let mut entry = entry(0, 0, "a");
+    assert_eq!(entry.semantic_date(), "1970-01-01");
+    entry.date = 951_782_400;
+    assert_eq!(entry.semantic_date(), "2000-02-29");
+    entry.date = 1_700_000_000;
+    assert_eq!(entry.semantic_date(), "2023-11-14");
+  }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::ops;
+
+use json::JsonValue;
+
+use persons::children::ChildType;
+use super::{ ScoreType, TickType };
+use super::leaderboards::level_key;
+
+// What happened in a level that counts towards the lifetime stats
+#[derive(Clone, Copy, Debug)]
+pub enum StatsEvent {
+  Jumped,
+  CommandedChild(ChildType),
+  SavedPlayer,            // The player was in the goal when the level was cleared
+  SavedChild(ChildType),  // As was this child
+  Reset,
+  Cleared,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LevelStats {
+  pub ticks:          TickType,  // Time played, in simulation updates
+  pub jumps:          ScoreType,
+  pub commands:       HashMap<ChildType, ScoreType>,
+  pub resets:         ScoreType,
+  pub saved:          ScoreType,  // Persons, the player and the children
+  pub children_saved: ScoreType,
+  pub cleared:        ScoreType,
+}
+
+impl LevelStats {
+  fn from_json(json: &JsonValue) -> Self {
+    let mut commands = HashMap::new();
+    for (name, count) in json["commands"].entries() {
+      if let Some(child) = ChildType::from_short(name) {
+        commands.insert(child, count.as_u32().unwrap_or(0));  // as ScoreType
+      }
+    }
+    Self {
+      ticks:          json["ticks"].as_u32().unwrap_or(0),  // as TickType
+      jumps:          json["jumps"].as_u32().unwrap_or(0),
+      commands,
+      resets:         json["resets"].as_u32().unwrap_or(0),
+      saved:          json["saved"].as_u32().unwrap_or(0),
+      children_saved: json["children_saved"].as_u32().unwrap_or(0),
+      cleared:        json["cleared"].as_u32().unwrap_or(0),
+    }
+  }
+
+  fn as_json(&self) -> JsonValue {
+    let mut commands = object!{};
+    for (child, &count) in &self.commands {
+      commands[child.short()] = count.into();
+    }
+    object!{
+      "ticks"          => self.ticks,
+      "jumps"          => self.jumps,
+      "commands"       => commands,
+      "resets"         => self.resets,
+      "saved"          => self.saved,
+      "children_saved" => self.children_saved,
+      "cleared"        => self.cleared,
+    }
+  }
+
+  pub fn record(&mut self, event: StatsEvent) {
+    use self::StatsEvent::*;
+    match event {
+      Jumped                => self.jumps   += 1,
+      CommandedChild(child) => *self.commands.entry(child).or_insert(0) += 1,
+      SavedPlayer           => self.saved   += 1,
+      SavedChild(_)         => {
+        self.saved          += 1;
+        self.children_saved += 1;
+      },
+      Reset                 => self.resets  += 1,
+      Cleared               => self.cleared += 1,
+    }
+  }
+
+  pub fn commands(&self) -> ScoreType {
+    self.commands.values().sum()
+  }
+
+  pub fn commands_for(&self, child: ChildType) -> ScoreType {
+    self.commands.get(&child).cloned().unwrap_or(0)
+  }
+}
+
+impl<'a> ops::AddAssign<&'a LevelStats> for LevelStats {
+  fn add_assign(&mut self, other: &LevelStats) {
+    self.ticks          += other.ticks;
+    self.jumps          += other.jumps;
+    for (&child, &count) in &other.commands {
+      *self.commands.entry(child).or_insert(0) += count;
+    }
+    self.resets         += other.resets;
+    self.saved          += other.saved;
+    self.children_saved += other.children_saved;
+    self.cleared        += other.cleared;
+  }
+}
+
+// A profile's statistics over all of its play, saved with the profile.
+// Levels are keyed by their level pack's id and their name, like on the leaderboards.
+pub struct LifetimeStats {
+  levels: HashMap<String, LevelStats>,
+}
+
+impl LifetimeStats {
+  pub fn new() -> Self {
+    Self {
+      levels: HashMap::new(),
+    }
+  }
+
+  pub fn from_json(json: &JsonValue) -> Self {
+    let mut levels = HashMap::new();
+    for (key, level_json) in json["levels"].entries() {
+      levels.insert(key.to_string(), LevelStats::from_json(level_json));
+    }
+    Self { levels }
+  }
+
+  pub fn as_json(&self) -> JsonValue {
+    let mut levels = object!{};
+    for (key, stats) in &self.levels {
+      levels[key.as_str()] = stats.as_json();
+    }
+    object!{ "levels" => levels }
+  }
+
+  pub fn level(&self, pack_id: &str, level_name: &str) -> Option<&LevelStats> {
+    self.levels.get(&level_key(pack_id, level_name))
+  }
+
+  fn level_mut(&mut self, pack_id: &str, level_name: &str) -> &mut LevelStats {
+    self.levels.entry(level_key(pack_id, level_name)).or_insert(LevelStats::default())
+  }
+
+  pub fn tick(&mut self, pack_id: &str, level_name: &str) {
+    self.level_mut(pack_id, level_name).ticks += 1;
+  }
+
+  pub fn record(&mut self, pack_id: &str, level_name: &str, event: StatsEvent) {
+    self.level_mut(pack_id, level_name).record(event);
+  }
+
+  // All levels together, including those of level packs that were removed since
+  pub fn total(&self) -> LevelStats {
+    let mut total = LevelStats::default();
+    for stats in self.levels.values() {
+      total += stats;
+    }
+    total
+  }
+}
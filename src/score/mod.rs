@@ -1,4 +1,6 @@
 mod child_moves_counter;
+pub mod leaderboards;
+pub mod lifetime;
 
 use std::collections::HashMap;
 use std::cmp::Ordering;
@@ -9,14 +11,17 @@ use json::JsonValue;
 
 use settings::score::*;
 use settings::player;
+use config;
 use persons::children::ChildType;
 use self::child_moves_counter::ChildMovesCounter;
 
 pub type ScoreType = u32;
+pub type TickType  = u32;
 
 pub mod prelude {
   pub use super::Score;
   pub use super::ScoreType;
+  pub use super::TickType;
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +29,7 @@ pub struct Score {
   times_saved_player:   ScoreType,
   times_saved_children: HashMap<ChildType, ScoreType>,
   moves_counter:        ChildMovesCounter,
+  ticks:                TickType,  // Simulation updates spent in the level
 }
 
 impl Score {
@@ -32,6 +38,7 @@ impl Score {
       times_saved_player:   0,
       times_saved_children: HashMap::new(),
       moves_counter:        ChildMovesCounter::new(),
+      ticks:                0,
     }
   }
 
@@ -56,13 +63,16 @@ impl Score {
       times_saved_player: json["player"]["saved"].as_u32().unwrap_or(0),  // as ScoreType
       times_saved_children,
       moves_counter: ChildMovesCounter::with(times_moved_children),
+      ticks:         json["ticks"].as_u32().unwrap_or(0),  // as TickType
     })
   }
 
-  pub fn as_json(&self) -> Option<JsonValue> {
-    if self.score() == 0 { return None; }
+  // Written for every cleared level, even one cleared with 0 points,
+  // because a level with a score is what counts as cleared.
+  pub fn as_json(&self) -> JsonValue {
     let mut data = object!{
       "children" => object!{},
+      "ticks"    => self.ticks,
     };
     if self.times_saved_player > 0 {
       data["player"] = object!{
@@ -85,7 +95,7 @@ impl Score {
         data["children"][child.short()]["moves"] = moves.into();
       }
     }
-    Some(data)
+    data
   }
 
   pub fn score(&self) -> ScoreType {
@@ -148,7 +158,15 @@ impl Score {
   }
 
   pub fn semantic_highscore(&self) -> String {
-    format!("Highscore: {}", self)
+    if self.ticks > 0 {
+      format!("Highscore: {} in {}", self, format_ticks(self.ticks))
+    } else {
+      format!("Highscore: {}", self)
+    }
+  }
+
+  pub fn semantic_time(&self) -> String {
+    format!("Time: {}", format_ticks(self.ticks))
   }
 
   pub fn semantic_player(&self) -> Option<String> {
@@ -249,10 +267,55 @@ impl Score {
     self.moves_counter.moves_for(child)
   }
 
+  pub fn moves(&self) -> ScoreType {
+    self.moves_counter.total()
+  }
+
+  // Called once per simulation update, so the timer stands still while the level isn't updated
+  pub fn tick(&mut self) {
+    self.ticks += 1;
+  }
+
+  pub fn ticks(&self) -> TickType {
+    self.ticks
+  }
+
   pub fn clear(&mut self) {
     self.times_saved_player = 0;
     self.times_saved_children.clear();
     self.moves_counter.clear();
+    self.ticks = 0;
+  }
+}
+
+// Ticks as `m:ss.cc`, at the configured updates per second
+pub fn format_ticks(ticks: TickType) -> String {
+  let centis = (ticks as f32 / config::with( |c| c.ups ) * 100.0).round() as u32;
+  format!("{}:{:02}.{:02}", centis / 6000, centis / 100 % 60, centis % 100)
+}
+
+// Longer times, like the lifetime play time, as `h:mm:ss`
+pub fn format_play_time(ticks: TickType) -> String {
+  let secs = (ticks as f64 / config::with( |c| c.ups ) as f64) as u32;
+  format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+// Greater for the faster time; no time (0 ticks, saved before levels were timed) is the slowest
+pub fn compare_ticks(ticks: TickType, other: TickType) -> Ordering {
+  match (ticks, other) {
+    (0, 0) => Ordering::Equal,
+    (0, _) => Ordering::Less,
+    (_, 0) => Ordering::Greater,
+    (a, b) => b.cmp(&a),
+  }
+}
+
+// A difference between two times, e.g. to a personal best, as `+m:ss.cc` or `-m:ss.cc`
+pub fn format_ticks_delta(ticks: TickType, other: TickType) -> String {
+  if ticks >= other {
+    format!("+{}", format_ticks(ticks - other))
+  } else {
+    format!("-{}", format_ticks(other - ticks))
   }
 }
 
@@ -281,18 +344,21 @@ impl ops::AddAssign<&Score> for Score {
       // moves
       self.moves_counter += &other.moves_counter;
     }
+    // time
+    self.ticks += other.ticks;
   }
 }
 
+// Scores are compared by points first; with equal points, the faster time is better.
 impl PartialEq for Score {
   fn eq(&self, other: &Score) -> bool {
-    self.score() == other.score()
+    self.score() == other.score() && self.ticks == other.ticks
   }
 }
 
 impl PartialOrd for Score {
   fn partial_cmp(&self, other: &Score) -> Option<Ordering> {
-    Some(self.score().cmp(&other.score()))
+    Some(self.score().cmp(&other.score()).then_with( || compare_ticks(self.ticks, other.ticks) ))
   }
 }
 
@@ -301,3 +367,72 @@ impl fmt::Display for Score {
     write!(f, "{}", self.score())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn score_with(saved_player: bool, saved_children: &[ChildType], moved_children: &[ChildType], ticks: TickType) -> Score {
+    let mut score = Score::new();
+    if saved_player {
+      score.saved_player();
+    }
+    for &child in saved_children {
+      score.saved_child(child);
+    }
+    for &child in moved_children {
+      score.moved_child(child);
+    }
+    for _ in 0 .. ticks {
+      score.tick();
+    }
+    score
+  }
+
+  #[test]
+  fn more_points_rank_higher() {
+    let low  = score_with(false, &[ChildType::Larry], &[], 100);
+    let high = score_with(true,  &[ChildType::Larry], &[], 100);
+    assert!(high > low);
+  }
+
+  #[test]
+  fn equal_points_rank_the_faster_time_higher() {
+    let slow = score_with(true, &[], &[], 100);
+    let fast = score_with(true, &[], &[], 50);
+    assert!(fast > slow);
+  }
+
+  #[test]
+  fn a_run_without_a_time_ranks_below_a_timed_one() {
+    let untimed = score_with(true, &[], &[], 0);
+    let timed   = score_with(true, &[], &[], 100);
+    assert!(timed > untimed);
+  }
+
+  #[test]
+  fn moves_cant_take_the_score_below_zero() {
+    let score = score_with(false, &[], &[ChildType::Larry, ChildType::Thing], 10);
+    assert_eq!(score.score(), 0);
+  }
+
+  #[test]
+  fn json_round_trip() {
+    let score = score_with(true, &[ChildType::Larry, ChildType::Thing, ChildType::Thing], &[ChildType::Bloat], 1234);
+    let loaded = Score::from_json(&score.as_json()).expect("Should load the written score");
+    assert_eq!(loaded.score(),                             score.score());
+    assert_eq!(loaded.ticks(),                             1234);
+    assert_eq!(loaded.times_saved_player(),                1);
+    assert_eq!(loaded.times_saved_child(ChildType::Thing), Some(2));
+    assert_eq!(loaded.child_moves_for(ChildType::Bloat),   Some(1));
+    assert_eq!(loaded.moves(),                             1);
+  }
+
+  #[test]
+  fn a_zero_point_clear_is_written_too() {
+    let score = score_with(false, &[], &[], 100);
+    let loaded = Score::from_json(&score.as_json()).expect("Should load the written score");
+    assert_eq!(loaded.score(), 0);
+    assert_eq!(loaded.ticks(), 100);
+  }
+}
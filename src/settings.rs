@@ -10,18 +10,53 @@ pub mod meta {
 pub mod game {
   use noframe::color::Color;
   pub const BG_COLOR: Color = [0.33, 0.33, 0.33, 1.0];
+  // Defaults, can be changed in the config file
   pub const UPS: f32 = 60.0;
-  pub const UPDATE_INTERVAL_MS: u64 = (1.0 / UPS * 1000.0) as u64;
   pub const MUTED: bool = false;
   pub const VOLUME: f32 = 0.5;
-  pub const SAVEFILE: &str = "save.json";
+  pub const SFX_VOLUME: f32 = 0.8;
+  pub const WINDOW_SCALE: f32 = 1.0;
+  pub const RESIZABLE: bool = true;
+  pub const INTEGER_SCALING: bool = false;
+  pub const CAMERA_FOLLOW: bool = false;
+  pub const SPEEDRUN: bool = false;
+  pub const GHOSTS: bool = true;
+  pub const HOT_RELOAD: bool = false;  // Dev option: reload the current level when its JSON file changes
+  pub const LETTERBOX_COLOR: Color = [0.0, 0.0, 0.0, 1.0];
+}
+
+pub mod config {
+  pub const FILENAME: &str = "config.json";  // In the user config directory
+}
+
+pub mod save {
+  use noframe::color::Color;
+  pub const VERSION:              u32   = 1;
+  pub const FILENAME:             &str  = "save.json";
+  pub const BACKUP_EXTENSION:     &str  = "json.bak";
+  pub const TEMP_EXTENSION:       &str  = "json.tmp";
+  pub const LEGACY_FILENAME:      &str  = "save.json";  // Relative to the working directory
+  pub const PROFILES_DIR:         &str  = "profiles";
+  pub const ACTIVE_PROFILE_FILE:  &str  = "active";
+  pub const DEFAULT_PROFILE_ID:   &str  = "profile_0";
+  pub const DEFAULT_PROFILE_NAME: &str  = "Player 1";
+  pub const MAX_PROFILE_NAME_LEN: usize = 16;
+  pub const NOTICE_DURATION:      u64   = 5;  // Seconds
+  pub const NOTICE_FONT_SIZE:     u32   = 16;
+  pub const NOTICE_BG_COLOR:      Color = [0.6, 0.0, 0.0, 0.85];
+  pub const NOTICE_COLOR:         Color = [1.0, 1.0, 1.0, 1.0];
 }
 
 pub mod res {
   pub const IMAGES:            &str = "/images/";
   pub const BACKGROUND_IMAGES: &str = "/images/backgrounds/";
   pub const LEVELS:            &str = "resources/levels/";
+  pub const USER_LEVELS:       &str = "levels";
+  pub const LEVEL_PACK_MANIFEST: &str = "manifest.json";
+  pub const DIALOGUE:          &str = "resources/dialogue/";
+  pub const ACHIEVEMENTS:      &str = "resources/achievements.json";
   pub const AUDIO:             &str = "/audio/";
+  pub const SFX:               &str = "/audio/sfx/";
   pub const MISSING_IMAGE:     &str = "/images/missing.png";
   pub const FONTS:             &str = "/fonts/";
   pub mod fonts {
@@ -29,8 +64,50 @@ pub mod res {
   }
 }
 
+pub mod music {
+  pub const FORMAT:         &str = "ogg";
+  pub const TITLE_TRACK:    &str = "titletheme";
+  pub const CROSSFADE_SECS: f32  = 1.5;
+  // If a track's file is missing, the track without this suffix is played instead
+  pub const VARIANT_SUFFIX: &str = ".fl";
+}
+
+pub mod sfx {
+  pub const FORMAT:    &str  = "ogg";
+  pub const POOL_SIZE: usize = 4;  // Instances per sound, so the same sound can overlap itself
+}
+
+pub mod gamepad {
+  use noframe::color::Color;
+  pub const STICK_DEADZONE:    i16   = 8000;
+  pub const TRIGGER_THRESHOLD: i16   = 16000;
+  pub const FOCUS_COLOR:       Color = [1.0, 1.0, 1.0, 0.3];
+
+  pub mod controls {
+    use ggez::event::Button;
+    pub const JUMP:          Button = Button::A;
+    pub const PAUSE:         Button = Button::Start;
+    pub const RESET:         Button = Button::Y;
+    pub const NEXT:          Button = Button::X;
+    pub const TO_TITLE:      Button = Button::Back;
+    pub const CENTER_CAMERA: Button = Button::RightStick;
+    pub const PREV_CHILD:    Button = Button::LeftShoulder;
+    pub const NEXT_CHILD:    Button = Button::RightShoulder;
+    pub const MENU_CONFIRM:  Button = Button::A;
+    pub const MENU_BACK:     Button = Button::B;
+  }
+}
+
 pub mod camera {
   pub const CAMERA_SPEED: f32 = 500.0;
+
+  pub mod controls {
+    use ggez::event::Keycode;
+    pub const UP:    Keycode = Keycode::Up;
+    pub const DOWN:  Keycode = Keycode::Down;
+    pub const LEFT:  Keycode = Keycode::Left;
+    pub const RIGHT: Keycode = Keycode::Right;
+  }
 }
 
 pub mod player {
@@ -54,6 +131,7 @@ pub mod player {
 }
 
 pub mod child {
+  use noframe::color::Color;
   pub const IMAGES: &str = "/images/children/";
   pub const SPEED_INCREASE: f32 = 400.0;
   pub const SPEED_DECREASE_X: f32 = 600.0;
@@ -61,6 +139,18 @@ pub mod child {
   pub const MAX_VELOCITY_X: f32 = 80.0;
   pub const MAX_VELOCITY_Y: f32 = 1000.0;
   pub const GRAVITY_INCREASE: f32 = 800.0;
+  pub const SELECTED_COLOR: Color = [1.0, 1.0, 0.5, 0.35];
+  pub const SELECTED_PADDING: f32 = 4.0;
+
+  pub mod controls {
+    use ggez::event::Keycode;
+    pub const SELECT_1:      Keycode = Keycode::Num1;
+    pub const SELECT_2:      Keycode = Keycode::Num2;
+    pub const SELECT_3:      Keycode = Keycode::Num3;
+    pub const NEXT:          Keycode = Keycode::Tab;
+    pub const COMMAND_LEFT:  Keycode = Keycode::Q;
+    pub const COMMAND_RIGHT: Keycode = Keycode::E;
+  }
 
   pub mod names {
     pub const LARRY: &str = "Larry";
@@ -73,12 +163,97 @@ pub mod child {
     pub const BLOAT: &str = "bloat";
     pub const THING: &str = "thing";
   }
+
+  // Said when commanded, picked at random
+  pub mod flavour {
+    pub const LARRY: &[&str] = &["On my way!", "Okay, okay...", "Wheee!"];
+    pub const BLOAT: &[&str] = &["*burp*", "Do I have to?", "Rolling out."];
+    pub const THING: &[&str] = &["...", "*gurgle*", "It moves."];
+  }
 }
 
 pub mod wall {
   pub const IMAGES: &str = "/images/walls/";
 }
 
+pub mod background {
+  // 0.0 keeps a layer fixed to the screen, 1.0 scrolls it along with the level
+  pub const DEFAULT_PARALLAX: f32 = 0.0;
+  pub const DEFAULT_INTERVAL: u64 = 1000;
+}
+
+pub mod popups {
+  use noframe::color::Color;
+  pub const FONT_SIZE:     u32   = 16;
+  pub const LIFETIME:      f32   = 1.5;   // Seconds
+  pub const FADE_SECS:     f32   = 0.5;   // Fades out over the end of its lifetime
+  pub const RISE_SPEED:    f32   = 32.0;  // Pixels per second
+  pub const OFFSET_Y:      f32   = 8.0;   // Above the person's head
+  pub const LINE_SPACING:  f32   = 4.0;
+  pub const TEXT_COLOR:    Color = [1.0, 1.0, 1.0, 1.0];
+  pub const PENALTY_COLOR: Color = [0.9, 0.3, 0.2, 1.0];
+  pub const REWARD_COLOR:  Color = [0.3, 0.9, 0.3, 1.0];
+}
+
+pub mod hints {
+  use noframe::color::Color;
+  pub const FONT_SIZE:       u32   = 16;
+  pub const PADDING:         f32   = 12.0;
+  pub const OFFSET_Y:        f32   = 40.0;   // Below the level's highscore
+  pub const ARROW_SIZE:      f32   = 24.0;
+  pub const ARROW_BOB:       f32   = 6.0;    // Pixels the arrow moves up and down
+  pub const ARROW_BOB_SPEED: f32   = 5.0;
+  pub const BG_COLOR:        Color = [0.1, 0.1, 0.1, 0.85];
+  pub const FONT_COLOR:      Color = [1.0, 1.0, 1.0, 1.0];
+  pub const ARROW_COLOR:     Color = [1.0, 0.85, 0.2, 1.0];
+}
+
+pub mod ghosts {
+  use noframe::color::Color;
+  pub const DIR_SUFFIX: &str  = ".ghosts";  // Next to the profile's save file, e.g. `profile_0.ghosts/`
+  pub const COLOR:      Color = [1.0, 1.0, 1.0, 0.4];
+}
+
+pub mod achievements {
+  pub mod toasts {
+    use noframe::color::Color;
+    pub const DURATION:        u64   = 4;  // Seconds
+    pub const TITLE_FONT_SIZE: u32   = 18;
+    pub const FONT_SIZE:       u32   = 14;
+    pub const PADDING:         f32   = 12.0;
+    pub const BG_COLOR:        Color = [0.1, 0.1, 0.1, 0.9];
+    pub const TITLE_COLOR:     Color = [1.0, 0.85, 0.2, 1.0];
+    pub const FONT_COLOR:      Color = [1.0, 1.0, 1.0, 1.0];
+  }
+}
+
+pub mod dialogue {
+  use noframe::color::Color;
+  pub const FONT_SIZE:         u32   = 16;
+  pub const NAME_FONT_SIZE:    u32   = 20;
+  pub const CHOICE_FONT_SIZE:  u32   = 16;
+  pub const PANEL_HEIGHT:      f32   = 160.0;
+  pub const PADDING:           f32   = 16.0;
+  pub const PORTRAIT_SIZE:     f32   = 128.0;
+  pub const LINE_HEIGHT:       f32   = 22.0;
+  pub const LINE_CHARS:        usize = 56;     // Text is wrapped after this many characters
+  pub const CHOICE_WIDTH:      f32   = 320.0;
+  pub const CHOICE_HEIGHT:     f32   = 40.0;
+  pub const PORTRAIT_INTERVAL: u64   = 500;    // ms
+  pub const BG_COLOR:          Color = [0.1, 0.1, 0.1, 0.9];
+  pub const FONT_COLOR:        Color = [1.0, 1.0, 1.0, 1.0];
+  pub const NAME_COLOR:        Color = [1.0, 0.85, 0.4, 1.0];
+}
+
+pub mod particles {
+  pub const MAX_PARTICLES: usize = 512;  // Per level; new particles are dropped while at the cap
+}
+
+pub mod decoration {
+  pub const IMAGES:           &str = "/images/decorations/";
+  pub const DEFAULT_INTERVAL: u64  = 250;
+}
+
 pub mod interactables {
   pub const IMAGES: &str = "/images/interactables/";
 
@@ -96,12 +271,60 @@ pub mod menus {
   pub mod title {
     pub const TOTAL_SCORE_FONT_SIZE: u32 = 12;
     pub mod level_select {
+      use noframe::color::Color;
+      pub const FONT_SIZE:        u32   = 24;
+      pub const STATUS_FONT_SIZE: u32   = 14;
+      pub const LOCKED_COLOR:     Color = [0.0, 0.0, 0.0, 0.6];
+      pub mod thumbnail {
+        pub const WIDTH:  u16 = 112;
+        pub const HEIGHT: u16 = 63;
+        // RGBA colors of the instances in the preview
+        pub const BG_COLOR:           [u8; 4] = [ 40,  40,  40, 255];
+        pub const WALL_COLOR:         [u8; 4] = [160, 160, 160, 255];
+        pub const GOAL_COLOR:         [u8; 4] = [ 80, 200,  80, 255];
+        pub const INTERACTABLE_COLOR: [u8; 4] = [220, 180,  40, 255];
+        pub const PERSON_COLOR:       [u8; 4] = [220,  60,  60, 255];
+      }
+    }
+    pub mod level_packs {
       pub const FONT_SIZE: u32 = 24;
     }
+    pub mod profiles {
+      use noframe::color::Color;
+      pub const FONT_SIZE:         u32   = 24;
+      pub const NAME_FONT_SIZE:    u32   = 12;
+      pub const RENAME_BG_COLOR:   Color = [0.3, 0.3, 0.3, 1.0];
+      pub const RENAME_FONT_COLOR: Color = [1.0, 1.0, 1.0, 1.0];
+    }
+    pub mod statistics {
+      use noframe::color::Color;
+      pub const FONT_SIZE:       u32   = 16;
+      pub const TITLE_FONT_SIZE: u32   = 32;
+      pub const ROW_HEIGHT:      f32   = 24.0;
+      pub const PAGE_ROWS:       usize = 20;     // Levels per page of the breakdown
+      pub const TOTALS_WIDTH:    f32   = 400.0;  // Left of the breakdown
+      pub const FONT_COLOR:      Color = [1.0, 1.0, 1.0, 1.0];
+      pub const HEADER_COLOR:    Color = [0.6, 0.6, 0.6, 1.0];
+    }
+    pub mod achievements {
+      use noframe::color::Color;
+      pub const NAME_FONT_SIZE:  u32   = 20;
+      pub const FONT_SIZE:       u32   = 16;
+      pub const TITLE_FONT_SIZE: u32   = 32;
+      pub const ROW_HEIGHT:      f32   = 56.0;  // Name and description
+      pub const PAGE_ROWS:       usize = 12;
+      pub const UNLOCKED_COLOR:  Color = [1.0, 0.85, 0.2, 1.0];
+      pub const LOCKED_COLOR:    Color = [0.5, 0.5, 0.5, 1.0];
+      pub const FONT_COLOR:      Color = [1.0, 1.0, 1.0, 1.0];
+    }
     pub mod controls {
       use ggez::event::Keycode;
       pub const PLAY:         Keycode = Keycode::Return;
       pub const LEVEL_SELECT: Keycode = Keycode::L;
+      pub const LEVEL_PACKS:  Keycode = Keycode::K;
+      pub const EDITOR:       Keycode = Keycode::E;
+      pub const PROFILES:     Keycode = Keycode::P;
+      pub const CONFIRM:      Keycode = Keycode::Return;
       pub const BACK:         Keycode = Keycode::Backspace;
     }
   }
@@ -112,8 +335,26 @@ pub mod menus {
     pub const FONT_COLOR: Color = color::BLACK;
   }
   pub mod pause {
+    use noframe::color::Color;
+    pub const TITLE_FONT_SIZE:    u32   = 32;
+    pub const TITLE_BG_COLOR:     Color = [0.5, 0.5, 0.5, 1.0];
+    pub const TITLE_FONT_COLOR:   Color = [0.0, 0.0, 0.0, 1.0];
+    pub const CONTROLS_FONT_SIZE: u32   = 24;
+  }
+  pub mod options {
     use noframe::color::Color;
     pub const TITLE_FONT_SIZE:  u32   = 32;
+    pub const FONT_SIZE:        u32   = 24;
+    pub const TITLE_BG_COLOR:   Color = [0.5, 0.5, 0.5, 1.0];
+    pub const TITLE_FONT_COLOR: Color = [0.0, 0.0, 0.0, 1.0];
+    pub const SLIDER_COLOR:     Color = [1.0, 1.0, 1.0, 0.4];
+    pub const VOLUME_STEP:      f32   = 0.1;
+    pub const WINDOW_SCALES:    [f32; 5] = [0.5, 0.75, 1.0, 1.25, 1.5];
+  }
+  pub mod controls {
+    use noframe::color::Color;
+    pub const TITLE_FONT_SIZE:  u32   = 32;
+    pub const FONT_SIZE:        u32   = 20;
     pub const TITLE_BG_COLOR:   Color = [0.5, 0.5, 0.5, 1.0];
     pub const TITLE_FONT_COLOR: Color = [0.0, 0.0, 0.0, 1.0];
   }
@@ -133,11 +374,65 @@ pub mod level {
   use ggez::event::Keycode;
   pub const NAME_FONT_SIZE: u32 = 12;
   pub const CENTER_KEY: Keycode = Keycode::C;
-  pub const SKIP_KEY: Keycode = Keycode::N;  // TODO: Temporary! Only for debugging!
+  pub const SKIP_KEY: Keycode = Keycode::N;  // Only in debug builds
+}
+
+pub mod editor {
+  use noframe::color::Color;
+  pub const GRID_SIZE:       f32   = 32.0;
+  pub const INFO_FONT_SIZE:  u32   = 14;
+  pub const GRID_COLOR:      Color = [1.0, 1.0, 1.0, 0.15];
+  pub const SELECTED_COLOR:  Color = [1.0, 1.0, 0.0, 0.4];
+  pub const INVISIBLE_COLOR: Color = [0.5, 0.5, 0.5, 0.5];
+  pub const INFO_BG_COLOR:   Color = [0.0, 0.0, 0.0, 0.7];
+  pub const INFO_FONT_COLOR: Color = [1.0, 1.0, 1.0, 1.0];
+  pub const STRENGTH_STEP:   f32   = 50.0;
+  pub const COLORS: [&'static str; 5] = [ "red", "blue", "green", "yellow", "purple" ];
+  pub const DIALOGUE_SCRIPT: &str = "example";  // Placed DialogueTriggers start this until their `script` is edited
+
+  pub mod controls {
+    use ggez::event::Keycode;
+    pub const PREV_TYPE:     Keycode = Keycode::Q;
+    pub const NEXT_TYPE:     Keycode = Keycode::E;
+    pub const TOGGLE_SNAP:   Keycode = Keycode::G;
+    pub const REMOVE:        Keycode = Keycode::X;
+    pub const ID_UP:         Keycode = Keycode::Equals;
+    pub const ID_DOWN:       Keycode = Keycode::Minus;
+    pub const COLOR:         Keycode = Keycode::V;
+    pub const STATE:         Keycode = Keycode::T;
+    pub const LINK:          Keycode = Keycode::L;
+    pub const STRENGTH_UP:   Keycode = Keycode::RightBracket;
+    pub const STRENGTH_DOWN: Keycode = Keycode::LeftBracket;
+    pub const SAVE:          Keycode = Keycode::F2;
+    pub const PLAY:          Keycode = Keycode::F5;
+    pub const PREV_LEVEL:    Keycode = Keycode::PageUp;
+    pub const NEXT_LEVEL:    Keycode = Keycode::PageDown;
+    pub const TO_TITLE:      Keycode = Keycode::Backspace;
+  }
 }
 
 pub mod level_manager {
-  pub const AUDIO_FORMAT: &str = "ogg";
+  pub const MAIN_PACK_ID:   &str = "main";
+  pub const MAIN_PACK_NAME: &str = "Campaign";
+  pub const CHAPTER_SIZE:           usize = 5;
+  pub const CHAPTER_UNLOCK_CLEARED: usize = 3;  // Cleared levels of a chapter needed to unlock the next chapter
+  pub mod hot_reload {
+    use noframe::color::Color;
+    pub const INTERVAL_MS: u64   = 500;
+    pub const FONT_SIZE:   u32   = 16;
+    pub const BG_COLOR:    Color = [0.6, 0.0, 0.0, 0.85];
+    pub const FONT_COLOR:  Color = [1.0, 1.0, 1.0, 1.0];
+  }
+  pub mod speedrun {
+    use noframe::color::Color;
+    pub const FONT_SIZE:       u32   = 24;
+    pub const SPLIT_FONT_SIZE: u32   = 16;
+    pub const PADDING:         f32   = 8.0;
+    pub const OFFSET_Y:        f32   = 28.0;  // Below the level's name
+    pub const FONT_COLOR:      Color = [1.0, 1.0, 1.0, 1.0];
+    pub const AHEAD_COLOR:     Color = [0.3, 0.9, 0.3, 1.0];  // Split faster than the personal best
+    pub const BEHIND_COLOR:    Color = [0.9, 0.3, 0.3, 1.0];
+  }
   pub const LEVEL_NAMES: [&'static str; 15] = [
     "01_one",
     "02_two",
@@ -168,9 +463,9 @@ pub mod level_manager {
     "ingame_2.fl",
     "ingame_2.fl",
 
-    "ingame_3",  // TODO: ingame_3.fl.ogg is missing
-    "ingame_3",
-    "ingame_3",
+    "ingame_3.fl",  // Falls back to ingame_3 until ingame_3.fl.ogg exists
+    "ingame_3.fl",
+    "ingame_3.fl",
     "ingame_4.fl",
     "ingame_4.fl",
   ];
@@ -198,6 +493,22 @@ pub mod score {
   pub const NEW_HIGHSCORE_COLOR: Color     = [0.1, 0.5, 0.1, 1.0];
 }
 
+pub mod leaderboards {
+  use noframe::color::Color;
+  use noframe::geo::size::Size;
+  pub const FILENAME:        &str  = "leaderboards.json";  // In the user data directory, shared by all profiles
+  pub const MAX_ENTRIES:     usize = 8;
+  pub const FONT_SIZE:       u32   = 14;
+  pub const TITLE_FONT_SIZE: u32   = 20;
+  pub const ROW_HEIGHT:      f32   = 20.0;
+  pub const PADDING:         f32   = 32.0;
+  pub const FONT_COLOR:      Color = [0.0, 0.0, 0.0, 1.0];
+  pub const HEADER_COLOR:    Color = [0.3, 0.3, 0.3, 1.0];
+  pub const HIGHLIGHT_COLOR: Color = [1.0, 0.9, 0.3, 0.6];  // Behind the new entry
+  pub const BG_COLOR:        Color = [0.66, 0.66, 0.66, 0.95];  // In the level select
+  pub const PANEL_SIZE:      Size  = Size { w: 720.0, h: 320.0 };
+}
+
 pub mod color_rect {
   use noframe::color::*;
   pub const DEFAULT_COLOR: Color = BLACK;
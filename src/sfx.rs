@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use ggez::{
+  Context,
+  GameResult,
+  audio::{ SoundData, Source },
+};
+
+use settings::res;
+use settings::sfx::*;
+use config;
+
+// Everything in a level that makes a sound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundEvent {
+  PlayerJump,
+  PlayerLand,
+  JumpPad,
+  Switch,
+  DoorOpen,
+  DoorClose,
+  ChildCommanded,
+  ChildBump,
+  GoalEntered,
+  LevelCleared,
+}
+
+impl SoundEvent {
+  // The sound's filename in `res::SFX`, without the extension
+  pub fn name(&self) -> &'static str {
+    use self::SoundEvent::*;
+    match self {
+      PlayerJump     => "player_jump",
+      PlayerLand     => "player_land",
+      JumpPad        => "jump_pad",
+      Switch         => "switch",
+      DoorOpen       => "door_open",
+      DoorClose      => "door_close",
+      ChildCommanded => "child_commanded",
+      ChildBump      => "child_bump",
+      GoalEntered    => "goal_entered",
+      LevelCleared   => "level_cleared",
+    }
+  }
+}
+
+// Plays sound effects on their own volume channel.
+// Every sound is loaded the first time it is played, into a small pool of sources.
+// A sound whose file is missing is reported once and then stays silent.
+// Nothing plays while the config has `muted` set.
+pub struct Sfx {
+  pools:   HashMap<SoundEvent, Vec<Source>>,
+  missing: Vec<SoundEvent>,
+}
+
+impl Sfx {
+  pub fn new() -> Self {
+    Self {
+      pools:   HashMap::new(),
+      missing: Vec::new(),
+    }
+  }
+
+  pub fn play_all(&mut self, ctx: &mut Context, events: Vec<SoundEvent>) -> GameResult<()> {
+    for event in events {
+      self.play(ctx, event)?;
+    }
+    Ok(())
+  }
+
+  pub fn play(&mut self, ctx: &mut Context, event: SoundEvent) -> GameResult<()> {
+    if config::with( |c| c.muted ) || self.missing.contains(&event) { return Ok(()); }
+    if !self.pools.contains_key(&event) {
+      match new_pool(ctx, event) {
+        Ok(pool) => { self.pools.insert(event, pool); },
+        Err(e)   => {
+          eprintln!("Couldn't load sound '{}': {}", event.name(), e);
+          self.missing.push(event);
+          return Ok(());
+        }
+      }
+    }
+    let volume = config::with( |c| c.sfx_volume );
+    let pool = self.pools.get_mut(&event).expect("Should have sound pool");
+    // If every source is busy, the sound is dropped rather than cutting one off
+    if let Some(source) = pool.iter_mut().find( |source| source.stopped() ) {
+      source.set_volume(volume);
+      source.play()?;
+    }
+    Ok(())
+  }
+}
+
+fn new_pool(ctx: &mut Context, event: SoundEvent) -> GameResult<Vec<Source>> {
+  let data = SoundData::new(ctx, format!("{}{}.{}", res::SFX, event.name(), FORMAT))?;
+  (0 .. POOL_SIZE).map( |_| Source::from_data(ctx, data.clone()) ).collect()
+}
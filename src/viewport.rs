@@ -0,0 +1,72 @@
+use ggez::{
+  Context,
+  GameResult,
+  graphics,
+};
+use noframe::geo::prelude::*;
+use noframe::entity::Entity;
+
+use settings::game::LETTERBOX_COLOR;
+use config::Config;
+use color_rect::ColorRect;
+
+// The game is always laid out and drawn at the virtual resolution, `config.window_size`.
+// The window may have any size: the virtual screen is scaled to fit it and centered,
+// and the space left over on either side is covered with letterbox bars.
+// With integer scaling the scale is rounded down to a whole number, if the window is big enough.
+pub fn apply(ctx: &mut Context, config: &Config) -> GameResult<()> {
+  let rect = screen_rect(&config.window_size, graphics::get_size(ctx), config.integer_scaling);
+  graphics::set_screen_coordinates(ctx, rect)
+}
+
+// The area of virtual coordinates that covers the whole window
+fn screen_rect(virtual_size: &Size, window: (u32, u32), integer_scaling: bool) -> graphics::Rect {
+  let (w, h) = (window.0 as NumType, window.1 as NumType);
+  if w == 0.0 || h == 0.0 {
+    return graphics::Rect::new(0.0, 0.0, virtual_size.w, virtual_size.h);
+  }
+  let mut scale = (w / virtual_size.w).min(h / virtual_size.h);
+  if integer_scaling && scale >= 1.0 {
+    scale = scale.floor();
+  }
+  let size = Size::new(w / scale, h / scale);
+  graphics::Rect::new(
+    (virtual_size.w - size.w) / 2.0,
+    (virtual_size.h - size.h) / 2.0,
+    size.w,
+    size.h
+  )
+}
+
+// Mouse events are in window pixels
+pub fn to_virtual(ctx: &Context, x: i32, y: i32) -> (i32, i32) {
+  let (dx, dy) = to_virtual_rel(ctx, x, y);
+  let screen   = graphics::get_screen_coordinates(ctx);
+  ((screen.x + dx as NumType).round() as i32, (screen.y + dy as NumType).round() as i32)
+}
+
+pub fn to_virtual_rel(ctx: &Context, x: i32, y: i32) -> (i32, i32) {
+  let (w, h) = graphics::get_size(ctx);
+  if w == 0 || h == 0 { return (x, y); }
+  let screen = graphics::get_screen_coordinates(ctx);
+  (
+    (x as NumType * screen.w / w as NumType).round() as i32,
+    (y as NumType * screen.h / h as NumType).round() as i32,
+  )
+}
+
+// Covers everything outside of the virtual screen, where entities may be drawn partially
+pub fn draw_letterbox(ctx: &mut Context, virtual_size: &Size) -> GameResult<()> {
+  let screen = graphics::get_screen_coordinates(ctx);
+  let left   = -screen.x;
+  let top    = -screen.y;
+  if left > 0.0 {
+    ColorRect::new(Point::new(screen.x, screen.y), Size::new(left, screen.h), Origin::TopLeft, LETTERBOX_COLOR).draw(ctx)?;
+    ColorRect::new(Point::new(virtual_size.w, screen.y), Size::new(left, screen.h), Origin::TopLeft, LETTERBOX_COLOR).draw(ctx)?;
+  }
+  if top > 0.0 {
+    ColorRect::new(Point::new(screen.x, screen.y), Size::new(screen.w, top), Origin::TopLeft, LETTERBOX_COLOR).draw(ctx)?;
+    ColorRect::new(Point::new(screen.x, virtual_size.h), Size::new(screen.w, top), Origin::TopLeft, LETTERBOX_COLOR).draw(ctx)?;
+  }
+  Ok(())
+}